@@ -0,0 +1,100 @@
+// File: src-tauri/src/address.rs
+// The schema.org `PostalAddress` parts scraped from detail pages: Dutch postcode validation, the
+// one-line display string shown in the UI and calendars, and reading the parts back out of
+// display strings stored before the parts were kept.
+use regex::Regex;
+use std::sync::LazyLock;
+
+static POSTCODE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^([1-9][0-9]{3})\s*([A-Za-z]{2})$").unwrap());
+
+/// "5611nb", "5611 NB" and " 5611  nb " all become "5611 NB". `None` for anything that isn't a
+/// valid Dutch postcode: the number can't start with 0 and SA, SD and SS are never issued.
+pub fn normalize_postcode(raw: &str) -> Option<String> {
+    let captures = POSTCODE_RE.captures(raw.trim())?;
+    let letters = captures[2].to_uppercase();
+    if ["SA", "SD", "SS"].contains(&letters.as_str()) {
+        return None;
+    }
+    Some(format!("{} {}", &captures[1], letters))
+}
+
+/// The address as one line, e.g. "Van Abbemuseum, Stratumsedijk 2, 5611 NB, Eindhoven".
+/// `None` when there's nothing to show.
+pub fn display_address(
+    venue_name: Option<&str>,
+    street: Option<&str>,
+    postal_code: Option<&str>,
+    locality: Option<&str>,
+) -> Option<String> {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in [venue_name, street, postal_code, locality].into_iter().flatten() {
+        let part = part.trim();
+        if !part.is_empty() && !parts.contains(&part) {
+            parts.push(part);
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+/// Street, postcode and locality read back from a `display_address` line, leaving out the
+/// venue's `names`. Only needed for events stored before the parts were kept separately.
+pub fn parse_display_address(address: &str, names: &[&str]) -> (Option<String>, Option<String>, Option<String>) {
+    let parts: Vec<&str> = address
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty() && !names.iter().any(|name| name.trim().eq_ignore_ascii_case(part)))
+        .collect();
+    match parts.iter().position(|part| normalize_postcode(part).is_some()) {
+        Some(postcode_at) => (
+            postcode_at.checked_sub(1).map(|street_at| parts[street_at].to_string()),
+            normalize_postcode(parts[postcode_at]),
+            parts.get(postcode_at + 1).map(|locality| locality.to_string()),
+        ),
+        None => (parts.first().map(|street| street.to_string()), None, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_valid_postcodes() {
+        assert_eq!(normalize_postcode("5611 NB").as_deref(), Some("5611 NB"));
+        assert_eq!(normalize_postcode("5611nb").as_deref(), Some("5611 NB"));
+        assert_eq!(normalize_postcode(" 5611  Et ").as_deref(), Some("5611 ET"));
+    }
+
+    #[test]
+    fn rejects_invalid_postcodes() {
+        for raw in ["0611 NB", "561 NB", "5611", "5611 N8", "5611 SS", "B-3920", "Eindhoven"] {
+            assert_eq!(normalize_postcode(raw), None, "{}", raw);
+        }
+    }
+
+    #[test]
+    fn display_skips_missing_and_repeated_parts() {
+        assert_eq!(
+            display_address(Some("Van Abbemuseum"), Some("Stratumsedijk 2"), Some("5611 NB"), Some("Eindhoven")).as_deref(),
+            Some("Van Abbemuseum, Stratumsedijk 2, 5611 NB, Eindhoven")
+        );
+        assert_eq!(
+            display_address(Some("Strijp-S"), Some("Strijp-S"), None, Some("Eindhoven")).as_deref(),
+            Some("Strijp-S, Eindhoven")
+        );
+        assert_eq!(display_address(None, Some(" "), None, None), None);
+    }
+
+    #[test]
+    fn parses_stored_display_strings() {
+        assert_eq!(
+            parse_display_address("Stage Music Café, Stratumseind 23, 5611 ET, Eindhoven", &["Stage Music Café"]),
+            (Some("Stratumseind 23".to_string()), Some("5611 ET".to_string()), Some("Eindhoven".to_string()))
+        );
+        assert_eq!(
+            parse_display_address("Muziekgebouw, Heuvel 140, 5611AV, Eindhoven", &["muziekgebouw"]),
+            (Some("Heuvel 140".to_string()), Some("5611 AV".to_string()), Some("Eindhoven".to_string()))
+        );
+        assert_eq!(parse_display_address("Markt 1", &[]), (Some("Markt 1".to_string()), None, None));
+    }
+}
@@ -0,0 +1,474 @@
+// File: src-tauri/src/bin/eindhoven-events.rs
+// Headless front-end to the same scraper and event store the desktop app uses, for cron
+// jobs and scripts on machines without a display.
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::fs;
+#[cfg(feature = "server")]
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use eindhoven_event_viewer_lib::error::AppError;
+use eindhoven_event_viewer_lib::models::{Event, ScrapingProgress};
+use eindhoven_event_viewer_lib::query::{EventPage, SortKey};
+use eindhoven_event_viewer_lib::series::EventSeries;
+use eindhoven_event_viewer_lib::{
+    build_scraping_client, build_scraping_client_with, changes, db, fetch_event_details_batch_with_store,
+    fetch_event_details_with_store, ics, query, scraper, series,
+};
+#[cfg(feature = "server")]
+use eindhoven_event_viewer_lib::server;
+
+#[derive(Parser)]
+#[command(name = "eindhoven-events", version, about = "Scrape, browse and export Eindhoven events")]
+struct Cli {
+    /// Log scraping progress to stderr
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List stored events
+    List {
+        #[command(flatten)]
+        filter: EventFilter,
+        /// Skip this many matching events
+        #[arg(long, default_value_t = 0)]
+        offset: usize,
+        /// Show at most this many events
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Show the dates of a recurring event as one entry; offset and limit then count entries
+        #[arg(long)]
+        group: bool,
+    },
+    /// Show one stored event, fetching its details if they are missing or stale
+    Show {
+        /// Event id as printed by `list`, e.g. /en/events/dutch-design-week-2025
+        id: String,
+    },
+    /// Export stored events as an iCalendar (.ics) file
+    ExportIcs {
+        #[command(flatten)]
+        filter: EventFilter,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Export the dates of a recurring event as one repeating calendar event
+        #[arg(long)]
+        group: bool,
+    },
+    /// Export stored events as JSON
+    ExportJson {
+        #[command(flatten)]
+        filter: EventFilter,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Scrape the event listings and store the results
+    Refresh {
+        /// Only scrape this many listing pages (removed events are then not detected)
+        #[arg(long)]
+        pages: Option<u32>,
+        /// Also scrape the Dutch listing and add the Dutch titles and descriptions
+        #[arg(long)]
+        dutch: bool,
+        /// Also fetch the detail page of every listed event
+        #[arg(long)]
+        details: bool,
+        /// Detail pages fetched in parallel
+        #[arg(long)]
+        concurrency: Option<usize>,
+        /// Maximum requests per second per host
+        #[arg(long)]
+        requests_per_second: Option<f64>,
+        /// Least seconds between two requests to the same host; a longer robots.txt Crawl-delay wins
+        #[arg(long)]
+        delay: Option<f64>,
+    },
+    /// Serve the stored events as a subscribable calendar feed at /calendar.ics
+    #[cfg(feature = "server")]
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = server::DEFAULT_BIND_ADDRESS)]
+        bind: SocketAddr,
+    },
+}
+
+#[derive(Args)]
+struct EventFilter {
+    /// Only events starting on or after this date (YYYY-MM-DD or YYYY-MM-DDTHH:MM:SS)
+    #[arg(long, value_parser = parse_from_arg)]
+    from: Option<NaiveDateTime>,
+    /// Only events starting on or before this date (YYYY-MM-DD or YYYY-MM-DDTHH:MM:SS)
+    #[arg(long, value_parser = parse_to_arg)]
+    to: Option<NaiveDateTime>,
+    /// Only events at venues whose name contains this text
+    #[arg(long)]
+    venue: Option<String>,
+    /// Only events whose title, description or venue contains this text
+    #[arg(long)]
+    search: Option<String>,
+    /// Only free events
+    #[arg(long)]
+    free: bool,
+    /// Only events whose cheapest ticket costs at least this much (in euros)
+    #[arg(long)]
+    min_price: Option<f64>,
+    /// Only events whose cheapest ticket costs at most this much (in euros)
+    #[arg(long)]
+    max_price: Option<f64>,
+    /// Only events with map coordinates
+    #[arg(long)]
+    has_coordinates: bool,
+    /// Only events with a ticket link
+    #[arg(long)]
+    has_tickets: bool,
+    /// Order of the events
+    #[arg(long, value_enum, default_value_t = Sort::DateAsc)]
+    sort: Sort,
+}
+
+/// `query::SortKey` as a command line value.
+#[derive(Clone, Copy, ValueEnum)]
+enum Sort {
+    /// Soonest first, undated events last
+    DateAsc,
+    /// Latest first, undated events last
+    DateDesc,
+    /// Cheapest first, events without a known price last
+    PriceAsc,
+    /// Most expensive first, events without a known price last
+    PriceDesc,
+    Title,
+}
+
+impl From<Sort> for SortKey {
+    fn from(sort: Sort) -> Self {
+        match sort {
+            Sort::DateAsc => SortKey::DateAsc,
+            Sort::DateDesc => SortKey::DateDesc,
+            Sort::PriceAsc => SortKey::PriceAsc,
+            Sort::PriceDesc => SortKey::PriceDesc,
+            Sort::Title => SortKey::Title,
+        }
+    }
+}
+
+impl EventFilter {
+    fn query(&self, offset: usize, limit: Option<usize>) -> Result<EventPage, AppError> {
+        let filter = query::EventFilter {
+            from: self.from,
+            to: self.to,
+            min_price: self.min_price,
+            max_price: self.max_price,
+            free_only: self.free,
+            venue: self.venue.clone(),
+            text: self.search.clone(),
+            has_coordinates: self.has_coordinates,
+            has_tickets: self.has_tickets,
+        };
+        let conn = db::open()?;
+        query::query_events(&conn, &filter, self.sort.into(), offset, limit)
+    }
+
+    fn stored_events(&self) -> Result<Vec<Event>, AppError> {
+        self.query(0, None).map(|page| page.events)
+    }
+}
+
+fn parse_datetime_arg(value: &str, time_for_date: NaiveTime) -> Result<NaiveDateTime, String> {
+    if let Ok(datetime) = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S") {
+        return Ok(datetime);
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|date| date.and_time(time_for_date))
+        .map_err(|_| format!("'{}' is not a date (YYYY-MM-DD) or date and time (YYYY-MM-DDTHH:MM:SS)", value))
+}
+
+fn parse_from_arg(value: &str) -> Result<NaiveDateTime, String> {
+    parse_datetime_arg(value, NaiveTime::MIN)
+}
+
+// A bare date as the end of a range includes that whole day.
+fn parse_to_arg(value: &str) -> Result<NaiveDateTime, String> {
+    parse_datetime_arg(value, NaiveTime::from_hms_opt(23, 59, 59).unwrap_or(NaiveTime::MIN))
+}
+
+fn write_output(output: Option<&Path>, contents: &str) -> Result<(), AppError> {
+    match output {
+        Some(path) => fs::write(path, contents).map_err(|e| AppError::Io {
+            context: format!("write {:?}", path),
+            message: e.to_string(),
+        }),
+        None => {
+            print!("{}", contents);
+            Ok(())
+        }
+    }
+}
+
+fn log_progress(progress: ScrapingProgress) {
+    log::info!("{}", progress.message);
+}
+
+fn format_when(event: &Event) -> String {
+    if event.all_day {
+        match (event.start_date, event.end_date) {
+            (Some(start), Some(end)) if end > start => format!("{} – {}", start, end),
+            (Some(start), _) => start.to_string(),
+            _ => "date unknown".to_string(),
+        }
+    } else if let Some(start) = event.start_datetime {
+        match event.end_datetime {
+            Some(end) if end.date() == start.date() => format!("{} – {}", start.format("%Y-%m-%d %H:%M"), end.format("%H:%M")),
+            Some(end) => format!("{} – {}", start.format("%Y-%m-%d %H:%M"), end.format("%Y-%m-%d %H:%M")),
+            None => start.format("%Y-%m-%d %H:%M").to_string(),
+        }
+    } else {
+        event
+            .list_date
+            .clone()
+            .or_else(|| event.date_time_summary.clone())
+            .unwrap_or_else(|| "date unknown".to_string())
+    }
+}
+
+fn venue_name(event: &Event) -> Option<&str> {
+    event
+        .specific_location_name
+        .as_deref()
+        .or(event.list_specific_location.as_deref())
+}
+
+fn list(filter: EventFilter, offset: usize, limit: Option<usize>) -> Result<(), AppError> {
+    let page = filter.query(offset, limit)?;
+    if page.total == 0 {
+        eprintln!("No stored events match. Run `eindhoven-events refresh` to scrape the listings.");
+        return Ok(());
+    }
+    for event in &page.events {
+        println!(
+            "{}  {}{}\n    {}",
+            format_when(event),
+            event.title,
+            venue_name(event).map(|venue| format!(" @ {}", venue)).unwrap_or_default(),
+            event.id
+        );
+    }
+    if page.events.len() == page.total {
+        eprintln!("{} events", page.total);
+    } else {
+        eprintln!("{} of {} events", page.events.len(), page.total);
+    }
+    Ok(())
+}
+
+fn list_series(filter: EventFilter, offset: usize, limit: Option<usize>) -> Result<(), AppError> {
+    let grouped = series::group_into_series(filter.stored_events()?);
+    if grouped.is_empty() {
+        eprintln!("No stored events match. Run `eindhoven-events refresh` to scrape the listings.");
+        return Ok(());
+    }
+    let shown: Vec<&EventSeries> = grouped.iter().skip(offset).take(limit.unwrap_or(usize::MAX)).collect();
+    for one_series in &shown {
+        let first = &one_series.occurrences[0];
+        let dates = match one_series.occurrences.len() {
+            1 => String::new(),
+            count => format!("  ({} dates)", count),
+        };
+        println!(
+            "{}  {}{}{}",
+            format_when(first),
+            one_series.title,
+            venue_name(first).map(|venue| format!(" @ {}", venue)).unwrap_or_default(),
+            dates
+        );
+        if one_series.is_recurring() {
+            let later: Vec<String> = one_series.occurrences[1..].iter().map(format_when).collect();
+            println!("    also {}", later.join(", "));
+        }
+        println!("    {}", one_series.id);
+    }
+    let event_count: usize = grouped.iter().map(|one_series| one_series.occurrences.len()).sum();
+    if shown.len() == grouped.len() {
+        eprintln!("{} events in {} entries", event_count, grouped.len());
+    } else {
+        eprintln!("{} of {} entries ({} events)", shown.len(), grouped.len(), event_count);
+    }
+    Ok(())
+}
+
+fn show(id: &str) -> Result<(), AppError> {
+    let conn = db::open()?;
+    let Some(stored) = db::find_event(&conn, id)? else {
+        eprintln!("Run `eindhoven-events refresh` to scrape the listings.");
+        return Err(AppError::NotFound {
+            what: format!("Stored event '{}'", id),
+        });
+    };
+    drop(conn);
+
+    let event = match build_scraping_client().and_then(|client| fetch_event_details_with_store(&client, stored.clone())) {
+        Ok(detailed) => detailed,
+        Err(e) => {
+            eprintln!("Could not fetch details, showing the stored summary: {}", e);
+            stored
+        }
+    };
+
+    println!("{}", event.title);
+    println!("When:     {}", format_when(&event));
+    let fields = [
+        ("Venue:", venue_name(&event)),
+        ("Address:", event.address.as_deref()),
+        ("Price:", event.price.as_deref().or(event.list_price.as_deref())),
+        ("Tickets:", event.ticket_url.as_deref()),
+        ("URL:", event.full_url.as_deref()),
+    ];
+    for (label, value) in fields {
+        if let Some(value) = value {
+            println!("{:<9} {}", label, value);
+        }
+    }
+    if let Some(description) = event.full_description.as_deref().or(event.short_description.as_deref()) {
+        println!("\n{}", description);
+    }
+    Ok(())
+}
+
+fn export_ics(filter: EventFilter, output: Option<PathBuf>, group: bool) -> Result<(), AppError> {
+    let events = filter.stored_events()?;
+    let export = if group {
+        ics::export_series_calendar(&series::group_into_series(events))?
+    } else {
+        ics::export_calendar(&events)?
+    };
+    write_output(output.as_deref(), &export.ics)?;
+    for skipped in &export.skipped {
+        eprintln!("Skipped '{}': {}", skipped.title, skipped.reason);
+    }
+    eprintln!("Exported {} events ({} skipped).", export.exported_count, export.skipped.len());
+    Ok(())
+}
+
+fn export_json(filter: EventFilter, output: Option<PathBuf>) -> Result<(), AppError> {
+    let events = filter.stored_events()?;
+    let json = serde_json::to_string_pretty(&events)
+        .map_err(|e| AppError::internal(format!("Failed to serialize events: {}", e)))?;
+    write_output(output.as_deref(), &(json + "\n"))?;
+    eprintln!("Exported {} events.", events.len());
+    Ok(())
+}
+
+fn refresh(
+    pages: Option<u32>,
+    dutch: bool,
+    details: bool,
+    concurrency: Option<usize>,
+    requests_per_second: Option<f64>,
+    delay: Option<f64>,
+) -> Result<(), AppError> {
+    let mut policy = scraper::CrawlPolicy::default();
+    if let Some(delay) = delay {
+        policy.min_delay = Duration::try_from_secs_f64(delay).map_err(|_| AppError::InvalidRequest {
+            message: format!("--delay must be a number of seconds, not {}", delay),
+        })?;
+    }
+    if let Some(requests_per_second) = requests_per_second {
+        policy.limit_requests_per_second(requests_per_second);
+    }
+    let client = build_scraping_client_with(policy)?;
+    let registry = scraper::SourceRegistry::with_default_sources().with_translations(dutch);
+    let report = scraper::fetch_event_list_summaries(&client, &registry, pages, true, &scraper::CancelToken::default(), log_progress)?;
+    let summary = changes::EventsChanged::new(report.changes);
+    println!(
+        "Stored {} events: {} added, {} changed, {} removed.",
+        report.events.len(),
+        summary.added,
+        summary.changed,
+        summary.removed
+    );
+    for failed in &report.failed_pages {
+        eprintln!("Failed to scrape {}: {}", failed.url, failed.error);
+    }
+    if !report.failed_pages.is_empty() {
+        eprintln!("{} listing pages failed; their events were not updated.", report.failed_pages.len());
+    }
+
+    if details {
+        let mut options = scraper::DetailBatchOptions::default();
+        if let Some(concurrency) = concurrency {
+            options.concurrency = concurrency;
+        }
+        let batch = fetch_event_details_batch_with_store(&client, report.events, &options, log_progress)?;
+        println!(
+            "Fetched details for {} events ({} failed).",
+            batch.detailed_count(),
+            batch.failed_ids.len()
+        );
+        for id in &batch.failed_ids {
+            eprintln!("Failed to fetch details for {}", id);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "server")]
+fn serve(bind: SocketAddr) -> Result<(), AppError> {
+    let db_path = db::default_path()?;
+    // Create and migrate the database up front so a bad path fails here, not per request.
+    db::open()?;
+    let runtime = tokio::runtime::Runtime::new()
+        .map_err(|e| AppError::internal(format!("Failed to start async runtime: {}", e)))?;
+    eprintln!("Serving http://{}/calendar.ics (Ctrl+C to stop)", bind);
+    runtime.block_on(server::serve(bind, db_path))
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let default_filter = if cli.verbose { "info" } else { "warn" };
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(default_filter)).init();
+
+    let result = match cli.command {
+        Command::List {
+            filter,
+            offset,
+            limit,
+            group,
+        } => {
+            if group {
+                list_series(filter, offset, limit)
+            } else {
+                list(filter, offset, limit)
+            }
+        }
+        Command::Show { id } => show(&id),
+        Command::ExportIcs { filter, output, group } => export_ics(filter, output, group),
+        Command::ExportJson { filter, output } => export_json(filter, output),
+        Command::Refresh {
+            pages,
+            dutch,
+            details,
+            concurrency,
+            requests_per_second,
+            delay,
+        } => refresh(pages, dutch, details, concurrency, requests_per_second, delay),
+        #[cfg(feature = "server")]
+        Command::Serve { bind } => serve(bind),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
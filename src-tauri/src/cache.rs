@@ -1,10 +1,16 @@
 use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
-use chrono::{Utc, DateTime, Duration};
+use std::path::{Path, PathBuf};
+use chrono::{Utc, DateTime};
 
-const CACHE_FILE_NAME: &str = "events_cache.json";
-const CACHE_LIFETIME_HOURS: i64 = 1; // Cache valid for 1 hour
+use crate::error::AppError;
+use crate::models::Event;
+
+// Events now live in the SQLite store (see db.rs). These JSON files are what earlier
+// versions wrote; they are only read once to import them into the database.
+const LEGACY_LIST_CACHE_FILE_NAME: &str = "events_cache.json";
+const LEGACY_DETAIL_CACHE_FILE_NAME: &str = "event_details_cache.json";
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CacheEntry<T> {
@@ -12,71 +18,96 @@ pub struct CacheEntry<T> {
     pub data: T,
 }
 
-impl<T: Serialize + for<'de> Deserialize<'de>> CacheEntry<T> {
-    pub fn new(data: T) -> Self {
-        CacheEntry {
-            timestamp: Utc::now(),
-            data,
-        }
-    }
-
-    pub fn is_fresh(&self) -> bool {
-        Utc::now().signed_duration_since(self.timestamp) < Duration::hours(CACHE_LIFETIME_HOURS)
-    }
-}
+/// Detailed events keyed by `Event.id`, as stored in the legacy detail cache file.
+pub type DetailCache = HashMap<String, CacheEntry<Event>>;
 
-pub fn get_cache_path() -> Result<PathBuf, String> {
+pub fn get_cache_path(file_name: &str) -> Result<PathBuf, AppError> {
     // Use tauri::api::path::app_data_dir() for a platform-appropriate cache directory
     // For simplicity, let's use a temporary directory or current working directory for now.
     // In a real Tauri app, you'd use tauri::api::path::app_data_dir().
     // For this CLI context, let's use a subdirectory in the current working directory.
-    let mut path = std::env::current_dir().map_err(|e| format!("Failed to get current directory: {}", e))?;
+    let mut path = std::env::current_dir().map_err(|e| AppError::cache("get current directory", e))?;
     path.push("cache");
     if !path.exists() {
-        fs::create_dir_all(&path).map_err(|e| format!("Failed to create cache directory: {}", e))?;
+        fs::create_dir_all(&path).map_err(|e| AppError::cache("create cache directory", e))?;
     }
-    path.push(CACHE_FILE_NAME);
+    path.push(file_name);
     Ok(path)
 }
 
-pub fn read_cache<T: Serialize + for<'de> Deserialize<'de>>() -> Option<CacheEntry<T>> {
-    let cache_path = match get_cache_path() {
-        Ok(path) => path,
-        Err(e) => {
-            log::error!("Failed to get cache path: {}", e);
-            return None;
-        }
-    };
-
-    if cache_path.exists() {
-        match fs::read_to_string(&cache_path) {
-            Ok(contents) => match serde_json::from_str(&contents) {
-                Ok(entry) => {
-                    log::info!("Cache read successfully from {:?}", cache_path);
-                    Some(entry)
-                },
-                Err(e) => {
-                    log::error!("Failed to deserialize cache: {}. Deleting corrupted cache.", e);
-                    // Optionally delete corrupted cache file
-                    let _ = fs::remove_file(&cache_path);
-                    None
-                }
-            },
+fn read_legacy_file<T: for<'de> Deserialize<'de>>(cache_path: PathBuf) -> Option<(PathBuf, T)> {
+    if !cache_path.exists() {
+        return None;
+    }
+    match fs::read_to_string(&cache_path) {
+        Ok(contents) => match serde_json::from_str(&contents) {
+            Ok(data) => Some((cache_path, data)),
             Err(e) => {
-                log::error!("Failed to read cache file {:?}: {}", cache_path, e);
+                log::error!("Failed to deserialize legacy cache {:?}: {}. Skipping import.", cache_path, e);
+                rename_imported_file(&cache_path);
                 None
             }
+        },
+        Err(e) => {
+            log::error!("Failed to read legacy cache file {:?}: {}", cache_path, e);
+            None
         }
-    } else {
-        log::info!("Cache file does not exist at {:?}", cache_path);
-        None
     }
 }
 
-pub fn write_cache<T: Serialize + for<'de> Deserialize<'de>>(entry: &CacheEntry<T>) -> Result<(), String> {
-    let cache_path = get_cache_path()?;
-    let contents = serde_json::to_string_pretty(entry).map_err(|e| format!("Failed to serialize cache: {}", e))?;
-    fs::write(&cache_path, contents).map_err(|e| format!("Failed to write cache file {:?}: {}", cache_path, e))?;
-    log::info!("Cache written successfully to {:?}", cache_path);
-    Ok(())
+/// Contents of the legacy JSON cache files that still need importing.
+#[derive(Default)]
+pub struct LegacyCaches {
+    pub list: Option<CacheEntry<Vec<Event>>>,
+    pub details: DetailCache,
+    files: Vec<PathBuf>,
+}
+
+impl LegacyCaches {
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Moves the imported files out of the way so they are only imported once. They are
+    /// renamed rather than deleted so the original data survives a botched import.
+    pub fn mark_imported(self) {
+        for cache_path in &self.files {
+            rename_imported_file(cache_path);
+        }
+    }
+}
+
+fn rename_imported_file(cache_path: &PathBuf) {
+    let mut imported_path = cache_path.clone().into_os_string();
+    imported_path.push(".imported");
+    if let Err(e) = fs::rename(cache_path, &imported_path) {
+        log::error!("Failed to rename imported cache file {:?}: {}", cache_path, e);
+    }
+}
+
+/// Reads the legacy summary and detail cache files, if present.
+pub fn read_legacy_caches() -> LegacyCaches {
+    match get_cache_path(LEGACY_LIST_CACHE_FILE_NAME) {
+        Ok(path) => read_legacy_caches_in(path.parent().unwrap_or(&path)),
+        Err(e) => {
+            log::error!("Failed to get cache path: {}", e);
+            LegacyCaches::default()
+        }
+    }
+}
+
+/// `read_legacy_caches` for the cache files in `dir`.
+pub fn read_legacy_caches_in(dir: &Path) -> LegacyCaches {
+    let mut legacy = LegacyCaches::default();
+    if let Some((path, entry)) = read_legacy_file::<CacheEntry<Vec<Event>>>(dir.join(LEGACY_LIST_CACHE_FILE_NAME)) {
+        log::info!("Found {} events in legacy cache {:?}", entry.data.len(), path);
+        legacy.list = Some(entry);
+        legacy.files.push(path);
+    }
+    if let Some((path, entries)) = read_legacy_file::<DetailCache>(dir.join(LEGACY_DETAIL_CACHE_FILE_NAME)) {
+        log::info!("Found {} detailed events in legacy cache {:?}", entries.len(), path);
+        legacy.details = entries;
+        legacy.files.push(path);
+    }
+    legacy
 }
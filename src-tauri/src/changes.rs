@@ -0,0 +1,237 @@
+// File: src-tauri/src/changes.rs
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::models::Event;
+
+// Fields that aren't part of what the site publishes about an event, or are derived from
+// fields that are (a price change already shows up as `price`, a venue change as the location).
+const IGNORED_FIELDS: &[&str] = &["detail_page_content", "parsed_price", "venue_id", "field_sources", "series_id"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Changed,
+    Removed,
+}
+
+impl ChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "added",
+            ChangeKind::Changed => "changed",
+            ChangeKind::Removed => "removed",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Option<ChangeKind> {
+        match kind {
+            "added" => Some(ChangeKind::Added),
+            "changed" => Some(ChangeKind::Changed),
+            "removed" => Some(ChangeKind::Removed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old_value: Value,
+    pub new_value: Value,
+}
+
+/// What happened to one event between two scrapes. `fields` is only filled for `Changed`.
+#[derive(Debug, Serialize, Clone)]
+pub struct EventChange {
+    pub run_id: i64,
+    pub detected_at: DateTime<Utc>,
+    pub event_id: String,
+    pub title: String,
+    pub kind: ChangeKind,
+    pub fields: Vec<FieldChange>,
+}
+
+/// Payload of the `events_changed` Tauri event.
+#[derive(Debug, Serialize, Clone)]
+pub struct EventsChanged {
+    pub added: u32,
+    pub changed: u32,
+    pub removed: u32,
+    pub changes: Vec<EventChange>,
+}
+
+impl EventsChanged {
+    pub fn new(changes: Vec<EventChange>) -> Self {
+        let count = |kind: ChangeKind| changes.iter().filter(|c| c.kind == kind).count() as u32;
+        EventsChanged {
+            added: count(ChangeKind::Added),
+            changed: count(ChangeKind::Changed),
+            removed: count(ChangeKind::Removed),
+            changes,
+        }
+    }
+}
+
+fn event_fields(event: &Event) -> BTreeMap<String, Value> {
+    match serde_json::to_value(event) {
+        Ok(Value::Object(map)) => map
+            .into_iter()
+            .filter(|(field, _)| !IGNORED_FIELDS.contains(&field.as_str()))
+            .collect(),
+        _ => BTreeMap::new(),
+    }
+}
+
+fn field_changes(previous: &Event, current: &Event) -> Vec<FieldChange> {
+    let old_fields = event_fields(previous);
+    let mut new_fields = event_fields(current);
+    let mut changes = Vec::new();
+    for (field, old_value) in old_fields {
+        let new_value = new_fields.remove(&field).unwrap_or(Value::Null);
+        if old_value != new_value {
+            changes.push(FieldChange {
+                field,
+                old_value,
+                new_value,
+            });
+        }
+    }
+    // Fields only the new version has (e.g. added to the model since the last scrape).
+    for (field, new_value) in new_fields {
+        if !new_value.is_null() {
+            changes.push(FieldChange {
+                field,
+                old_value: Value::Null,
+                new_value,
+            });
+        }
+    }
+    changes
+}
+
+/// Compares two scrapes by `Event.id`. Removals are only reported when `detect_removals`
+/// is set, i.e. when `current` is a complete listing rather than a page-limited one.
+pub fn diff_events(
+    previous: &[Event],
+    current: &[Event],
+    detect_removals: bool,
+    run_id: i64,
+    detected_at: DateTime<Utc>,
+) -> Vec<EventChange> {
+    let previous_by_id: HashMap<&str, &Event> = previous.iter().map(|e| (e.id.as_str(), e)).collect();
+    let current_ids: HashMap<&str, &Event> = current.iter().map(|e| (e.id.as_str(), e)).collect();
+    let change = |event: &Event, kind: ChangeKind, fields: Vec<FieldChange>| EventChange {
+        run_id,
+        detected_at,
+        event_id: event.id.clone(),
+        title: event.title.clone(),
+        kind,
+        fields,
+    };
+
+    let mut changes = Vec::new();
+    for event in current {
+        match previous_by_id.get(event.id.as_str()) {
+            None => changes.push(change(event, ChangeKind::Added, Vec::new())),
+            Some(previous_event) => {
+                let fields = field_changes(previous_event, event);
+                if !fields.is_empty() {
+                    changes.push(change(event, ChangeKind::Changed, fields));
+                }
+            }
+        }
+    }
+    if detect_removals {
+        for event in previous {
+            if !current_ids.contains_key(event.id.as_str()) {
+                changes.push(change(event, ChangeKind::Removed, Vec::new()));
+            }
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(id: &str, title: &str) -> Event {
+        Event {
+            id: id.to_string(),
+            title: title.to_string(),
+            source: "thisiseindhoven".to_string(),
+            ..Event::default()
+        }
+    }
+
+    #[test]
+    fn reports_added_changed_and_removed_events() {
+        let previous = vec![event("/en/events/jazz", "Jazz"), event("/en/events/open", "Open Air")];
+        let mut renamed = event("/en/events/jazz", "Jazz Night");
+        renamed.list_price = Some("€ 12,50".to_string());
+        let current = vec![renamed, event("/en/events/cinema", "Cinema")];
+        let now = Utc::now();
+
+        let changes = diff_events(&previous, &current, true, 7, now);
+        let kinds: Vec<(&str, ChangeKind)> = changes.iter().map(|c| (c.event_id.as_str(), c.kind)).collect();
+        assert_eq!(
+            kinds,
+            [
+                ("/en/events/jazz", ChangeKind::Changed),
+                ("/en/events/cinema", ChangeKind::Added),
+                ("/en/events/open", ChangeKind::Removed),
+            ]
+        );
+        assert!(changes.iter().all(|c| c.run_id == 7 && c.detected_at == now));
+        assert_eq!(changes[0].title, "Jazz Night");
+        assert_eq!(
+            changes[0].fields,
+            [
+                FieldChange {
+                    field: "list_price".to_string(),
+                    old_value: Value::Null,
+                    new_value: json!("€ 12,50"),
+                },
+                FieldChange {
+                    field: "title".to_string(),
+                    old_value: json!("Jazz"),
+                    new_value: json!("Jazz Night"),
+                },
+            ]
+        );
+        assert!(changes[1].fields.is_empty());
+
+        // A page-limited scrape didn't see every event, so none are reported removed.
+        let partial = diff_events(&previous, &current, false, 8, now);
+        assert!(partial.iter().all(|c| c.kind != ChangeKind::Removed));
+    }
+
+    #[test]
+    fn ignored_fields_are_not_changes() {
+        let previous = event("/en/events/summer-art-club/9-july", "Summer Art Club");
+        let mut current = previous.clone();
+        current.detail_page_content = Some("<html></html>".to_string());
+        current.venue_id = Some(3);
+        current.series_id = Some("/en/events/summer-art-club".to_string());
+        current.set_field_source("title", crate::models::FieldSource::JsonLd);
+        assert!(diff_events(std::slice::from_ref(&previous), std::slice::from_ref(&current), true, 1, Utc::now()).is_empty());
+
+        current.full_description = Some("Painting in the garden.".to_string());
+        let changes = diff_events(&[previous], &[current], true, 1, Utc::now());
+        let fields: Vec<&str> = changes[0].fields.iter().map(|f| f.field.as_str()).collect();
+        assert_eq!(fields, ["full_description"]);
+    }
+
+    #[test]
+    fn change_kinds_round_trip() {
+        for kind in [ChangeKind::Added, ChangeKind::Changed, ChangeKind::Removed] {
+            assert_eq!(ChangeKind::parse(kind.as_str()), Some(kind));
+        }
+        assert_eq!(ChangeKind::parse("renamed"), None);
+    }
+}
@@ -0,0 +1,1239 @@
+// File: src-tauri/src/db.rs
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::cache;
+use crate::error::AppError;
+use crate::changes::{self, ChangeKind, EventChange, FieldChange};
+use crate::models::{Event, EventTranslation, Venue};
+use crate::price::Price;
+use crate::series;
+use crate::venue::{self, VenueDetails};
+
+const DB_FILE_NAME: &str = "events.db";
+const SUMMARY_LIFETIME_HOURS: i64 = 1; // A scrape is reused for 1 hour
+const DETAIL_LIFETIME_HOURS: i64 = 24; // Detail pages change far less often than the listing
+
+const RUN_STATUS_COMPLETED: &str = "completed";
+const RUN_STATUS_PARTIAL: &str = "partial";
+const RUN_STATUS_CANCELLED: &str = "cancelled";
+const RUN_STATUS_FAILED: &str = "failed";
+const RUN_STATUS_IMPORTED: &str = "imported";
+
+/// Schema migrations, applied in order. `PRAGMA user_version` records how many have run,
+/// so new migrations must only ever be appended.
+const MIGRATIONS: &[&str] = &[
+    // 1: initial schema
+    r#"
+    CREATE TABLE scrape_runs (
+        id INTEGER PRIMARY KEY,
+        started_at TEXT NOT NULL,
+        finished_at TEXT,
+        status TEXT NOT NULL,
+        pages_scraped INTEGER NOT NULL DEFAULT 0,
+        event_count INTEGER NOT NULL DEFAULT 0,
+        error TEXT
+    );
+
+    CREATE TABLE venues (
+        id INTEGER PRIMARY KEY,
+        name TEXT NOT NULL UNIQUE,
+        address TEXT,
+        latitude REAL,
+        longitude REAL
+    );
+
+    CREATE TABLE events (
+        id TEXT PRIMARY KEY,
+        source TEXT NOT NULL,
+        title TEXT NOT NULL,
+        start_datetime TEXT,
+        end_datetime TEXT,
+        venue_id INTEGER REFERENCES venues(id),
+        data TEXT NOT NULL,
+        first_seen_run_id INTEGER REFERENCES scrape_runs(id),
+        last_seen_run_id INTEGER REFERENCES scrape_runs(id),
+        updated_at TEXT NOT NULL,
+        details_fetched_at TEXT
+    );
+
+    CREATE INDEX idx_events_start_datetime ON events(start_datetime);
+    CREATE INDEX idx_events_venue_id ON events(venue_id);
+    CREATE INDEX idx_events_last_seen_run_id ON events(last_seen_run_id);
+    "#,
+    // 2: change detection between scrapes
+    r#"
+    ALTER TABLE events ADD COLUMN removed_at TEXT;
+
+    CREATE TABLE event_changes (
+        id INTEGER PRIMARY KEY,
+        run_id INTEGER NOT NULL REFERENCES scrape_runs(id),
+        detected_at TEXT NOT NULL,
+        event_id TEXT NOT NULL,
+        title TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        field TEXT,
+        old_value TEXT,
+        new_value TEXT
+    );
+
+    CREATE INDEX idx_event_changes_detected_at ON event_changes(detected_at);
+    "#,
+    // 3: venues matched by normalized name, with address parts and aliases. The old venues
+    // are cleared here and rebuilt from the stored events in the same transaction (see
+    // `rebuild_venues`).
+    r#"
+    ALTER TABLE venues ADD COLUMN normalized_name TEXT;
+    ALTER TABLE venues ADD COLUMN street TEXT;
+    ALTER TABLE venues ADD COLUMN postal_code TEXT;
+    ALTER TABLE venues ADD COLUMN locality TEXT;
+
+    CREATE TABLE venue_aliases (
+        normalized_alias TEXT PRIMARY KEY,
+        venue_id INTEGER NOT NULL REFERENCES venues(id),
+        alias TEXT NOT NULL
+    );
+
+    UPDATE events SET venue_id = NULL;
+    DELETE FROM venues;
+    CREATE UNIQUE INDEX idx_venues_normalized_name ON venues(normalized_name);
+    "#,
+    // 4: validators of fetched pages, for conditional requests
+    r#"
+    CREATE TABLE http_pages (
+        url TEXT PRIMARY KEY,
+        etag TEXT,
+        last_modified TEXT,
+        body TEXT NOT NULL,
+        fetched_at TEXT NOT NULL
+    );
+    "#,
+];
+
+/// The language of the sources' main listings, which every scrape covers.
+const LISTING_LANGUAGE: &str = "en";
+
+/// The migration (1-based) whose transaction also runs `rebuild_venues`.
+const VENUE_REBUILD_MIGRATION: usize = 3;
+
+/// How much of the listing a scrape covered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrapeCoverage {
+    /// Every page of every listing.
+    Complete,
+    /// Every page of every listing, but none of the translated ones: events only listed in
+    /// another language were not looked for, so they are not marked removed.
+    Untranslated,
+    /// Page-limited, or some pages failed.
+    Partial,
+    /// Stopped early through its `CancelToken`.
+    Cancelled,
+}
+
+impl ScrapeCoverage {
+    fn run_status(self) -> &'static str {
+        match self {
+            ScrapeCoverage::Complete | ScrapeCoverage::Untranslated => RUN_STATUS_COMPLETED,
+            ScrapeCoverage::Partial => RUN_STATUS_PARTIAL,
+            ScrapeCoverage::Cancelled => RUN_STATUS_CANCELLED,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ScrapeRun {
+    pub id: i64,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub status: String,
+    pub pages_scraped: u32,
+    pub event_count: u32,
+    pub error: Option<String>,
+}
+
+/// A fetched page and the `ETag`/`Last-Modified` validators it came with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredPage {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub body: String,
+}
+
+/// What `events_changed_since` found.
+#[derive(Debug, Default)]
+pub struct ChangedEvents {
+    /// Added or updated events that are still listed.
+    pub listed: Vec<Event>,
+    pub removed_ids: Vec<String>,
+    /// The newest write among them; pass it as `since` next time.
+    pub until: Option<DateTime<Utc>>,
+}
+
+fn sql_error(context: &'static str) -> impl Fn(rusqlite::Error) -> AppError {
+    move |e| AppError::cache(context, e)
+}
+
+/// Where `open()` keeps the database.
+pub fn default_path() -> Result<PathBuf, AppError> {
+    cache::get_cache_path(DB_FILE_NAME)
+}
+
+/// Opens the app's database in the cache directory, migrating it and importing any
+/// legacy JSON cache files on first use.
+pub fn open() -> Result<Connection, AppError> {
+    let db_path = default_path()?;
+    let mut conn = open_at(&db_path)?;
+    import_legacy_caches(&mut conn, cache::read_legacy_caches())?;
+    Ok(conn)
+}
+
+/// The database scrapes and detail fetches store into.
+#[derive(Debug, Clone, Copy)]
+pub enum Store<'a> {
+    /// The app's database, see `open`. The default search index follows this one.
+    App,
+    /// The database at a path, see `open_at`.
+    At(&'a Path),
+}
+
+impl Store<'_> {
+    pub fn open(self) -> Result<Connection, AppError> {
+        match self {
+            Store::App => open(),
+            Store::At(path) => open_at(path),
+        }
+    }
+}
+
+/// Opens (or creates) a database at `path` and brings its schema up to date.
+pub fn open_at(path: &Path) -> Result<Connection, AppError> {
+    let mut conn = Connection::open(path).map_err(|e| AppError::cache(format!("open {:?}", path), e))?;
+    conn.busy_timeout(std::time::Duration::from_secs(5))
+        .map_err(sql_error("set busy timeout"))?;
+    // WAL lets the UI keep reading while a scrape or detail batch is writing.
+    conn.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))
+        .map_err(sql_error("enable WAL"))?;
+    conn.pragma_update(None, "foreign_keys", "ON")
+        .map_err(sql_error("enable foreign keys"))?;
+    migrate(&mut conn)?;
+    Ok(conn)
+}
+
+fn migrate(conn: &mut Connection) -> Result<(), AppError> {
+    let applied: usize = conn
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(sql_error("read schema version"))?;
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(applied) {
+        let tx = conn.transaction().map_err(sql_error("begin migration"))?;
+        tx.execute_batch(migration).map_err(sql_error("apply migration"))?;
+        // Before the version bump, so a failed rebuild leaves the old venues in place.
+        if index + 1 == VENUE_REBUILD_MIGRATION {
+            rebuild_venues(&tx)?;
+        }
+        tx.pragma_update(None, "user_version", index + 1)
+            .map_err(sql_error("update schema version"))?;
+        tx.commit().map_err(sql_error("commit migration"))?;
+        log::info!("Applied database migration {}", index + 1);
+    }
+    Ok(())
+}
+
+/// Derives the venues again from every stored event and points the events at them.
+fn rebuild_venues(tx: &Transaction) -> Result<(), AppError> {
+    let events = {
+        let mut stmt = tx.prepare("SELECT data FROM events").map_err(sql_error("prepare venue rebuild"))?;
+        let events = stmt
+            .query_map([], |row| event_from_data(row.get(0)?))
+            .map_err(sql_error("query events for venues"))?
+            .collect::<rusqlite::Result<Vec<Event>>>()
+            .map_err(sql_error("read events for venues"))?;
+        events
+    };
+    for mut event in events {
+        event.venue_id = upsert_venue(tx, &event)?;
+        tx.execute(
+            "UPDATE events SET venue_id = ?1, data = ?2 WHERE id = ?3",
+            params![event.venue_id, serialize_event(&event)?, event.id],
+        )
+        .map_err(sql_error("link event to venue"))?;
+    }
+    log::info!("Rebuilt venues from the stored events.");
+    Ok(())
+}
+
+fn import_legacy_caches(conn: &mut Connection, legacy: cache::LegacyCaches) -> Result<(), AppError> {
+    if legacy.is_empty() {
+        return Ok(());
+    }
+
+    let tx = conn.transaction().map_err(sql_error("begin import"))?;
+    if let Some(entry) = &legacy.list {
+        tx.execute(
+            "INSERT INTO scrape_runs (started_at, finished_at, status, event_count) VALUES (?1, ?1, ?2, ?3)",
+            params![entry.timestamp, RUN_STATUS_IMPORTED, entry.data.len()],
+        )
+        .map_err(sql_error("record import run"))?;
+        let run_id = tx.last_insert_rowid();
+        for event in &entry.data {
+            upsert_event(&tx, event, Some(run_id), entry.timestamp, None)?;
+        }
+    }
+    for entry in legacy.details.values() {
+        upsert_event(&tx, &entry.data, None, entry.timestamp, Some(entry.timestamp))?;
+    }
+    tx.commit().map_err(sql_error("commit import"))?;
+
+    log::info!("Imported legacy JSON caches into the database.");
+    legacy.mark_imported();
+    Ok(())
+}
+
+// The listing's translations, keeping the full descriptions fetched earlier. A scrape that
+// skipped the translated listings keeps the stored translations as they were.
+fn listed_translations(summary: &Event, stored: Option<&Event>) -> BTreeMap<String, EventTranslation> {
+    let Some(stored) = stored else {
+        return summary.translations.clone();
+    };
+    if summary.translations.is_empty() {
+        return stored.translations.clone();
+    }
+    let mut translations = summary.translations.clone();
+    for (language, translation) in translations.iter_mut() {
+        if translation.full_description.is_none() {
+            translation.full_description = stored.translations.get(language).and_then(|stored| stored.full_description.clone());
+        }
+    }
+    translations
+}
+
+fn find_venue_id(tx: &Transaction, normalized_name: &str) -> Result<Option<i64>, AppError> {
+    tx.query_row(
+        "SELECT id FROM venues WHERE normalized_name = ?1
+         UNION ALL SELECT venue_id FROM venue_aliases WHERE normalized_alias = ?1
+         LIMIT 1",
+        [normalized_name],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(sql_error("look up venue"))
+}
+
+/// Finds the event's venue by any of its names, filling in what the venue didn't know yet,
+/// or creates it. `None` when the event names no venue.
+fn upsert_venue(tx: &Transaction, event: &Event) -> Result<Option<i64>, AppError> {
+    let Some(details) = VenueDetails::from_event(event) else {
+        return Ok(None);
+    };
+    let mut existing = None;
+    for name in details.names() {
+        existing = find_venue_id(tx, &venue::normalize_name(name))?;
+        if existing.is_some() {
+            break;
+        }
+    }
+
+    let venue_id = match existing {
+        Some(venue_id) => {
+            tx.execute(
+                "UPDATE venues SET
+                    address = COALESCE(?2, address),
+                    street = COALESCE(?3, street),
+                    postal_code = COALESCE(?4, postal_code),
+                    locality = COALESCE(?5, locality),
+                    latitude = COALESCE(?6, latitude),
+                    longitude = COALESCE(?7, longitude)
+                 WHERE id = ?1",
+                params![
+                    venue_id,
+                    event.address,
+                    details.street,
+                    details.postal_code,
+                    details.locality,
+                    details.latitude,
+                    details.longitude
+                ],
+            )
+            .map_err(sql_error("update venue"))?;
+            venue_id
+        }
+        None => {
+            tx.execute(
+                "INSERT INTO venues (name, normalized_name, address, street, postal_code, locality, latitude, longitude)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    details.name,
+                    venue::normalize_name(&details.name),
+                    event.address,
+                    details.street,
+                    details.postal_code,
+                    details.locality,
+                    details.latitude,
+                    details.longitude
+                ],
+            )
+            .map_err(sql_error("insert venue"))?;
+            tx.last_insert_rowid()
+        }
+    };
+
+    // Remember every other spelling, so the next event using it finds this venue.
+    let venue_name: String = tx
+        .query_row("SELECT normalized_name FROM venues WHERE id = ?1", [venue_id], |row| row.get(0))
+        .map_err(sql_error("read venue name"))?;
+    for name in details.names() {
+        let normalized = venue::normalize_name(name);
+        if normalized != venue_name {
+            tx.execute(
+                "INSERT OR IGNORE INTO venue_aliases (normalized_alias, venue_id, alias) VALUES (?1, ?2, ?3)",
+                params![normalized, venue_id, name],
+            )
+            .map_err(sql_error("add venue alias"))?;
+        }
+    }
+    Ok(Some(venue_id))
+}
+
+fn serialize_event(event: &Event) -> Result<String, AppError> {
+    serde_json::to_string(event).map_err(|e| AppError::internal(format!("Failed to serialize event '{}': {}", event.id, e)))
+}
+
+fn upsert_event(
+    tx: &Transaction,
+    event: &Event,
+    run_id: Option<i64>,
+    updated_at: DateTime<Utc>,
+    details_fetched_at: Option<DateTime<Utc>>,
+) -> Result<Option<i64>, AppError> {
+    let mut event = event.clone();
+    event.venue_id = upsert_venue(tx, &event)?.or(event.venue_id);
+    let data = serialize_event(&event)?;
+    tx.execute(
+        "INSERT INTO events (id, source, title, start_datetime, end_datetime, venue_id, data,
+                             first_seen_run_id, last_seen_run_id, updated_at, details_fetched_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8, ?9, ?10)
+         ON CONFLICT(id) DO UPDATE SET
+            source = excluded.source,
+            title = excluded.title,
+            start_datetime = excluded.start_datetime,
+            end_datetime = excluded.end_datetime,
+            venue_id = COALESCE(excluded.venue_id, events.venue_id),
+            data = excluded.data,
+            last_seen_run_id = COALESCE(excluded.last_seen_run_id, events.last_seen_run_id),
+            removed_at = CASE WHEN excluded.last_seen_run_id IS NULL THEN events.removed_at END,
+            updated_at = excluded.updated_at,
+            details_fetched_at = COALESCE(excluded.details_fetched_at, events.details_fetched_at)",
+        params![
+            event.id,
+            event.source,
+            event.title,
+            event.starts_at(),
+            event.ends_at(),
+            event.venue_id,
+            data,
+            run_id,
+            updated_at,
+            details_fetched_at,
+        ],
+    )
+    .map_err(sql_error("upsert event"))?;
+    Ok(event.venue_id)
+}
+
+fn event_from_data(data: String) -> rusqlite::Result<Event> {
+    let mut event: Event = serde_json::from_str(&data)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))?;
+    // Events stored before prices were parsed only have the raw text.
+    if event.parsed_price.is_none() {
+        event.parsed_price = event.price.as_deref().or(event.list_price.as_deref()).map(Price::parse);
+    }
+    // And before the address parts were kept separately.
+    event.backfill_address_parts();
+    // And before recurring events were grouped.
+    if event.series_id.is_none() {
+        event.series_id = event.url_suffix.as_deref().and_then(series::series_id);
+    }
+    Ok(event)
+}
+
+fn stored_event(tx: &Transaction, id: &str) -> Result<Option<(Event, bool)>, AppError> {
+    tx.query_row(
+        "SELECT data, details_fetched_at IS NOT NULL FROM events WHERE id = ?1",
+        [id],
+        |row| Ok((event_from_data(row.get(0)?)?, row.get(1)?)),
+    )
+    .optional()
+    .map_err(sql_error("read stored event"))
+}
+
+/// Events that were in the listing as of the last complete scrape.
+fn active_events(tx: &Transaction) -> Result<Vec<Event>, AppError> {
+    let mut stmt = tx
+        .prepare("SELECT data FROM events WHERE removed_at IS NULL AND last_seen_run_id IS NOT NULL")
+        .map_err(sql_error("prepare active events"))?;
+    let events = stmt
+        .query_map([], |row| event_from_data(row.get(0)?))
+        .map_err(sql_error("query active events"))?
+        .collect::<rusqlite::Result<Vec<Event>>>()
+        .map_err(sql_error("read active events"))?;
+    Ok(events)
+}
+
+fn insert_changes(tx: &Transaction, changes: &[EventChange]) -> Result<(), AppError> {
+    let mut stmt = tx
+        .prepare(
+            "INSERT INTO event_changes (run_id, detected_at, event_id, title, kind, field, old_value, new_value)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )
+        .map_err(sql_error("prepare change insert"))?;
+    for change in changes {
+        let mut insert = |field: Option<&FieldChange>| {
+            stmt.execute(params![
+                change.run_id,
+                change.detected_at,
+                change.event_id,
+                change.title,
+                change.kind.as_str(),
+                field.map(|f| f.field.as_str()),
+                field.map(|f| f.old_value.to_string()),
+                field.map(|f| f.new_value.to_string()),
+            ])
+            .map_err(sql_error("insert change"))
+        };
+        if change.fields.is_empty() {
+            insert(None)?;
+        }
+        for field in &change.fields {
+            insert(Some(field))?;
+        }
+    }
+    Ok(())
+}
+
+/// Stores a scrape and returns the events as stored, plus what changed since the previous
+/// scrape. Summaries of events whose details were fetched earlier keep those details, with
+/// the listing fields refreshed. Events are only marked removed after a complete scrape,
+/// since a page-limited or cancelled one can't tell a removed event from one it didn't
+/// reach, and only in the languages it scraped. Nor does `load_fresh_events` serve an incomplete scrape as the listing, or a later
+/// scrape take it as its baseline.
+pub fn record_scrape(
+    conn: &mut Connection,
+    started_at: DateTime<Utc>,
+    pages_scraped: u32,
+    summaries: &[Event],
+    coverage: ScrapeCoverage,
+) -> Result<(Vec<Event>, Vec<EventChange>), AppError> {
+    let complete = matches!(coverage, ScrapeCoverage::Complete | ScrapeCoverage::Untranslated);
+    let finished_at = Utc::now();
+    let tx = conn.transaction().map_err(sql_error("begin scrape"))?;
+    // The very first scrape has nothing to compare against, so it doesn't report every event as new.
+    let has_baseline: bool = tx
+        .query_row(
+            "SELECT EXISTS (SELECT 1 FROM scrape_runs WHERE status IN (?1, ?2))",
+            params![RUN_STATUS_COMPLETED, RUN_STATUS_IMPORTED],
+            |row| row.get(0),
+        )
+        .map_err(sql_error("check previous scrapes"))?;
+    let mut previous_events = if has_baseline { active_events(&tx)? } else { Vec::new() };
+    if coverage == ScrapeCoverage::Untranslated {
+        previous_events.retain(|event| event.language == LISTING_LANGUAGE);
+    }
+
+    tx.execute(
+        "INSERT INTO scrape_runs (started_at, finished_at, status, pages_scraped, event_count)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            started_at,
+            finished_at,
+            coverage.run_status(),
+            pages_scraped,
+            summaries.len()
+        ],
+    )
+    .map_err(sql_error("record scrape run"))?;
+    let run_id = tx.last_insert_rowid();
+
+    let mut stored_events = Vec::with_capacity(summaries.len());
+    for summary in summaries {
+        let stored = stored_event(&tx, &summary.id)?;
+        let translations = listed_translations(summary, stored.as_ref().map(|(event, _)| event));
+        let event = match stored {
+            // The listing is current; only what the detail page added is kept from before.
+            Some((detailed, true)) => Event {
+                title: summary.title.clone(),
+                url_suffix: summary.url_suffix.clone(),
+                full_url: summary.full_url.clone(),
+                series_id: summary.series_id.clone(),
+                source: summary.source.clone(),
+                language: summary.language.clone(),
+                translations,
+                date_time_summary: summary.date_time_summary.clone(),
+                list_date: summary.list_date.clone(),
+                short_description: summary.short_description.clone(),
+                image_url: summary.image_url.clone(),
+                list_specific_location: summary.list_specific_location.clone(),
+                list_price: summary.list_price.clone(),
+                parsed_price: if detailed.price.is_some() {
+                    detailed.parsed_price
+                } else {
+                    summary.parsed_price.clone()
+                },
+                ..detailed
+            },
+            _ => Event {
+                translations,
+                ..summary.clone()
+            },
+        };
+        let venue_id = upsert_event(&tx, &event, Some(run_id), finished_at, None)?;
+        stored_events.push(Event { venue_id, ..event });
+    }
+
+    if complete {
+        tx.execute(
+            "UPDATE events SET removed_at = ?1
+             WHERE removed_at IS NULL AND last_seen_run_id IS NOT NULL AND last_seen_run_id != ?2
+               AND (?3 OR COALESCE(json_extract(data, '$.language'), ?4) = ?4)",
+            params![finished_at, run_id, coverage == ScrapeCoverage::Complete, LISTING_LANGUAGE],
+        )
+        .map_err(sql_error("mark removed events"))?;
+    }
+
+    let event_changes = if has_baseline {
+        changes::diff_events(&previous_events, &stored_events, complete, run_id, finished_at)
+    } else {
+        Vec::new()
+    };
+    insert_changes(&tx, &event_changes)?;
+
+    tx.commit().map_err(sql_error("commit scrape"))?;
+    log::info!(
+        "Stored scrape run {} with {} events ({} changes).",
+        run_id,
+        stored_events.len(),
+        event_changes.len()
+    );
+    Ok((stored_events, event_changes))
+}
+
+/// Records a scrape that failed before producing any events.
+pub fn record_failed_scrape(conn: &Connection, started_at: DateTime<Utc>, error: &str) -> Result<(), AppError> {
+    conn.execute(
+        "INSERT INTO scrape_runs (started_at, finished_at, status, error) VALUES (?1, ?2, ?3, ?4)",
+        params![started_at, Utc::now(), RUN_STATUS_FAILED, error],
+    )
+    .map_err(sql_error("record failed scrape"))?;
+    Ok(())
+}
+
+/// The events from the latest scrape, if it covered the whole listing and finished less than
+/// an hour ago. After a partial or cancelled scrape there is nothing to serve: it only has
+/// part of the listing, and the events it saw no longer count as seen by the run before it.
+pub fn load_fresh_events(conn: &Connection) -> Result<Option<Vec<Event>>, AppError> {
+    let latest_run: Option<(i64, String, DateTime<Utc>)> = conn
+        .query_row(
+            "SELECT id, status, finished_at FROM scrape_runs
+             WHERE status != ?1 AND finished_at IS NOT NULL
+             ORDER BY finished_at DESC LIMIT 1",
+            params![RUN_STATUS_FAILED],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()
+        .map_err(sql_error("find latest scrape run"))?;
+
+    match latest_run {
+        Some((run_id, status, finished_at))
+            if (status == RUN_STATUS_COMPLETED || status == RUN_STATUS_IMPORTED)
+                && Utc::now().signed_duration_since(finished_at) < Duration::hours(SUMMARY_LIFETIME_HOURS) =>
+        {
+            events_for_run(conn, run_id).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Events seen by scrape run `run_id`, in the order they were first stored.
+pub fn events_for_run(conn: &Connection, run_id: i64) -> Result<Vec<Event>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT data FROM events WHERE last_seen_run_id = ?1 ORDER BY rowid")
+        .map_err(sql_error("prepare run events"))?;
+    let events = stmt
+        .query_map([run_id], |row| event_from_data(row.get(0)?))
+        .map_err(sql_error("query run events"))?
+        .collect::<rusqlite::Result<Vec<Event>>>()
+        .map_err(sql_error("read run events"))?;
+    Ok(events)
+}
+
+/// The stored detailed version of event `id`, if its details were fetched within the last day.
+pub fn fresh_event_details(conn: &Connection, id: &str) -> Result<Option<Event>, AppError> {
+    let fetched: Option<(String, DateTime<Utc>)> = conn
+        .query_row(
+            "SELECT data, details_fetched_at FROM events WHERE id = ?1 AND details_fetched_at IS NOT NULL",
+            [id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(sql_error("read event details"))?;
+
+    match fetched {
+        Some((data, fetched_at)) if Utc::now().signed_duration_since(fetched_at) < Duration::hours(DETAIL_LIFETIME_HOURS) => {
+            event_from_data(data).map(Some).map_err(sql_error("decode event details"))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// When stored events were last written or marked removed, i.e. when the last scrape or
+/// detail fetch finished. `None` for an empty database.
+pub fn events_last_modified(conn: &Connection) -> Result<Option<DateTime<Utc>>, AppError> {
+    conn.query_row(
+        "SELECT MAX(changed_at) FROM (SELECT updated_at AS changed_at FROM events
+                                      UNION ALL SELECT removed_at FROM events)",
+        [],
+        |row| row.get(0),
+    )
+    .map_err(sql_error("read last modification time"))
+}
+
+/// Events written or marked removed after `since` (everything when `None`), so derived data
+/// such as the search index can catch up without rereading the whole store.
+pub fn events_changed_since(conn: &Connection, since: Option<DateTime<Utc>>) -> Result<ChangedEvents, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT data, removed_at IS NOT NULL, MAX(updated_at, COALESCE(removed_at, updated_at))
+             FROM events
+             WHERE ?1 IS NULL OR updated_at > ?1 OR removed_at > ?1",
+        )
+        .map_err(sql_error("prepare changed events query"))?;
+    let rows = stmt
+        .query_map([since], |row| Ok((event_from_data(row.get(0)?)?, row.get::<_, bool>(1)?, row.get(2)?)))
+        .map_err(sql_error("query changed events"))?;
+
+    let mut changed = ChangedEvents::default();
+    for row in rows {
+        let (event, removed, written_at): (Event, bool, DateTime<Utc>) = row.map_err(sql_error("read changed events"))?;
+        changed.until = changed.until.max(Some(written_at));
+        if removed {
+            changed.removed_ids.push(event.id);
+        } else {
+            changed.listed.push(event);
+        }
+    }
+    Ok(changed)
+}
+
+/// The stored version of event `id`, however old, including events no longer listed.
+pub fn find_event(conn: &Connection, id: &str) -> Result<Option<Event>, AppError> {
+    conn.query_row("SELECT data FROM events WHERE id = ?1", [id], |row| event_from_data(row.get(0)?))
+        .optional()
+        .map_err(sql_error("read event"))
+}
+
+/// Stores freshly fetched detailed events.
+pub fn save_event_details(conn: &mut Connection, events: &[Event]) -> Result<(), AppError> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    let now = Utc::now();
+    let tx = conn.transaction().map_err(sql_error("begin detail save"))?;
+    for event in events {
+        upsert_event(&tx, event, None, now, Some(now))?;
+    }
+    tx.commit().map_err(sql_error("commit detail save"))?;
+    log::info!("Stored details for {} events.", events.len());
+    Ok(())
+}
+
+/// All stored events that are still listed, ordered by start time with undated events last.
+/// Filtering is up to `query::query_events`, so every caller matches venues and dates alike.
+pub fn query_stored_events(conn: &Connection) -> Result<Vec<Event>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT data FROM events
+             WHERE removed_at IS NULL
+             ORDER BY start_datetime IS NULL, start_datetime, title",
+        )
+        .map_err(sql_error("prepare event query"))?;
+    let events = stmt
+        .query_map([], |row| event_from_data(row.get(0)?))
+        .map_err(sql_error("query events"))?
+        .collect::<rusqlite::Result<Vec<Event>>>()
+        .map_err(sql_error("read events"))?;
+    Ok(events)
+}
+
+const VENUE_COLUMNS: &str = "venues.id, venues.name, venues.normalized_name, venues.address, venues.street,
+    venues.postal_code, venues.locality, venues.latitude, venues.longitude,
+    (SELECT COUNT(*) FROM events WHERE events.venue_id = venues.id AND events.removed_at IS NULL)";
+
+fn venue_from_row(conn: &Connection, row: &rusqlite::Row) -> rusqlite::Result<Venue> {
+    let id: i64 = row.get(0)?;
+    let aliases = conn
+        .prepare_cached("SELECT alias FROM venue_aliases WHERE venue_id = ?1 ORDER BY alias")?
+        .query_map([id], |row| row.get(0))?
+        .collect::<rusqlite::Result<Vec<String>>>()?;
+    Ok(Venue {
+        id,
+        name: row.get(1)?,
+        normalized_name: row.get(2)?,
+        address: row.get(3)?,
+        street: row.get(4)?,
+        postal_code: row.get(5)?,
+        locality: row.get(6)?,
+        latitude: row.get(7)?,
+        longitude: row.get(8)?,
+        aliases,
+        event_count: row.get(9)?,
+    })
+}
+
+/// All venues with their aliases, by name.
+pub fn list_venues(conn: &Connection) -> Result<Vec<Venue>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!("SELECT {} FROM venues ORDER BY venues.name", VENUE_COLUMNS))
+        .map_err(sql_error("prepare venue query"))?;
+    let venues = stmt
+        .query_map([], |row| venue_from_row(conn, row))
+        .map_err(sql_error("query venues"))?
+        .collect::<rusqlite::Result<Vec<Venue>>>()
+        .map_err(sql_error("read venues"))?;
+    Ok(venues)
+}
+
+pub fn find_venue(conn: &Connection, id: i64) -> Result<Option<Venue>, AppError> {
+    conn.query_row(
+        &format!("SELECT {} FROM venues WHERE venues.id = ?1", VENUE_COLUMNS),
+        [id],
+        |row| venue_from_row(conn, row),
+    )
+    .optional()
+    .map_err(sql_error("read venue"))
+}
+
+/// The still-listed events at venue `venue_id`, ordered like `query_stored_events`.
+pub fn venue_events(conn: &Connection, venue_id: i64) -> Result<Vec<Event>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT data FROM events
+             WHERE removed_at IS NULL AND venue_id = ?1
+             ORDER BY start_datetime IS NULL, start_datetime, title",
+        )
+        .map_err(sql_error("prepare venue event query"))?;
+    let events = stmt
+        .query_map([venue_id], |row| event_from_data(row.get(0)?))
+        .map_err(sql_error("query venue events"))?
+        .collect::<rusqlite::Result<Vec<Event>>>()
+        .map_err(sql_error("read venue events"))?;
+    Ok(events)
+}
+
+/// The stored copy of the page at `url`, if it had validators when it was last fetched.
+pub fn stored_page(conn: &Connection, url: &str) -> Result<Option<StoredPage>, AppError> {
+    conn.query_row("SELECT etag, last_modified, body FROM http_pages WHERE url = ?1", [url], |row| {
+        Ok(StoredPage {
+            etag: row.get(0)?,
+            last_modified: row.get(1)?,
+            body: row.get(2)?,
+        })
+    })
+    .optional()
+    .map_err(sql_error("read stored page"))
+}
+
+pub fn save_page(conn: &Connection, url: &str, page: &StoredPage) -> Result<(), AppError> {
+    conn.execute(
+        "INSERT INTO http_pages (url, etag, last_modified, body, fetched_at) VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(url) DO UPDATE SET etag = ?2, last_modified = ?3, body = ?4, fetched_at = ?5",
+        params![url, page.etag, page.last_modified, page.body, Utc::now()],
+    )
+    .map_err(sql_error("store page"))?;
+    Ok(())
+}
+
+pub fn list_scrape_runs(conn: &Connection, limit: u32) -> Result<Vec<ScrapeRun>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, started_at, finished_at, status, pages_scraped, event_count, error
+             FROM scrape_runs ORDER BY started_at DESC LIMIT ?1",
+        )
+        .map_err(sql_error("prepare scrape run query"))?;
+    let runs = stmt
+        .query_map([limit], |row| {
+            Ok(ScrapeRun {
+                id: row.get(0)?,
+                started_at: row.get(1)?,
+                finished_at: row.get(2)?,
+                status: row.get(3)?,
+                pages_scraped: row.get(4)?,
+                event_count: row.get(5)?,
+                error: row.get(6)?,
+            })
+        })
+        .map_err(sql_error("query scrape runs"))?
+        .collect::<rusqlite::Result<Vec<ScrapeRun>>>()
+        .map_err(sql_error("read scrape runs"))?;
+    Ok(runs)
+}
+
+/// The change log since `since` (or all of it), oldest first.
+pub fn query_event_changes(conn: &Connection, since: Option<DateTime<Utc>>) -> Result<Vec<EventChange>, AppError> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT run_id, detected_at, event_id, title, kind, field, old_value, new_value
+             FROM event_changes
+             WHERE ?1 IS NULL OR detected_at > ?1
+             ORDER BY id",
+        )
+        .map_err(sql_error("prepare change query"))?;
+    let mut rows = stmt.query([since]).map_err(sql_error("query changes"))?;
+
+    // Field-level rows of the same event in the same run are stored next to each other.
+    let mut event_changes: Vec<EventChange> = Vec::new();
+    while let Some(row) = rows.next().map_err(sql_error("read changes"))? {
+        let run_id: i64 = row.get(0).map_err(sql_error("read change run"))?;
+        let event_id: String = row.get(2).map_err(sql_error("read change event"))?;
+        let kind_str: String = row.get(4).map_err(sql_error("read change kind"))?;
+        let Some(kind) = ChangeKind::parse(&kind_str) else {
+            log::warn!("Skipping change with unknown kind '{}'.", kind_str);
+            continue;
+        };
+        let field: Option<String> = row.get(5).map_err(sql_error("read change field"))?;
+        let field_change = match field {
+            Some(field) => {
+                let parse_value = |value: Option<String>| {
+                    value
+                        .and_then(|v| serde_json::from_str(&v).ok())
+                        .unwrap_or(serde_json::Value::Null)
+                };
+                Some(FieldChange {
+                    field,
+                    old_value: parse_value(row.get(6).map_err(sql_error("read old value"))?),
+                    new_value: parse_value(row.get(7).map_err(sql_error("read new value"))?),
+                })
+            }
+            None => None,
+        };
+
+        match event_changes.last_mut() {
+            Some(last) if last.run_id == run_id && last.event_id == event_id && last.kind == kind => {
+                last.fields.extend(field_change);
+            }
+            _ => event_changes.push(EventChange {
+                run_id,
+                detected_at: row.get(1).map_err(sql_error("read change time"))?,
+                event_id,
+                title: row.get(3).map_err(sql_error("read change title"))?,
+                kind,
+                fields: field_change.into_iter().collect(),
+            }),
+        }
+    }
+    Ok(event_changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn test_db() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn).unwrap();
+        conn
+    }
+
+    // A fresh path in the temp directory, unique to this test run.
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("eindhoven-events-db-{}-{}", name, std::process::id()));
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+        let _ = std::fs::remove_dir_all(&path);
+        path
+    }
+
+    fn listed(slug: &str, venue: &str) -> Event {
+        Event {
+            id: format!("/en/events/{}", slug),
+            title: slug.replace('-', " "),
+            source: "thisiseindhoven".to_string(),
+            list_specific_location: Some(venue.to_string()),
+            ..Event::default()
+        }
+    }
+
+    fn schema_version(conn: &Connection) -> usize {
+        conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn migrates_new_and_older_databases() {
+        for version in 0..=MIGRATIONS.len() {
+            let path = temp_path(&format!("v{}", version));
+            {
+                let conn = Connection::open(&path).unwrap();
+                for migration in &MIGRATIONS[..version] {
+                    conn.execute_batch(migration).unwrap();
+                }
+                conn.pragma_update(None, "user_version", version).unwrap();
+                if version > 0 {
+                    let event = listed("jazz-night", "Effenaar");
+                    conn.execute(
+                        "INSERT INTO events (id, source, title, data, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+                        params![event.id, event.source, event.title, serialize_event(&event).unwrap(), Utc::now()],
+                    )
+                    .unwrap();
+                }
+            }
+
+            let conn = open_at(&path).unwrap();
+            assert_eq!(schema_version(&conn), MIGRATIONS.len(), "from version {}", version);
+            assert_eq!(stored_page(&conn, "https://thisiseindhoven.com/robots.txt").unwrap(), None);
+            if version == 0 {
+                continue;
+            }
+            let event = find_event(&conn, "/en/events/jazz-night").unwrap().unwrap();
+            if version < VENUE_REBUILD_MIGRATION {
+                let venues = list_venues(&conn).unwrap();
+                assert_eq!(venues.len(), 1, "from version {}", version);
+                assert_eq!(venues[0].name, "Effenaar");
+                assert_eq!(event.venue_id, Some(venues[0].id));
+            }
+            drop(conn);
+            // Opening an up-to-date database changes nothing.
+            assert_eq!(schema_version(&open_at(&path).unwrap()), MIGRATIONS.len());
+        }
+    }
+
+    #[test]
+    fn failed_venue_rebuild_keeps_the_old_schema() {
+        let path = temp_path("venue-rebuild");
+        {
+            let conn = Connection::open(&path).unwrap();
+            for migration in &MIGRATIONS[..VENUE_REBUILD_MIGRATION - 1] {
+                conn.execute_batch(migration).unwrap();
+            }
+            conn.pragma_update(None, "user_version", VENUE_REBUILD_MIGRATION - 1).unwrap();
+            conn.execute("INSERT INTO venues (id, name) VALUES (1, 'Effenaar')", []).unwrap();
+            conn.execute(
+                "INSERT INTO events (id, source, title, venue_id, data, updated_at) VALUES ('/en/events/broken', 'thisiseindhoven', 'Broken', 1, 'not json', ?1)",
+                [Utc::now()],
+            )
+            .unwrap();
+        }
+
+        assert!(open_at(&path).is_err());
+        let conn = Connection::open(&path).unwrap();
+        assert_eq!(schema_version(&conn), VENUE_REBUILD_MIGRATION - 1);
+        let venue_id: Option<i64> = conn.query_row("SELECT venue_id FROM events", [], |row| row.get(0)).unwrap();
+        assert_eq!(venue_id, Some(1));
+        let venues: i64 = conn.query_row("SELECT COUNT(*) FROM venues", [], |row| row.get(0)).unwrap();
+        assert_eq!(venues, 1);
+    }
+
+    #[test]
+    fn legacy_caches_are_imported_once() {
+        let dir = temp_path("legacy");
+        std::fs::create_dir_all(&dir).unwrap();
+        let summary = listed("jazz-night", "Effenaar");
+        let detailed = Event {
+            full_description: Some("An evening of jazz.".to_string()),
+            ..summary.clone()
+        };
+        let list_cache = cache::CacheEntry {
+            timestamp: Utc::now(),
+            data: vec![summary.clone()],
+        };
+        let detail_cache: cache::DetailCache = HashMap::from([(
+            detailed.id.clone(),
+            cache::CacheEntry {
+                timestamp: Utc::now(),
+                data: detailed,
+            },
+        )]);
+        std::fs::write(dir.join("events_cache.json"), serde_json::to_string(&list_cache).unwrap()).unwrap();
+        std::fs::write(dir.join("event_details_cache.json"), serde_json::to_string(&detail_cache).unwrap()).unwrap();
+
+        let mut conn = test_db();
+        import_legacy_caches(&mut conn, cache::read_legacy_caches_in(&dir)).unwrap();
+        assert_eq!(load_fresh_events(&conn).unwrap().map(|events| events.len()), Some(1));
+        let stored = fresh_event_details(&conn, &summary.id).unwrap().unwrap();
+        assert_eq!(stored.full_description.as_deref(), Some("An evening of jazz."));
+
+        assert!(!dir.join("events_cache.json").exists());
+        assert!(dir.join("events_cache.json.imported").exists());
+        assert!(dir.join("event_details_cache.json.imported").exists());
+        let legacy = cache::read_legacy_caches_in(&dir);
+        assert!(legacy.is_empty());
+        import_legacy_caches(&mut conn, legacy).unwrap();
+        assert_eq!(list_scrape_runs(&conn, 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn stored_listings_and_details_expire() {
+        let mut conn = test_db();
+        let event = listed("jazz-night", "Effenaar");
+        record_scrape(&mut conn, Utc::now(), 1, std::slice::from_ref(&event), ScrapeCoverage::Complete).unwrap();
+        assert_eq!(load_fresh_events(&conn).unwrap().map(|events| events.len()), Some(1));
+        conn.execute("UPDATE scrape_runs SET finished_at = ?1", [Utc::now() - Duration::hours(SUMMARY_LIFETIME_HOURS) - Duration::minutes(1)])
+            .unwrap();
+        assert_eq!(load_fresh_events(&conn).unwrap().map(|events| events.len()), None);
+
+        save_event_details(&mut conn, std::slice::from_ref(&event)).unwrap();
+        assert!(fresh_event_details(&conn, &event.id).unwrap().is_some());
+        conn.execute(
+            "UPDATE events SET details_fetched_at = ?1",
+            [Utc::now() - Duration::hours(DETAIL_LIFETIME_HOURS) - Duration::minutes(1)],
+        )
+        .unwrap();
+        assert!(fresh_event_details(&conn, &event.id).unwrap().is_none());
+        // The stale copy is still there for offline use.
+        assert!(find_event(&conn, &event.id).unwrap().is_some());
+    }
+
+    #[test]
+    fn runs_with_failed_pages_are_not_served_as_fresh() {
+        let mut conn = test_db();
+        let events = [listed("jazz-night", "Effenaar"), listed("open-air", "Stadswandelpark")];
+        // Pages failed, so only the first page's events came in.
+        record_scrape(&mut conn, Utc::now(), 1, &events[..1], ScrapeCoverage::Partial).unwrap();
+        assert_eq!(list_scrape_runs(&conn, 1).unwrap()[0].status, RUN_STATUS_PARTIAL);
+        assert_eq!(load_fresh_events(&conn).unwrap().map(|events| events.len()), None);
+
+        record_scrape(&mut conn, Utc::now(), 2, &events, ScrapeCoverage::Complete).unwrap();
+        assert_eq!(load_fresh_events(&conn).unwrap().map(|events| events.len()), Some(2));
+        record_scrape(&mut conn, Utc::now(), 1, &events[..1], ScrapeCoverage::Partial).unwrap();
+        assert_eq!(load_fresh_events(&conn).unwrap().map(|events| events.len()), None);
+    }
+
+    #[test]
+    fn cancelled_runs_are_not_served_as_fresh() {
+        let mut conn = test_db();
+        let events = [listed("jazz-night", "Effenaar"), listed("open-air", "Stadswandelpark")];
+        record_scrape(&mut conn, Utc::now(), 2, &events, ScrapeCoverage::Complete).unwrap();
+        let (_, changes) = record_scrape(&mut conn, Utc::now(), 1, &events[..1], ScrapeCoverage::Cancelled).unwrap();
+        assert!(changes.is_empty());
+        assert_eq!(list_scrape_runs(&conn, 1).unwrap()[0].status, RUN_STATUS_CANCELLED);
+        assert_eq!(load_fresh_events(&conn).unwrap().map(|events| events.len()), None);
+        assert!(events_changed_since(&conn, None).unwrap().removed_ids.is_empty());
+    }
+
+    #[test]
+    fn partial_first_scrape_is_no_baseline() {
+        let mut conn = test_db();
+        let events = [listed("jazz-night", "Effenaar"), listed("open-air", "Stadswandelpark")];
+        record_scrape(&mut conn, Utc::now(), 1, &events[..1], ScrapeCoverage::Partial).unwrap();
+        let (_, changes) = record_scrape(&mut conn, Utc::now(), 2, &events, ScrapeCoverage::Complete).unwrap();
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn first_scrape_is_the_baseline() {
+        let mut conn = test_db();
+        let events = [listed("jazz-night", "Effenaar"), listed("open-air", "Stadswandelpark")];
+        let (stored, changes) = record_scrape(&mut conn, Utc::now(), 1, &events, ScrapeCoverage::Complete).unwrap();
+        assert_eq!(stored.len(), 2);
+        assert!(changes.is_empty());
+        assert!(query_event_changes(&conn, None).unwrap().is_empty());
+
+        let (_, changes) = record_scrape(&mut conn, Utc::now(), 1, &[events[0].clone(), listed("cinema", "LAB1")], ScrapeCoverage::Complete).unwrap();
+        let kinds: Vec<(&str, ChangeKind)> = changes.iter().map(|c| (c.event_id.as_str(), c.kind)).collect();
+        assert_eq!(kinds, [("/en/events/cinema", ChangeKind::Added), ("/en/events/open-air", ChangeKind::Removed)]);
+    }
+
+    #[test]
+    fn field_changes_are_logged_and_read_back() {
+        let mut conn = test_db();
+        let event = listed("jazz-night", "Effenaar");
+        let started = Utc::now();
+        record_scrape(&mut conn, started, 1, std::slice::from_ref(&event), ScrapeCoverage::Complete).unwrap();
+        let renamed = Event {
+            title: "Jazz Night".to_string(),
+            list_date: Some("12 July".to_string()),
+            ..event.clone()
+        };
+        record_scrape(&mut conn, Utc::now(), 1, &[renamed], ScrapeCoverage::Complete).unwrap();
+
+        let logged = query_event_changes(&conn, None).unwrap();
+        assert_eq!(logged.len(), 1);
+        assert_eq!(logged[0].kind, ChangeKind::Changed);
+        let fields: Vec<&str> = logged[0].fields.iter().map(|f| f.field.as_str()).collect();
+        assert_eq!(fields, ["list_date", "title"]);
+        assert_eq!(logged[0].fields[1].new_value, serde_json::json!("Jazz Night"));
+        assert!(query_event_changes(&conn, Some(logged[0].detected_at)).unwrap().is_empty());
+
+        let changed = events_changed_since(&conn, Some(started)).unwrap();
+        assert_eq!(changed.listed.len(), 1);
+        assert!(changed.removed_ids.is_empty());
+        assert!(events_changed_since(&conn, changed.until).unwrap().listed.is_empty());
+    }
+
+    #[test]
+    fn listing_fields_of_detailed_events_follow_the_listing() {
+        let mut conn = test_db();
+        let event = listed("jazz-night", "Effenaar");
+        record_scrape(&mut conn, Utc::now(), 1, std::slice::from_ref(&event), ScrapeCoverage::Complete).unwrap();
+        let detailed = Event {
+            full_description: Some("An evening of jazz.".to_string()),
+            specific_location_name: Some("Effenaar, Kleine Zaal".to_string()),
+            price: Some("€ 15".to_string()),
+            parsed_price: Some(Price::parse("€ 15")),
+            ..event.clone()
+        };
+        save_event_details(&mut conn, &[detailed]).unwrap();
+
+        let renamed = Event {
+            title: "Jazz Night (moved)".to_string(),
+            list_price: Some("€ 20".to_string()),
+            parsed_price: Some(Price::parse("€ 20")),
+            ..event.clone()
+        };
+        let (stored, _) = record_scrape(&mut conn, Utc::now(), 1, &[renamed], ScrapeCoverage::Complete).unwrap();
+        assert_eq!(stored[0].title, "Jazz Night (moved)");
+        assert_eq!(stored[0].list_price.as_deref(), Some("€ 20"));
+        assert_eq!(stored[0].full_description.as_deref(), Some("An evening of jazz."));
+        assert_eq!(stored[0].specific_location_name.as_deref(), Some("Effenaar, Kleine Zaal"));
+        assert_eq!(stored[0].parsed_price, Some(Price::parse("€ 15")));
+        let reloaded = find_event(&conn, &event.id).unwrap().unwrap();
+        assert_eq!(reloaded.title, "Jazz Night (moved)");
+    }
+
+    #[test]
+    fn scrape_without_translations_keeps_translated_only_events() {
+        let mut conn = test_db();
+        let dutch_only = Event {
+            id: "/nl/events/koningsdag".to_string(),
+            language: "nl".to_string(),
+            ..listed("koningsdag", "Stadhuisplein")
+        };
+        let english = listed("jazz-night", "Effenaar");
+        record_scrape(&mut conn, Utc::now(), 2, &[english.clone(), dutch_only], ScrapeCoverage::Complete).unwrap();
+
+        let (_, changes) =
+            record_scrape(&mut conn, Utc::now(), 1, std::slice::from_ref(&english), ScrapeCoverage::Untranslated).unwrap();
+        assert!(changes.is_empty());
+        assert!(events_changed_since(&conn, None).unwrap().removed_ids.is_empty());
+        assert_eq!(query_stored_events(&conn).unwrap().len(), 2);
+
+        // English events missing from it are still removed.
+        let (_, changes) = record_scrape(&mut conn, Utc::now(), 1, &[], ScrapeCoverage::Untranslated).unwrap();
+        let kinds: Vec<(&str, ChangeKind)> = changes.iter().map(|c| (c.event_id.as_str(), c.kind)).collect();
+        assert_eq!(kinds, [("/en/events/jazz-night", ChangeKind::Removed)]);
+        assert_eq!(query_stored_events(&conn).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn incomplete_scrape_marks_nothing_removed() {
+        let mut conn = test_db();
+        let events = [listed("jazz-night", "Effenaar"), listed("open-air", "Stadswandelpark")];
+        record_scrape(&mut conn, Utc::now(), 2, &events, ScrapeCoverage::Complete).unwrap();
+
+        let (_, changes) = record_scrape(&mut conn, Utc::now(), 1, &events[..1], ScrapeCoverage::Partial).unwrap();
+        assert!(changes.is_empty());
+        assert_eq!(query_stored_events(&conn).unwrap().len(), 2);
+        assert!(events_changed_since(&conn, None).unwrap().removed_ids.is_empty());
+    }
+}
@@ -0,0 +1,432 @@
+// File: src-tauri/src/desktop.rs
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::Emitter;
+
+use crate::error::AppError;
+use crate::models::{self, Event, Venue};
+use crate::{
+    build_scraping_client, build_scraping_client_with, changes, db, fetch_event_details_batch_with_store,
+    fetch_event_details_with_store, geo, ics, query, scraper, search, series, venue,
+};
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    tauri::Builder::default()
+        .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_fs::init())
+        .manage(ScrapeJobs::default())
+        .setup(|_app| {
+            #[cfg(feature = "server")]
+            start_feed_server();
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            greet,
+            fetch_events_rust,
+            fetch_specific_event_details_rust,
+            fetch_event_details_batch_rust,
+            cancel_scrape,
+            get_stored_events,
+            query_events,
+            query_event_series,
+            search_events,
+            get_events_near,
+            get_events_in_bounds,
+            get_venues,
+            get_venue_events,
+            get_scrape_runs,
+            get_event_changes,
+            generate_ics_rust, // Ensure this is the function name you use
+            generate_ics_batch_rust
+        ])
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}
+
+// Runs the calendar feed server next to the app when `server::BIND_ADDRESS_ENV` is set.
+#[cfg(feature = "server")]
+fn start_feed_server() {
+    let Ok(bind_address) = std::env::var(crate::server::BIND_ADDRESS_ENV) else {
+        return;
+    };
+    let bind_address = match bind_address.parse() {
+        Ok(bind_address) => bind_address,
+        Err(e) => {
+            log::error!("Invalid {} '{}': {}", crate::server::BIND_ADDRESS_ENV, bind_address, e);
+            return;
+        }
+    };
+    let db_path = match db::default_path() {
+        Ok(db_path) => db_path,
+        Err(e) => {
+            log::error!("Not starting the calendar feed server: {}", e);
+            return;
+        }
+    };
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = crate::server::serve(bind_address, db_path).await {
+            log::error!("{}", e);
+        }
+    });
+}
+
+/// Cancellation tokens of the scrapes in progress, by the job id the frontend chose.
+#[derive(Default)]
+struct ScrapeJobs(Mutex<HashMap<String, scraper::CancelToken>>);
+
+impl ScrapeJobs {
+    // A token for the job; without an id the scrape just can't be cancelled. An id that is
+    // still running is refused, since `finish` would then drop the new job's token.
+    fn start(&self, job_id: Option<&str>) -> Result<scraper::CancelToken, AppError> {
+        let token = scraper::CancelToken::default();
+        if let Some(job_id) = job_id {
+            let mut jobs = self.0.lock().unwrap_or_else(|e| e.into_inner());
+            if jobs.contains_key(job_id) {
+                return Err(AppError::Busy {
+                    task: format!("Scrape job '{}'", job_id),
+                });
+            }
+            jobs.insert(job_id.to_string(), token.clone());
+        }
+        Ok(token)
+    }
+
+    fn finish(&self, job_id: Option<&str>) {
+        if let Some(job_id) = job_id {
+            self.0.lock().unwrap_or_else(|e| e.into_inner()).remove(job_id);
+        }
+    }
+
+    fn cancel(&self, job_id: &str) -> Result<(), AppError> {
+        let jobs = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let token = jobs.get(job_id).ok_or_else(|| AppError::NotFound {
+            what: format!("Scrape job '{}'", job_id),
+        })?;
+        token.cancel();
+        Ok(())
+    }
+}
+
+#[tauri::command]
+fn greet(name: &str) -> String {
+    format!("Hello, {}! You've been greeted from Rust!", name)
+}
+
+/// What `fetch_events_rust` scraped; the events of `failed_pages` are missing.
+#[derive(Debug, Serialize)]
+struct FetchedSummaries {
+    events: Vec<Event>,
+    failed_pages: Vec<scraper::FailedPage>,
+    /// The scrape was cancelled through `cancel_scrape`; `events` are those found until then.
+    cancelled: bool,
+}
+
+#[tauri::command]
+async fn fetch_events_rust(
+    window: tauri::Window,
+    jobs: tauri::State<'_, ScrapeJobs>,
+    page_limit: Option<u32>,
+    force_refresh: bool,
+    include_dutch: Option<bool>,
+    job_id: Option<String>,
+) -> Result<FetchedSummaries, AppError> {
+    log::info!(
+        "fetch_events_rust (summaries) command invoked with page_limit: {:?}, force_refresh: {}, include_dutch: {:?}, job_id: {:?}",
+        page_limit,
+        force_refresh,
+        include_dutch,
+        job_id
+    );
+    let cancel = jobs.start(job_id.as_deref())?;
+
+    let progress_window = window.clone();
+    let progress_callback = move |progress: models::ScrapingProgress| {
+        let _ = progress_window.emit("scraping_progress", progress);
+    };
+
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let client = build_scraping_client()?;
+        let registry = scraper::SourceRegistry::with_default_sources().with_translations(include_dutch.unwrap_or(false));
+        scraper::fetch_event_list_summaries(&client, &registry, page_limit, force_refresh, &cancel, progress_callback)
+    })
+    .await;
+    jobs.finish(job_id.as_deref());
+
+    match result {
+        Ok(Ok(report)) => {
+            log::info!(
+                "Successfully fetched {} event summaries ({} pages failed, cancelled: {}).",
+                report.events.len(),
+                report.failed_pages.len(),
+                report.cancelled
+            );
+            if !report.changes.is_empty() {
+                let _ = window.emit("events_changed", changes::EventsChanged::new(report.changes));
+            }
+            Ok(FetchedSummaries {
+                events: report.events,
+                failed_pages: report.failed_pages,
+                cancelled: report.cancelled,
+            })
+        }
+        Ok(Err(e)) => {
+            log::error!("Error fetching event summaries: {}", e);
+            Err(e)
+        }
+        Err(join_error) => {
+            log::error!("Task panic while fetching event summaries: {}", join_error);
+            Err(AppError::internal(format!("Task panic (summaries): {}", join_error)))
+        }
+    }
+}
+
+#[tauri::command]
+async fn fetch_specific_event_details_rust(event_summary: Event) -> Result<Event, AppError> {
+    log::info!(
+        "fetch_specific_event_details_rust command invoked for event ID: {}",
+        event_summary.id
+    );
+    if event_summary.full_url.is_none() {
+        return Err(AppError::MissingField {
+            event_id: event_summary.id.clone(),
+            title: event_summary.title.clone(),
+            field: "full_url",
+        });
+    }
+
+    match tauri::async_runtime::spawn_blocking(move || fetch_event_details_with_store(&build_scraping_client()?, event_summary)).await
+    {
+        Ok(Ok(detailed_event)) => {
+            log::info!(
+                "Successfully fetched details for event ID: {}",
+                detailed_event.id
+            );
+            Ok(detailed_event)
+        }
+        Ok(Err(e)) => {
+            log::error!("Error fetching specific event details: {}", e);
+            Err(e)
+        }
+        Err(join_error) => {
+            log::error!(
+                "Task panic while fetching specific event details: {}",
+                join_error
+            );
+            Err(AppError::internal(format!("Task panic (details): {}", join_error)))
+        }
+    }
+}
+
+
+#[tauri::command]
+async fn fetch_event_details_batch_rust(
+    window: tauri::Window,
+    jobs: tauri::State<'_, ScrapeJobs>,
+    event_summaries: Vec<Event>,
+    concurrency: Option<usize>,
+    requests_per_second: Option<f64>,
+    job_id: Option<String>,
+) -> Result<scraper::DetailBatchResult, AppError> {
+    log::info!(
+        "fetch_event_details_batch_rust command invoked for {} events (concurrency: {:?}, requests_per_second: {:?}, job_id: {:?})",
+        event_summaries.len(),
+        concurrency,
+        requests_per_second,
+        job_id
+    );
+
+    let progress_window = window.clone();
+    let progress_callback = move |progress: models::ScrapingProgress| {
+        let _ = progress_window.emit("scraping_progress", progress);
+    };
+
+    let mut options = scraper::DetailBatchOptions::default();
+    if let Some(concurrency) = concurrency {
+        options.concurrency = concurrency;
+    }
+    let mut policy = scraper::CrawlPolicy::default();
+    if let Some(requests_per_second) = requests_per_second {
+        policy.limit_requests_per_second(requests_per_second);
+    }
+    options.cancel = jobs.start(job_id.as_deref())?;
+
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let client = build_scraping_client_with(policy)?;
+        fetch_event_details_batch_with_store(&client, event_summaries, &options, progress_callback)
+    })
+    .await;
+    jobs.finish(job_id.as_deref());
+
+    match result {
+        Ok(Ok(batch)) => {
+            log::info!(
+                "Fetched details for {} events ({} failed, {} cancelled).",
+                batch.detailed_count(),
+                batch.failed_ids.len(),
+                batch.cancelled_ids.len()
+            );
+            Ok(batch)
+        }
+        Ok(Err(e)) => {
+            log::error!("Error fetching event details batch: {}", e);
+            Err(e)
+        }
+        Err(join_error) => {
+            log::error!("Task panic while fetching event details batch: {}", join_error);
+            Err(AppError::internal(format!("Task panic (details batch): {}", join_error)))
+        }
+    }
+}
+
+/// Cancels the scrape started with `job_id`. It stops before its next page or detail fetch
+/// and returns what it had; its last `scraping_progress` message has `cancelled` set.
+#[tauri::command]
+fn cancel_scrape(jobs: tauri::State<'_, ScrapeJobs>, job_id: String) -> Result<(), AppError> {
+    log::info!("cancel_scrape command invoked for job {}", job_id);
+    jobs.cancel(&job_id)
+}
+
+// Runs a read-only query against the event database off the async runtime.
+async fn run_db_query<T: Send + 'static>(
+    query: impl FnOnce(&rusqlite::Connection) -> Result<T, AppError> + Send + 'static,
+) -> Result<T, AppError> {
+    tauri::async_runtime::spawn_blocking(move || db::open().and_then(|conn| query(&conn)))
+        .await
+        .map_err(|join_error| AppError::internal(format!("Task panic (database): {}", join_error)))?
+}
+
+#[tauri::command]
+async fn get_stored_events(
+    from: Option<NaiveDateTime>,
+    to: Option<NaiveDateTime>,
+    venue: Option<String>,
+) -> Result<Vec<Event>, AppError> {
+    log::info!("get_stored_events command invoked (from: {:?}, to: {:?}, venue: {:?})", from, to, venue);
+    let filter = query::EventFilter {
+        from,
+        to,
+        venue,
+        ..query::EventFilter::default()
+    };
+    run_db_query(move |conn| query::query_events(conn, &filter, query::SortKey::DateAsc, 0, None).map(|page| page.events)).await
+}
+
+/// Stored events matching `filter` in `sort` order, `limit` of them (all by default) from `offset` on.
+#[tauri::command]
+async fn query_events(
+    filter: Option<query::EventFilter>,
+    sort: Option<query::SortKey>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<query::EventPage, AppError> {
+    let filter = filter.unwrap_or_default();
+    log::info!("query_events command invoked (filter: {:?}, sort: {:?}, offset: {:?}, limit: {:?})", filter, sort, offset, limit);
+    run_db_query(move |conn| query::query_events(conn, &filter, sort.unwrap_or_default(), offset.unwrap_or(0), limit)).await
+}
+
+/// Stored events matching `filter`, with the dates of a recurring event grouped into one series.
+/// Series are in the order their first matching date has in `sort`.
+#[tauri::command]
+async fn query_event_series(filter: Option<query::EventFilter>, sort: Option<query::SortKey>) -> Result<Vec<series::EventSeries>, AppError> {
+    let filter = filter.unwrap_or_default();
+    log::info!("query_event_series command invoked (filter: {:?}, sort: {:?})", filter, sort);
+    run_db_query(move |conn| {
+        let page = query::query_events(conn, &filter, sort.unwrap_or_default(), 0, None)?;
+        Ok(series::group_into_series(page.events))
+    })
+    .await
+}
+
+/// Stored events matching the typed `query`, best match first, with highlighted titles and snippets.
+#[tauri::command]
+async fn search_events(query: String, limit: Option<usize>) -> Result<Vec<search::SearchHit>, AppError> {
+    log::info!("search_events command invoked (query: {:?}, limit: {:?})", query, limit);
+    run_db_query(move |conn| search::search_stored_events(conn, &query, limit.unwrap_or(search::DEFAULT_LIMIT))).await
+}
+
+/// Stored events matching `filter` within `radius_km` of the point, nearest first, with their distance.
+#[tauri::command]
+async fn get_events_near(
+    latitude: f64,
+    longitude: f64,
+    radius_km: f64,
+    limit: Option<usize>,
+    filter: Option<query::EventFilter>,
+) -> Result<Vec<geo::NearbyEvent>, AppError> {
+    log::info!("get_events_near command invoked ({}, {}, {} km, filter: {:?})", latitude, longitude, radius_km, filter);
+    let filter = filter.unwrap_or_default();
+    run_db_query(move |conn| geo::stored_events_near(conn, &filter, latitude, longitude, radius_km, limit)).await
+}
+
+/// Stored events matching `filter` inside the visible map area.
+#[tauri::command]
+async fn get_events_in_bounds(bounds: geo::Bounds, filter: Option<query::EventFilter>) -> Result<Vec<Event>, AppError> {
+    let filter = filter.unwrap_or_default();
+    run_db_query(move |conn| geo::stored_events_in_bounds(conn, &filter, &bounds)).await
+}
+
+#[tauri::command]
+async fn get_venues() -> Result<Vec<Venue>, AppError> {
+    run_db_query(db::list_venues).await
+}
+
+/// The upcoming events at one venue.
+#[tauri::command]
+async fn get_venue_events(venue_id: i64) -> Result<Vec<Event>, AppError> {
+    run_db_query(move |conn| venue::upcoming_events_at_venue(conn, venue_id)).await
+}
+
+#[tauri::command]
+async fn get_scrape_runs(limit: Option<u32>) -> Result<Vec<db::ScrapeRun>, AppError> {
+    run_db_query(move |conn| db::list_scrape_runs(conn, limit.unwrap_or(20))).await
+}
+
+
+#[tauri::command]
+async fn get_event_changes(since: Option<DateTime<Utc>>) -> Result<Vec<changes::EventChange>, AppError> {
+    log::info!("get_event_changes command invoked (since: {:?})", since);
+    run_db_query(move |conn| db::query_event_changes(conn, since)).await
+}
+
+#[tauri::command]
+async fn generate_ics_rust(event_data: Event) -> Result<String, AppError> {
+    log::info!(
+        "generate_ics_rust command invoked for event: {}",
+        event_data.title
+    );
+
+    ics::event_to_ics(&event_data).map_err(|e| {
+        log::error!("{}", e);
+        e
+    })
+}
+
+/// Exports `events`, or the stored events `filter` selects when no events are passed.
+#[tauri::command]
+async fn generate_ics_batch_rust(
+    events: Option<Vec<Event>>,
+    filter: Option<query::EventFilter>,
+    group_series: Option<bool>,
+) -> Result<ics::IcsExport, AppError> {
+    let events = match events {
+        Some(events) => events,
+        None => {
+            let filter = filter.unwrap_or_default();
+            run_db_query(move |conn| query::query_events(conn, &filter, query::SortKey::DateAsc, 0, None)).await?.events
+        }
+    };
+    log::info!("generate_ics_batch_rust command invoked for {} events", events.len());
+
+    if group_series.unwrap_or(false) {
+        ics::export_series_calendar(&series::group_into_series(events))
+    } else {
+        ics::export_calendar(&events)
+    }
+}
@@ -0,0 +1,208 @@
+// File: src-tauri/src/error.rs
+use serde::ser::{SerializeMap, Serializer};
+use serde::Serialize;
+use std::fmt::Debug;
+
+/// Everything that can go wrong while scraping, storing or exporting events.
+///
+/// Serializes as `{ "code": "...", "message": "...", ...details }`, e.g.
+/// `{ "code": "http_status", "message": "https://... returned HTTP 503", "url": "https://...", "status": 503 }`,
+/// so the frontend and API clients can branch on `code` instead of parsing messages.
+#[derive(Debug, thiserror::Error)]
+pub enum AppError {
+    #[error("Request to {url} failed: {message}")]
+    Network { url: String, message: String },
+    #[error("Request to {url} timed out")]
+    Timeout { url: String },
+    #[error("{url} returned HTTP {status}")]
+    HttpStatus { url: String, status: u16 },
+    /// The site's robots.txt asks crawlers not to fetch `url`, so it wasn't requested.
+    #[error("{url} is disallowed by the site's robots.txt")]
+    RobotsDisallowed { url: String },
+    /// HTML that doesn't look like we expect, a bad selector or undecodable stored data.
+    #[error("Failed to parse {what}: {message}")]
+    Parse { what: String, message: String },
+    /// The event database or the cache directory it lives in.
+    #[error("Cache error ({context}): {message}")]
+    Cache { context: String, message: String },
+    /// Files other than the cache, e.g. an export target.
+    #[error("Failed to {context}: {message}")]
+    Io { context: String, message: String },
+    /// `field` is the `Event` field name, e.g. `start_date` or `full_url`.
+    #[error("Event '{title}' has no {field}.")]
+    MissingField {
+        event_id: String,
+        title: String,
+        field: &'static str,
+    },
+    /// A local time that doesn't exist in `timezone`, i.e. one in the hour skipped when DST starts.
+    #[error("'{datetime}' does not exist in the {timezone} timezone.")]
+    Timezone { datetime: String, timezone: &'static str },
+    #[error("{what} not found.")]
+    NotFound { what: String },
+    #[error("{task} is already running.")]
+    Busy { task: String },
+    #[error("{message}")]
+    InvalidRequest { message: String },
+    #[error("{message}")]
+    Internal { message: String },
+}
+
+impl AppError {
+    /// Machine-readable identifier of the variant, sent as `code`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Network { .. } => "network",
+            AppError::Timeout { .. } => "timeout",
+            AppError::HttpStatus { .. } => "http_status",
+            AppError::RobotsDisallowed { .. } => "robots_disallowed",
+            AppError::Parse { .. } => "parse",
+            AppError::Cache { .. } => "cache_io",
+            AppError::Io { .. } => "io",
+            AppError::MissingField { .. } => "missing_field",
+            AppError::Timezone { .. } => "timezone",
+            AppError::NotFound { .. } => "not_found",
+            AppError::Busy { .. } => "busy",
+            AppError::InvalidRequest { .. } => "invalid_request",
+            AppError::Internal { .. } => "internal",
+        }
+    }
+
+    /// Whether the same request may succeed when tried again: timeouts, dropped connections,
+    /// rate limiting (HTTP 429) and server errors (5xx).
+    pub fn is_transient(&self) -> bool {
+        match self {
+            AppError::Network { .. } | AppError::Timeout { .. } => true,
+            AppError::HttpStatus { status, .. } => *status == 429 || (500..600).contains(status),
+            _ => false,
+        }
+    }
+
+    pub fn parse(what: impl Into<String>, message: impl ToString) -> Self {
+        AppError::Parse {
+            what: what.into(),
+            message: message.to_string(),
+        }
+    }
+
+    /// A CSS selector that `scraper::Selector::parse` rejected.
+    pub fn selector(name: &str, error: impl Debug) -> Self {
+        AppError::parse(format!("selector {}", name), format!("{:?}", error))
+    }
+
+    pub fn cache(context: impl Into<String>, message: impl ToString) -> Self {
+        AppError::Cache {
+            context: context.into(),
+            message: message.to_string(),
+        }
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        AppError::Internal { message: message.into() }
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(error: reqwest::Error) -> Self {
+        let url = error.url().map(|url| url.to_string()).unwrap_or_default();
+        if let Some(status) = error.status() {
+            AppError::HttpStatus {
+                url,
+                status: status.as_u16(),
+            }
+        } else if error.is_timeout() {
+            AppError::Timeout { url }
+        } else {
+            AppError::Network {
+                url,
+                message: error.without_url().to_string(),
+            }
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("code", self.code())?;
+        map.serialize_entry("message", &self.to_string())?;
+        match self {
+            AppError::Network { url, .. } | AppError::Timeout { url } | AppError::RobotsDisallowed { url } => {
+                map.serialize_entry("url", url)?
+            }
+            AppError::HttpStatus { url, status } => {
+                map.serialize_entry("url", url)?;
+                map.serialize_entry("status", status)?;
+            }
+            AppError::MissingField { event_id, field, .. } => {
+                map.serialize_entry("event_id", event_id)?;
+                map.serialize_entry("field", field)?;
+            }
+            AppError::Timezone { datetime, timezone } => {
+                map.serialize_entry("datetime", datetime)?;
+                map.serialize_entry("timezone", timezone)?;
+            }
+            _ => {}
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serializes_code_message_and_details() {
+        let error = AppError::HttpStatus {
+            url: "https://thisiseindhoven.com/en/events?page=2".to_string(),
+            status: 503,
+        };
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            json!({
+                "code": "http_status",
+                "message": "https://thisiseindhoven.com/en/events?page=2 returned HTTP 503",
+                "url": "https://thisiseindhoven.com/en/events?page=2",
+                "status": 503,
+            })
+        );
+
+        let error = AppError::MissingField {
+            event_id: "/en/events/undated".to_string(),
+            title: "Undated".to_string(),
+            field: "start_date",
+        };
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            json!({
+                "code": "missing_field",
+                "message": "Event 'Undated' has no start_date.",
+                "event_id": "/en/events/undated",
+                "field": "start_date",
+            })
+        );
+    }
+
+    #[test]
+    fn only_timeouts_rate_limits_and_server_errors_are_transient() {
+        let status = |status| AppError::HttpStatus {
+            url: "https://www.thisiseindhoven.com/en/events?page=2".to_string(),
+            status,
+        };
+        assert!(status(503).is_transient());
+        assert!(status(429).is_transient());
+        assert!(!status(404).is_transient());
+        assert!(AppError::Timeout { url: String::new() }.is_transient());
+        assert!(!AppError::RobotsDisallowed { url: String::new() }.is_transient());
+        assert!(!AppError::parse("listing", "no cards").is_transient());
+    }
+
+    #[test]
+    fn selector_errors_are_parse_errors() {
+        let error = AppError::selector("card_selector", "unexpected token");
+        assert_eq!(error.code(), "parse");
+        assert_eq!(error.to_string(), "Failed to parse selector card_selector: \"unexpected token\"");
+    }
+}
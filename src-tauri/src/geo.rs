@@ -0,0 +1,268 @@
+// File: src-tauri/src/geo.rs
+// Where-queries over the coordinates scraped from the detail pages' map embed: events near a
+// point and events inside the visible part of the map.
+use rstar::primitives::GeomWithData;
+use rstar::{RTree, AABB};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::models::Event;
+use crate::query::{self, EventFilter, SortKey};
+
+/// Mean earth radius (IUGG).
+const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// A map area in degrees, as reported by the map view. Areas crossing the antimeridian
+/// aren't supported; Eindhoven is nowhere near it.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub south: f64,
+    pub west: f64,
+    pub north: f64,
+    pub east: f64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct NearbyEvent {
+    pub event: Event,
+    /// Great-circle distance from the searched point.
+    pub distance_km: f64,
+}
+
+/// Great-circle distance between two `(latitude, longitude)` points, in kilometres.
+pub fn haversine_km(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (lat1, lat2) = (from.0.to_radians(), to.0.to_radians());
+    let delta_lat = lat2 - lat1;
+    let delta_lon = (to.1 - from.1).to_radians();
+    let a = (delta_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (delta_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+fn check_point(latitude: f64, longitude: f64) -> Result<(), AppError> {
+    if (-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude) {
+        Ok(())
+    } else {
+        Err(AppError::InvalidRequest {
+            message: format!("({}, {}) is not a valid latitude and longitude.", latitude, longitude),
+        })
+    }
+}
+
+fn coordinates(event: &Event) -> Option<(f64, f64)> {
+    let (latitude, longitude) = (event.latitude?, event.longitude?);
+    check_point(latitude, longitude).ok().map(|_| (latitude, longitude))
+}
+
+// `[longitude, latitude]`, so the tree's x axis runs east-west, plus the event's position in `GeoIndex::events`.
+type EventPoint = GeomWithData<[f64; 2], usize>;
+
+/// R-tree over the events that have valid coordinates.
+pub struct GeoIndex {
+    events: Vec<Event>,
+    tree: RTree<EventPoint>,
+}
+
+impl GeoIndex {
+    pub fn new(events: Vec<Event>) -> GeoIndex {
+        let events: Vec<Event> = events.into_iter().filter(|event| coordinates(event).is_some()).collect();
+        let points = events
+            .iter()
+            .enumerate()
+            .filter_map(|(position, event)| {
+                let (latitude, longitude) = coordinates(event)?;
+                Some(EventPoint::new([longitude, latitude], position))
+            })
+            .collect();
+        GeoIndex {
+            events,
+            tree: RTree::bulk_load(points),
+        }
+    }
+
+    /// Events inside `bounds` (edges included), in stored order.
+    pub fn within_bounds(&self, bounds: &Bounds) -> Result<Vec<Event>, AppError> {
+        check_point(bounds.south, bounds.west)?;
+        check_point(bounds.north, bounds.east)?;
+        if bounds.south > bounds.north || bounds.west > bounds.east {
+            return Err(AppError::InvalidRequest {
+                message: "The south-west corner of the map area must lie below and left of the north-east corner.".to_string(),
+            });
+        }
+        let envelope = AABB::from_corners([bounds.west, bounds.south], [bounds.east, bounds.north]);
+        let mut positions: Vec<usize> = self.tree.locate_in_envelope(&envelope).map(|point| point.data).collect();
+        positions.sort_unstable();
+        Ok(positions.into_iter().map(|position| self.events[position].clone()).collect())
+    }
+
+    /// Events within `radius_km` of the point, nearest first.
+    pub fn within_radius(&self, latitude: f64, longitude: f64, radius_km: f64) -> Result<Vec<NearbyEvent>, AppError> {
+        check_point(latitude, longitude)?;
+        if !radius_km.is_finite() || radius_km <= 0.0 {
+            return Err(AppError::InvalidRequest {
+                message: format!("The radius must be a positive number of kilometres, not {}.", radius_km),
+            });
+        }
+        // A box of degrees that surely contains the circle; exact distances decide after that.
+        let lat_degrees = (radius_km / EARTH_RADIUS_KM).to_degrees();
+        let lon_degrees = (lat_degrees / latitude.to_radians().cos().max(0.01)).min(180.0);
+        let envelope = AABB::from_corners(
+            [longitude - lon_degrees, latitude - lat_degrees],
+            [longitude + lon_degrees, latitude + lat_degrees],
+        );
+
+        let mut nearby: Vec<NearbyEvent> = self
+            .tree
+            .locate_in_envelope(&envelope)
+            .filter_map(|point| {
+                let [event_longitude, event_latitude] = *point.geom();
+                let distance_km = haversine_km((latitude, longitude), (event_latitude, event_longitude));
+                (distance_km <= radius_km).then(|| NearbyEvent {
+                    event: self.events[point.data].clone(),
+                    distance_km,
+                })
+            })
+            .collect();
+        nearby.sort_by(|a, b| a.distance_km.total_cmp(&b.distance_km));
+        Ok(nearby)
+    }
+}
+
+// The stored events `filter` selects, for `query::query_events` to agree with the map.
+fn located_events(conn: &Connection, filter: &EventFilter) -> Result<Vec<Event>, AppError> {
+    let filter = EventFilter {
+        has_coordinates: true,
+        ..filter.clone()
+    };
+    Ok(query::query_events(conn, &filter, SortKey::DateAsc, 0, None)?.events)
+}
+
+/// Stored events matching `filter` within `radius_km` of the point, nearest first, at most
+/// `limit` of them.
+pub fn stored_events_near(
+    conn: &Connection,
+    filter: &EventFilter,
+    latitude: f64,
+    longitude: f64,
+    radius_km: f64,
+    limit: Option<usize>,
+) -> Result<Vec<NearbyEvent>, AppError> {
+    let index = GeoIndex::new(located_events(conn, filter)?);
+    let mut nearby = index.within_radius(latitude, longitude, radius_km)?;
+    nearby.truncate(limit.unwrap_or(usize::MAX));
+    Ok(nearby)
+}
+
+/// Stored events matching `filter` inside the map area `bounds`.
+pub fn stored_events_in_bounds(conn: &Connection, filter: &EventFilter, bounds: &Bounds) -> Result<Vec<Event>, AppError> {
+    GeoIndex::new(located_events(conn, filter)?).within_bounds(bounds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EINDHOVEN_CENTRAAL: (f64, f64) = (51.4433, 5.4797);
+
+    fn event(id: &str, coordinates: Option<(f64, f64)>) -> Event {
+        Event {
+            id: id.to_string(),
+            title: id.to_string(),
+            latitude: coordinates.map(|(latitude, _)| latitude),
+            longitude: coordinates.map(|(_, longitude)| longitude),
+            ..Event::default()
+        }
+    }
+
+    fn sample_index() -> GeoIndex {
+        GeoIndex::new(vec![
+            event("strijp-s", Some((51.4486, 5.4573))),
+            event("amsterdam", Some((52.3791, 4.9003))),
+            event("no-coordinates", None),
+            event("stratumseind", Some((51.4379, 5.4826))),
+        ])
+    }
+
+    #[test]
+    fn haversine_matches_known_distances() {
+        let amsterdam_centraal = (52.3791, 4.9003);
+        assert!((haversine_km(EINDHOVEN_CENTRAAL, amsterdam_centraal) - 111.39).abs() < 0.01);
+        assert_eq!(haversine_km(EINDHOVEN_CENTRAAL, EINDHOVEN_CENTRAAL), 0.0);
+    }
+
+    #[test]
+    fn finds_events_within_radius_nearest_first() {
+        let (latitude, longitude) = EINDHOVEN_CENTRAAL;
+        let nearby = sample_index().within_radius(latitude, longitude, 2.0).unwrap();
+        let ids: Vec<&str> = nearby.iter().map(|hit| hit.event.id.as_str()).collect();
+        assert_eq!(ids, ["stratumseind", "strijp-s"]);
+        assert!((nearby[0].distance_km - 0.633).abs() < 0.001);
+        assert!((nearby[1].distance_km - 1.660).abs() < 0.001);
+
+        let all = sample_index().within_radius(latitude, longitude, 200.0).unwrap();
+        assert_eq!(all.len(), 3);
+    }
+
+    #[test]
+    fn finds_events_inside_bounds() {
+        let bounds = Bounds {
+            south: 51.40,
+            west: 5.40,
+            north: 51.45,
+            east: 5.47,
+        };
+        let ids: Vec<String> = sample_index().within_bounds(&bounds).unwrap().into_iter().map(|event| event.id).collect();
+        assert_eq!(ids, ["strijp-s"]);
+    }
+
+    #[test]
+    fn rejects_invalid_areas() {
+        let index = sample_index();
+        assert_eq!(index.within_radius(91.0, 5.0, 1.0).unwrap_err().code(), "invalid_request");
+        assert_eq!(index.within_radius(51.0, 5.0, 0.0).unwrap_err().code(), "invalid_request");
+        let flipped = Bounds {
+            south: 51.5,
+            west: 5.4,
+            north: 51.4,
+            east: 5.5,
+        };
+        assert_eq!(index.within_bounds(&flipped).unwrap_err().code(), "invalid_request");
+    }
+
+    #[test]
+    fn stored_event_queries_apply_the_event_filter() {
+        let db_path = std::env::temp_dir().join(format!("eindhoven-events-geo-{}.db", std::process::id()));
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", db_path.display(), suffix));
+        }
+        let mut conn = crate::db::open_at(&db_path).unwrap();
+        let at_venue = |id: &str, coordinates, venue: &str| Event {
+            list_specific_location: Some(venue.to_string()),
+            ..event(id, coordinates)
+        };
+        let events = [
+            at_venue("strijp-s", Some((51.4486, 5.4573)), "Area51"),
+            at_venue("stratumseind", Some((51.4379, 5.4826)), "Café Wilhelmina"),
+            at_venue("no-coordinates", None, "Area51"),
+        ];
+        crate::db::record_scrape(&mut conn, chrono::Utc::now(), 1, &events, crate::db::ScrapeCoverage::Complete).unwrap();
+
+        let filter = EventFilter {
+            venue: Some("area51".to_string()),
+            ..EventFilter::default()
+        };
+        let (latitude, longitude) = EINDHOVEN_CENTRAAL;
+        let nearby = stored_events_near(&conn, &filter, latitude, longitude, 5.0, None).unwrap();
+        let ids: Vec<&str> = nearby.iter().map(|hit| hit.event.id.as_str()).collect();
+        assert_eq!(ids, ["strijp-s"]);
+
+        let bounds = Bounds {
+            south: 51.40,
+            west: 5.40,
+            north: 51.50,
+            east: 5.50,
+        };
+        assert_eq!(stored_events_in_bounds(&conn, &EventFilter::default(), &bounds).unwrap().len(), 2);
+        assert_eq!(stored_events_in_bounds(&conn, &filter, &bounds).unwrap().len(), 1);
+    }
+}
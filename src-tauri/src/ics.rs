@@ -0,0 +1,753 @@
+// File: src-tauri/src/ics.rs
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::{OffsetComponents, OffsetName, Tz};
+use serde::Serialize;
+
+use crate::error::AppError;
+use crate::models::Event;
+use crate::series::{EventSeries, Recurrence};
+
+/// Events on thisiseindhoven.com are listed in Eindhoven local time.
+const EVENT_TIMEZONE: Tz = chrono_tz::Europe::Amsterdam;
+/// RFC 5545 section 3.1: content lines SHOULD NOT be longer than 75 octets.
+const MAX_LINE_OCTETS: usize = 75;
+const PRODID: &str = "-//EindhovenEventViewer//NONSGML v1.0//EN";
+
+#[derive(Debug, Serialize)]
+pub struct SkippedEvent {
+    pub id: String,
+    pub title: String,
+    pub reason: AppError,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IcsExport {
+    pub ics: String,
+    pub exported_count: u32,
+    pub skipped: Vec<SkippedEvent>,
+}
+
+pub fn escape_text(text: &str) -> String {
+    text.replace("\\", "\\\\")
+        .replace(";", "\\;")
+        .replace(",", "\\,")
+        .replace("\r\n", "\n")
+        .replace("\n", "\\n")
+}
+
+/// Folds a content line so that no physical line exceeds 75 octets, never splitting a
+/// UTF-8 character. Continuation lines start with a single space.
+pub fn fold_line(line: &str) -> String {
+    if line.len() <= MAX_LINE_OCTETS {
+        return line.to_string();
+    }
+    let mut folded = String::with_capacity(line.len() + 3 * (line.len() / (MAX_LINE_OCTETS - 1)));
+    let mut line_octets = 0;
+    for ch in line.chars() {
+        if line_octets + ch.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            line_octets = 1; // the leading space counts towards the limit
+        }
+        folded.push(ch);
+        line_octets += ch.len_utf8();
+    }
+    folded
+}
+
+fn format_local_datetime(naive_dt: NaiveDateTime) -> Result<String, AppError> {
+    // Reject local times that don't exist (the hour skipped when DST starts).
+    match EVENT_TIMEZONE.from_local_datetime(&naive_dt).latest() {
+        Some(_) => Ok(naive_dt.format("%Y%m%dT%H%M%S").to_string()),
+        None => Err(AppError::Timezone {
+            datetime: naive_dt.format("%Y-%m-%d %H:%M:%S").to_string(),
+            timezone: EVENT_TIMEZONE.name(),
+        }),
+    }
+}
+
+fn format_utc_offset(offset_seconds: i32) -> String {
+    let sign = if offset_seconds < 0 { '-' } else { '+' };
+    let seconds = offset_seconds.abs();
+    let (hours, minutes, secs) = (seconds / 3600, (seconds % 3600) / 60, seconds % 60);
+    if secs == 0 {
+        format!("{}{:02}{:02}", sign, hours, minutes)
+    } else {
+        format!("{}{:02}{:02}{:02}", sign, hours, minutes, secs)
+    }
+}
+
+/// A change of UTC offset in `EVENT_TIMEZONE`.
+struct Transition {
+    at: DateTime<Utc>,
+    offset_from: i32,
+    offset_to: i32,
+    is_dst: bool,
+    name: Option<String>,
+}
+
+fn offset_seconds(at: DateTime<Utc>) -> i32 {
+    EVENT_TIMEZONE.offset_from_utc_datetime(&at.naive_utc()).fix().local_minus_utc()
+}
+
+/// Finds every offset change between `from` and `to` by scanning hourly and then
+/// narrowing each change down to the second.
+fn find_transitions(from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<Transition> {
+    let mut transitions = Vec::new();
+    let mut cursor = from;
+    let mut current_offset = offset_seconds(cursor);
+    while cursor < to {
+        let next = cursor + Duration::hours(1);
+        let next_offset = offset_seconds(next);
+        if next_offset != current_offset {
+            let (mut low, mut high) = (cursor, next);
+            while high - low > Duration::seconds(1) {
+                let mid = low + (high - low) / 2;
+                if offset_seconds(mid) == current_offset {
+                    low = mid;
+                } else {
+                    high = mid;
+                }
+            }
+            let offset = EVENT_TIMEZONE.offset_from_utc_datetime(&high.naive_utc());
+            transitions.push(Transition {
+                at: high,
+                offset_from: current_offset,
+                offset_to: next_offset,
+                is_dst: !offset.dst_offset().is_zero(),
+                name: offset.abbreviation().map(str::to_string),
+            });
+            current_offset = next_offset;
+        }
+        cursor = next;
+    }
+    transitions
+}
+
+fn observance_lines(kind: &str, dtstart: NaiveDateTime, offset_from: i32, offset_to: i32, name: Option<&str>) -> Vec<String> {
+    let mut lines = vec![
+        format!("BEGIN:{}", kind),
+        format!("DTSTART:{}", dtstart.format("%Y%m%dT%H%M%S")),
+        format!("TZOFFSETFROM:{}", format_utc_offset(offset_from)),
+        format!("TZOFFSETTO:{}", format_utc_offset(offset_to)),
+    ];
+    if let Some(name) = name {
+        lines.push(format!("TZNAME:{}", escape_text(name)));
+    }
+    lines.push(format!("END:{}", kind));
+    lines
+}
+
+/// Builds a VTIMEZONE for `EVENT_TIMEZONE` from the tz database, covering every year in
+/// `first_year..=last_year` including the observance already in effect on January 1st.
+pub fn vtimezone_lines(first_year: i32, last_year: i32) -> Vec<String> {
+    let year_start = |year: i32| {
+        NaiveDate::from_ymd_opt(year, 1, 1)
+            .map(|date| Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap_or_default()))
+            .unwrap_or_else(Utc::now)
+    };
+    let all_transitions = find_transitions(year_start(first_year - 1), year_start(last_year + 1));
+    let range_start = year_start(first_year);
+    // Keep the last transition before the range so its start is covered too.
+    let first_kept = all_transitions
+        .iter()
+        .rposition(|t| t.at < range_start)
+        .unwrap_or(0);
+
+    let mut lines = vec![
+        "BEGIN:VTIMEZONE".to_string(),
+        format!("TZID:{}", EVENT_TIMEZONE.name()),
+    ];
+    let transitions = &all_transitions[first_kept.min(all_transitions.len())..];
+    if transitions.is_empty() {
+        // No offset changes in range: one STANDARD observance describes the whole period.
+        let offset = EVENT_TIMEZONE.offset_from_utc_datetime(&range_start.naive_utc());
+        let offset_seconds = offset.fix().local_minus_utc();
+        lines.extend(observance_lines(
+            "STANDARD",
+            NaiveDate::from_ymd_opt(1970, 1, 1).unwrap_or_default().and_hms_opt(0, 0, 0).unwrap_or_default(),
+            offset_seconds,
+            offset_seconds,
+            offset.abbreviation(),
+        ));
+    }
+    for transition in transitions {
+        // DTSTART is expressed in the local time that was in effect before the change.
+        let local_onset = transition.at.naive_utc() + Duration::seconds(transition.offset_from as i64);
+        lines.extend(observance_lines(
+            if transition.is_dst { "DAYLIGHT" } else { "STANDARD" },
+            local_onset,
+            transition.offset_from,
+            transition.offset_to,
+            transition.name.as_deref(),
+        ));
+    }
+    lines.push("END:VTIMEZONE".to_string());
+    lines
+}
+
+fn missing_start_error(event_data: &Event, field: &'static str) -> AppError {
+    AppError::MissingField {
+        event_id: event_data.id.clone(),
+        title: event_data.title.clone(),
+        field,
+    }
+}
+
+/// `DTSTART`/`DTEND` for an all-day event. `DTEND` is exclusive, so it is the day after
+/// the last day.
+fn all_day_lines(event_data: &Event) -> Result<Vec<String>, AppError> {
+    let start_date = event_data.start_date.ok_or_else(|| missing_start_error(event_data, "start_date"))?;
+    let end_date = match event_data.end_date {
+        Some(end_date) if end_date >= start_date => end_date,
+        Some(_) => {
+            log::warn!("End date is before start date for event '{}'. Treating it as a single day.", event_data.title);
+            start_date
+        }
+        None => start_date,
+    };
+    let mut lines = vec![format!("DTSTART;VALUE=DATE:{}", start_date.format("%Y%m%d"))];
+    if let Some(day_after) = end_date.succ_opt() {
+        lines.push(format!("DTEND;VALUE=DATE:{}", day_after.format("%Y%m%d")));
+    }
+    Ok(lines)
+}
+
+/// `DTSTART`/`DTEND` for a timed event, as local times in `EVENT_TIMEZONE`.
+fn timed_lines(event_data: &Event) -> Result<Vec<String>, AppError> {
+    let tzid = EVENT_TIMEZONE.name();
+    let start_naive_dt = event_data.start_datetime.ok_or_else(|| missing_start_error(event_data, "start_datetime"))?;
+    let mut lines = vec![format!("DTSTART;TZID={}:{}", tzid, format_local_datetime(start_naive_dt)?)];
+
+    if let Some(end_naive_dt) = event_data.end_datetime {
+        if end_naive_dt > start_naive_dt {
+            match format_local_datetime(end_naive_dt) {
+                Ok(end_dt_str) => lines.push(format!("DTEND;TZID={}:{}", tzid, end_dt_str)),
+                Err(e) => log::warn!("Could not format end datetime for ICS for event '{}': {}. Omitting DTEND.", event_data.title, e),
+            }
+        } else {
+            log::warn!("End datetime is not after start datetime for event '{}'. Omitting DTEND.", event_data.title);
+        }
+    }
+    Ok(lines)
+}
+
+/// `RRULE` or `RDATE` repeating the series' first occurrence on the other dates.
+fn recurrence_lines(recurrence: &Recurrence, all_day: bool) -> Result<Vec<String>, AppError> {
+    let rule = |freq: &str, interval: i64, count: usize| match interval {
+        1 => format!("RRULE:FREQ={};COUNT={}", freq, count),
+        _ => format!("RRULE:FREQ={};INTERVAL={};COUNT={}", freq, interval, count),
+    };
+    Ok(vec![match recurrence {
+        Recurrence::Daily { interval, count } => rule("DAILY", *interval, *count),
+        Recurrence::Weekly { interval, count } => rule("WEEKLY", *interval, *count),
+        Recurrence::Dates(starts) if all_day => format!(
+            "RDATE;VALUE=DATE:{}",
+            starts.iter().map(|start| start.format("%Y%m%d").to_string()).collect::<Vec<_>>().join(",")
+        ),
+        Recurrence::Dates(starts) => format!(
+            "RDATE;TZID={}:{}",
+            EVENT_TIMEZONE.name(),
+            starts.iter().map(|start| format_local_datetime(*start)).collect::<Result<Vec<_>, _>>()?.join(",")
+        ),
+    }])
+}
+
+/// Builds the content lines of one VEVENT, including BEGIN/END. Fails when the event has
+/// no usable start date.
+pub fn vevent_lines(event_data: &Event, dtstamp: &str) -> Result<Vec<String>, AppError> {
+    component_lines(event_data, &event_data.id, Vec::new(), dtstamp)
+}
+
+/// One VEVENT for a whole series: its first occurrence, repeated with `RRULE` when the dates
+/// are regular and `RDATE` when they aren't. `None` when the occurrences need VEVENTs of
+/// their own; see `EventSeries::recurrence`.
+pub fn series_vevent_lines(series: &EventSeries, dtstamp: &str) -> Option<Result<Vec<String>, AppError>> {
+    let recurrence = series.recurrence()?;
+    let first = &series.occurrences[0];
+    Some(
+        recurrence_lines(&recurrence, first.all_day)
+            .and_then(|rule_lines| component_lines(first, &series.id, rule_lines, dtstamp)),
+    )
+}
+
+fn component_lines(event_data: &Event, uid: &str, rule_lines: Vec<String>, dtstamp: &str) -> Result<Vec<String>, AppError> {
+    let schedule_lines = if event_data.all_day {
+        all_day_lines(event_data)?
+    } else {
+        timed_lines(event_data)?
+    };
+
+    let mut ics_event_lines: Vec<String> = vec!["BEGIN:VEVENT".to_string()];
+    ics_event_lines.push(format!("UID:{}", escape_text(uid)));
+    ics_event_lines.push(format!("DTSTAMP:{}", dtstamp));
+    ics_event_lines.extend(schedule_lines);
+    ics_event_lines.extend(rule_lines);
+
+    ics_event_lines.push(format!("SUMMARY:{}", escape_text(&event_data.title)));
+
+    let location_display = event_data.location_display().unwrap_or_else(|| "Eindhoven".to_string());
+
+    if !location_display.is_empty() {
+        ics_event_lines.push(format!("LOCATION:{}", escape_text(&location_display)));
+    }
+
+    // Event Page URL (Standard Property)
+    if let Some(url) = event_data.full_url.as_deref() {
+        let trimmed_url = url.trim();
+        if !trimmed_url.is_empty() {
+            ics_event_lines.push(format!("URL:{}", trimmed_url)); // URL should not be escaped like text
+        }
+    }
+
+    // Ticket URL (Custom Property)
+    if let Some(ticket_url) = event_data.ticket_url.as_deref() {
+        let trimmed_ticket_url = ticket_url.trim();
+        if !trimmed_ticket_url.is_empty() {
+            // Using X- property for custom fields. Support varies.
+            ics_event_lines.push(format!("X-TICKET-URL:{}", trimmed_ticket_url)); // URL should not be escaped
+        }
+    }
+
+    // Build Description
+    let mut description_parts: Vec<String> = Vec::new();
+    if let Some(desc) = event_data.full_description.as_deref().or(event_data.short_description.as_deref()) {
+        let trimmed_desc = desc.trim();
+        if !trimmed_desc.is_empty() && trimmed_desc.to_lowercase() != "n/a" {
+            description_parts.push(trimmed_desc.to_string());
+        }
+    }
+    if let Some(price) = event_data.price.as_deref().or(event_data.list_price.as_deref()) {
+        let trimmed_price = price.trim();
+        if !trimmed_price.is_empty() && trimmed_price.to_lowercase() != "n/a" {
+            description_parts.push(format!("Price: {}", trimmed_price));
+        }
+    }
+
+    // Add URLs to description for guaranteed visibility
+    if let Some(url) = event_data.full_url.as_deref() {
+        let trimmed_url = url.trim();
+        if !trimmed_url.is_empty() {
+            description_parts.push(format!("More Info: {}", trimmed_url));
+        }
+    }
+    if let Some(ticket_url) = event_data.ticket_url.as_deref() {
+        let trimmed_ticket_url = ticket_url.trim();
+        if !trimmed_ticket_url.is_empty() {
+            description_parts.push(format!("Buy Tickets: {}", trimmed_ticket_url));
+        }
+    }
+
+    if !description_parts.is_empty() {
+        let full_description_text = description_parts.join("\n\n");
+        ics_event_lines.push(format!("DESCRIPTION:{}", escape_text(&full_description_text)));
+    }
+
+    ics_event_lines.push("END:VEVENT".to_string());
+    Ok(ics_event_lines)
+}
+
+/// Assembles a VCALENDAR from already built VEVENTs, adding the VTIMEZONE their
+/// `TZID` references point to when `years` is set. Every line is folded and CRLF-terminated.
+fn calendar(vevents: &[Vec<String>], years: Option<(i32, i32)>) -> String {
+    let mut lines: Vec<String> = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODID),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+    if let Some((first_year, last_year)) = years {
+        lines.extend(vtimezone_lines(first_year, last_year));
+    }
+    for vevent in vevents {
+        lines.extend(vevent.iter().cloned());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut ics_content = String::new();
+    for line in lines {
+        ics_content.push_str(&fold_line(&line));
+        ics_content.push_str("\r\n");
+    }
+    ics_content
+}
+
+/// Years a timed event's `TZID` times fall in; all-day events don't reference the VTIMEZONE.
+fn event_years(event: &Event) -> impl Iterator<Item = i32> {
+    let timed = !event.all_day;
+    event
+        .start_datetime
+        .into_iter()
+        .chain(event.end_datetime)
+        .filter(move |_| timed)
+        .map(|dt| dt.year())
+}
+
+/// Builds a calendar with one VEVENT per event that has a start date; the others
+/// are reported in `skipped`. Fails only when no event could be exported at all, since
+/// a VCALENDAR needs at least one component.
+pub fn export_calendar(events: &[Event]) -> Result<IcsExport, AppError> {
+    let mut export = CalendarBuilder::default();
+    for event in events {
+        export.add_event(event);
+    }
+    export.finish(events.len())
+}
+
+/// Like `export_calendar`, but a recurring series becomes one VEVENT that repeats, as long
+/// as its occurrences only differ in their dates.
+pub fn export_series_calendar(series: &[EventSeries]) -> Result<IcsExport, AppError> {
+    let mut export = CalendarBuilder::default();
+    for one_series in series {
+        match series_vevent_lines(one_series, &export.dtstamp) {
+            Some(Ok(lines)) => {
+                export.vevents.push(lines);
+                for event in &one_series.occurrences {
+                    export.add_years(event);
+                }
+            }
+            Some(Err(reason)) => {
+                log::warn!("Skipping series '{}' in ICS export: {}", one_series.id, reason);
+                export.skipped.push(SkippedEvent {
+                    id: one_series.id.clone(),
+                    title: one_series.title.clone(),
+                    reason,
+                });
+            }
+            None => {
+                for event in &one_series.occurrences {
+                    export.add_event(event);
+                }
+            }
+        }
+    }
+    export.finish(series.len())
+}
+
+struct CalendarBuilder {
+    dtstamp: String,
+    vevents: Vec<Vec<String>>,
+    skipped: Vec<SkippedEvent>,
+    years: Option<(i32, i32)>,
+}
+
+impl Default for CalendarBuilder {
+    fn default() -> Self {
+        CalendarBuilder {
+            dtstamp: Utc::now().format("%Y%m%dT%H%M%SZ").to_string(),
+            vevents: Vec::new(),
+            skipped: Vec::new(),
+            years: None,
+        }
+    }
+}
+
+impl CalendarBuilder {
+    fn add_years(&mut self, event: &Event) {
+        for year in event_years(event) {
+            self.years = Some(match self.years {
+                Some((first, last)) => (first.min(year), last.max(year)),
+                None => (year, year),
+            });
+        }
+    }
+
+    fn add_event(&mut self, event: &Event) {
+        match vevent_lines(event, &self.dtstamp) {
+            Ok(lines) => {
+                self.vevents.push(lines);
+                self.add_years(event);
+            }
+            Err(reason) => {
+                log::warn!("Skipping event '{}' in ICS export: {}", event.id, reason);
+                self.skipped.push(SkippedEvent {
+                    id: event.id.clone(),
+                    title: event.title.clone(),
+                    reason,
+                });
+            }
+        }
+    }
+
+    // `requested` is how many events (or series) were asked for.
+    fn finish(self, requested: usize) -> Result<IcsExport, AppError> {
+        if requested == 0 {
+            return Err(AppError::InvalidRequest {
+                message: "There are no events to export.".to_string(),
+            });
+        }
+        if self.vevents.is_empty() {
+            return Err(AppError::InvalidRequest {
+                message: format!(
+                    "None of the {} events could be exported. The first failed with: {}",
+                    requested,
+                    self.skipped[0].reason
+                ),
+            });
+        }
+
+        Ok(IcsExport {
+            ics: calendar(&self.vevents, self.years),
+            exported_count: self.vevents.len() as u32,
+            skipped: self.skipped,
+        })
+    }
+}
+
+/// Like `export_calendar`, but an empty calendar when there is nothing to export: a subscribed
+/// feed has to keep working when no events match. Other errors are passed on.
+pub fn feed_calendar(events: &[Event]) -> Result<String, AppError> {
+    match export_calendar(events) {
+        Ok(export) => Ok(export.ics),
+        Err(AppError::InvalidRequest { .. }) => Ok(calendar(&[], None)),
+        Err(e) => Err(e),
+    }
+}
+
+/// Builds a single-event calendar.
+pub fn event_to_ics(event: &Event) -> Result<String, AppError> {
+    let dtstamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let vevent = vevent_lines(event, &dtstamp)?;
+    let years = event_years(event).fold(None, |range: Option<(i32, i32)>, year| match range {
+        Some((first, last)) => Some((first.min(year), last.max(year))),
+        None => Some((year, year)),
+    });
+    Ok(calendar(&[vevent], years))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ical::parser::ical::component::IcalCalendar;
+    use ical::property::Property;
+    use std::io::BufReader;
+
+    fn sample_event() -> Event {
+        Event {
+            id: "/en/events/summer-art-club-creative-with-the-garden/9-july".to_string(),
+            title: "Summer Art Club: creative, with; the garden".to_string(),
+            full_url: Some("https://www.thisiseindhoven.com/en/events/summer-art-club-creative-with-the-garden/9-july".to_string()),
+            start_datetime: NaiveDate::from_ymd_opt(2025, 7, 9).unwrap().and_hms_opt(10, 0, 0),
+            end_datetime: NaiveDate::from_ymd_opt(2025, 7, 9).unwrap().and_hms_opt(12, 30, 0),
+            full_description: Some(
+                "Did you know that the Van Abbemuseum has a beautiful garden? Let yourself be inspired by everything that grows, blooms, and is built.\nDuring this workshop you'll work with plant-based paints — no experience needed; ages 6–12.".to_string(),
+            ),
+            address: Some("Van Abbemuseum, Stratumsedijk 2, 5611 NB, Eindhoven".to_string()),
+            price: Some("€ 7,50".to_string()),
+            ..Event::default()
+        }
+    }
+
+    fn parse_calendar(ics: &str) -> IcalCalendar {
+        let mut parser = ical::IcalParser::new(BufReader::new(ics.as_bytes()));
+        let calendar = parser.next().expect("one calendar").expect("calendar parses");
+        assert!(parser.next().is_none(), "exactly one calendar");
+        calendar
+    }
+
+    fn property<'a>(properties: &'a [Property], name: &str) -> &'a Property {
+        properties
+            .iter()
+            .find(|p| p.name == name)
+            .unwrap_or_else(|| panic!("missing property {}", name))
+    }
+
+    fn unescape_text(value: &str) -> String {
+        value
+            .replace("\\n", "\n")
+            .replace("\\,", ",")
+            .replace("\\;", ";")
+            .replace("\\\\", "\\")
+    }
+
+    #[test]
+    fn folds_long_lines_at_75_octets_without_splitting_characters() {
+        let line = format!("DESCRIPTION:{}", "Café — grüße ".repeat(20));
+        let folded = fold_line(&line);
+        for physical_line in folded.split("\r\n") {
+            assert!(physical_line.len() <= MAX_LINE_OCTETS, "{:?} is {} octets", physical_line, physical_line.len());
+        }
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+
+    #[test]
+    fn short_lines_are_not_folded() {
+        assert_eq!(fold_line("SUMMARY:Short"), "SUMMARY:Short");
+    }
+
+    #[test]
+    fn event_round_trips_through_parser() {
+        let event = sample_event();
+        let ics = event_to_ics(&event).unwrap();
+        assert!(ics.split("\r\n").all(|line| line.len() <= MAX_LINE_OCTETS));
+
+        let calendar = parse_calendar(&ics);
+        assert_eq!(calendar.events.len(), 1);
+        let vevent = &calendar.events[0];
+
+        let dtstart = property(&vevent.properties, "DTSTART");
+        assert_eq!(dtstart.value.as_deref(), Some("20250709T100000"));
+        assert_eq!(
+            dtstart.params,
+            Some(vec![("TZID".to_string(), vec!["Europe/Amsterdam".to_string()])])
+        );
+        assert_eq!(property(&vevent.properties, "DTEND").value.as_deref(), Some("20250709T123000"));
+        assert_eq!(
+            unescape_text(property(&vevent.properties, "SUMMARY").value.as_deref().unwrap()),
+            event.title
+        );
+        let description = unescape_text(property(&vevent.properties, "DESCRIPTION").value.as_deref().unwrap());
+        assert!(description.starts_with(event.full_description.as_deref().unwrap()));
+        assert!(description.contains("Price: € 7,50"));
+        assert_eq!(
+            unescape_text(property(&vevent.properties, "LOCATION").value.as_deref().unwrap()),
+            event.address.unwrap()
+        );
+    }
+
+    #[test]
+    fn location_is_built_from_the_address_parts() {
+        let event = Event {
+            address: None,
+            specific_location_name: Some("Stage Music Café".to_string()),
+            street: Some("Stratumseind 23".to_string()),
+            postal_code: Some("5611 ET".to_string()),
+            locality: Some("Eindhoven".to_string()),
+            ..sample_event()
+        };
+        let calendar = parse_calendar(&event_to_ics(&event).unwrap());
+        assert_eq!(
+            unescape_text(property(&calendar.events[0].properties, "LOCATION").value.as_deref().unwrap()),
+            "Stage Music Café, Stratumseind 23, 5611 ET, Eindhoven"
+        );
+    }
+
+    #[test]
+    fn calendar_defines_the_referenced_timezone() {
+        let calendar = parse_calendar(&event_to_ics(&sample_event()).unwrap());
+        assert_eq!(calendar.timezones.len(), 1);
+        let timezone = &calendar.timezones[0];
+        assert_eq!(property(&timezone.properties, "TZID").value.as_deref(), Some("Europe/Amsterdam"));
+
+        let onsets: Vec<(String, String, String)> = timezone
+            .transitions
+            .iter()
+            .map(|t| {
+                (
+                    property(&t.properties, "DTSTART").value.clone().unwrap(),
+                    property(&t.properties, "TZOFFSETFROM").value.clone().unwrap(),
+                    property(&t.properties, "TZOFFSETTO").value.clone().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            onsets,
+            vec![
+                ("20241027T030000".to_string(), "+0200".to_string(), "+0100".to_string()),
+                ("20250330T020000".to_string(), "+0100".to_string(), "+0200".to_string()),
+                ("20251026T030000".to_string(), "+0200".to_string(), "+0100".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn all_day_range_uses_date_values() {
+        let event = Event {
+            id: "/en/events/dutch-design-week-2025".to_string(),
+            title: "Dutch Design Week 2025".to_string(),
+            start_date: NaiveDate::from_ymd_opt(2025, 10, 18),
+            end_date: NaiveDate::from_ymd_opt(2025, 10, 26),
+            all_day: true,
+            ..Event::default()
+        };
+        let calendar = parse_calendar(&event_to_ics(&event).unwrap());
+        let vevent = &calendar.events[0];
+        let date_param = Some(vec![("VALUE".to_string(), vec!["DATE".to_string()])]);
+
+        let dtstart = property(&vevent.properties, "DTSTART");
+        assert_eq!(dtstart.value.as_deref(), Some("20251018"));
+        assert_eq!(dtstart.params, date_param);
+        // DTEND is exclusive: the day after the last day.
+        let dtend = property(&vevent.properties, "DTEND");
+        assert_eq!(dtend.value.as_deref(), Some("20251027"));
+        assert_eq!(dtend.params, date_param);
+        assert!(calendar.timezones.is_empty(), "date values don't reference a timezone");
+    }
+
+    #[test]
+    fn export_skips_events_without_start_time() {
+        let undated = Event {
+            id: "/en/events/undated".to_string(),
+            title: "Undated".to_string(),
+            ..Event::default()
+        };
+        let export = export_calendar(&[sample_event(), undated]).unwrap();
+        assert_eq!(export.exported_count, 1);
+        assert_eq!(export.skipped.len(), 1);
+        assert_eq!(export.skipped[0].id, "/en/events/undated");
+        assert_eq!(export.skipped[0].reason.code(), "missing_field");
+        assert_eq!(parse_calendar(&export.ics).events.len(), 1);
+    }
+
+    #[test]
+    fn feed_without_exportable_events_is_an_empty_calendar() {
+        let undated = Event {
+            id: "/en/events/undated".to_string(),
+            ..Event::default()
+        };
+        for events in [Vec::new(), vec![undated]] {
+            let feed = feed_calendar(&events).unwrap();
+            assert!(parse_calendar(&feed).events.is_empty());
+        }
+    }
+
+    fn sample_occurrence(day: u32, month: u32) -> Event {
+        let start = NaiveDate::from_ymd_opt(2025, month, day).unwrap().and_hms_opt(10, 0, 0).unwrap();
+        Event {
+            id: format!("/en/events/summer-art-club-creative-with-the-garden/{}-{}", day, month),
+            series_id: Some("/en/events/summer-art-club-creative-with-the-garden".to_string()),
+            start_datetime: Some(start),
+            end_datetime: Some(start + Duration::minutes(150)),
+            ..sample_event()
+        }
+    }
+
+    #[test]
+    fn weekly_series_is_one_repeating_event() {
+        let series = crate::series::group_into_series(vec![sample_occurrence(9, 7), sample_occurrence(16, 7), sample_occurrence(23, 7)]);
+        let export = export_series_calendar(&series).unwrap();
+        assert_eq!(export.exported_count, 1);
+
+        let calendar = parse_calendar(&export.ics);
+        assert_eq!(calendar.events.len(), 1);
+        let properties = &calendar.events[0].properties;
+        assert_eq!(property(properties, "UID").value.as_deref(), Some("/en/events/summer-art-club-creative-with-the-garden"));
+        assert_eq!(property(properties, "DTSTART").value.as_deref(), Some("20250709T100000"));
+        assert_eq!(property(properties, "RRULE").value.as_deref(), Some("FREQ=WEEKLY;COUNT=3"));
+    }
+
+    #[test]
+    fn irregular_series_lists_its_other_dates() {
+        let series = crate::series::group_into_series(vec![sample_occurrence(6, 8), sample_occurrence(9, 7), sample_occurrence(16, 7)]);
+        let calendar = parse_calendar(&export_series_calendar(&series).unwrap().ics);
+        let properties = &calendar.events[0].properties;
+        assert_eq!(property(properties, "DTSTART").value.as_deref(), Some("20250709T100000"));
+        let rdate = property(properties, "RDATE");
+        assert_eq!(rdate.value.as_deref(), Some("20250716T100000,20250806T100000"));
+        assert!(rdate.params.as_ref().unwrap().iter().any(|(name, values)| name == "TZID" && values == &["Europe/Amsterdam"]));
+        assert!(properties.iter().all(|p| p.name != "RRULE"));
+    }
+
+    #[test]
+    fn non_existent_local_time_is_rejected() {
+        let event = Event {
+            start_datetime: NaiveDate::from_ymd_opt(2025, 3, 30).unwrap().and_hms_opt(2, 30, 0),
+            end_datetime: None,
+            ..sample_event()
+        };
+        assert!(export_calendar(std::slice::from_ref(&event)).is_err());
+        assert!(matches!(
+            event_to_ics(&event),
+            Err(AppError::Timezone { ref datetime, .. }) if datetime == "2025-03-30 02:30:00"
+        ));
+    }
+}
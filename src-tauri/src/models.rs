@@ -1,6 +1,10 @@
 // src-tauri/src/models.rs
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize}; // Removed Utc and DateTime as NaiveDateTime is primary for parsing/storage
+use std::collections::BTreeMap;
+
+use crate::address;
+use crate::price::Price;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Event {
@@ -8,11 +12,31 @@ pub struct Event {
     pub title: String,
     pub url_suffix: Option<String>,
     pub full_url: Option<String>,
+    /// The URL without its date segment when the event is one date of a recurring event, e.g.
+    /// "/en/events/summer-art-club-creative-with-the-garden"; see `series::series_id`.
+    #[serde(default)]
+    pub series_id: Option<String>,
+    /// `EventSource::id()` of the site this event was scraped from.
+    #[serde(default = "default_event_source")]
+    pub source: String,
+    /// Language of the title, descriptions and `full_url`: "en", or "nl" for events only
+    /// listed on the Dutch side of the site.
+    #[serde(default = "default_language")]
+    pub language: String,
+    /// The same event's pages in other languages, keyed by language code.
+    #[serde(default)]
+    pub translations: BTreeMap<String, EventTranslation>,
 
     pub date_time_summary: Option<String>,
     pub list_date: Option<String>,
     pub start_datetime: Option<NaiveDateTime>,
     pub end_datetime: Option<NaiveDateTime>,
+    /// First and last day the event runs (inclusive), also set for timed events.
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    /// Listed without a start time or spanning several days; only the dates are meaningful.
+    #[serde(default)]
+    pub all_day: bool,
     pub datetime_str_raw_detail: Option<String>,
 
     pub short_description: Option<String>,
@@ -22,18 +46,89 @@ pub struct Event {
 
     pub list_specific_location: Option<String>,
     pub specific_location_name: Option<String>,
+    /// One-line display address, derived from the venue name and the parts below.
     pub address: Option<String>,
+    /// schema.org `streetAddress`, e.g. "Stratumsedijk 2".
+    #[serde(default)]
+    pub street: Option<String>,
+    /// Dutch postcode as "5611 NB"; see `address::normalize_postcode`.
+    #[serde(default)]
+    pub postal_code: Option<String>,
+    /// schema.org `addressLocality`, e.g. "Eindhoven".
+    #[serde(default)]
+    pub locality: Option<String>,
     pub latitude: Option<f64>,
     pub longitude: Option<f64>,
+    /// `Venue::id` of where the event takes place, assigned when the event is stored.
+    #[serde(default)]
+    pub venue_id: Option<i64>,
 
     pub list_price: Option<String>,
     pub price: Option<String>,
+    /// `price` (or `list_price` before details are fetched), parsed.
+    #[serde(default)]
+    pub parsed_price: Option<Price>,
     pub ticket_url: Option<String>, // <-- NEW FIELD
+    /// Performers named in the detail page's schema.org data.
+    #[serde(default)]
+    pub performers: Vec<String>,
+    /// schema.org `eventStatus`, when the detail page states it.
+    #[serde(default)]
+    pub event_status: Option<EventStatus>,
+    /// Which part of the detail page each field was read from, keyed by field name. "dates"
+    /// stands for the start/end fields, "address" for the address parts and "coordinates"
+    /// for latitude and longitude.
+    #[serde(default)]
+    pub field_sources: BTreeMap<String, FieldSource>,
 
     #[serde(skip_deserializing)]
     pub detail_page_content: Option<String>,
 }
 
+/// An event's title and descriptions as published in another language.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct EventTranslation {
+    pub title: String,
+    pub short_description: Option<String>,
+    /// Filled in when the event's details are fetched.
+    pub full_description: Option<String>,
+    pub full_url: Option<String>,
+}
+
+/// Where on a detail page a field was found. Embedded schema.org data is preferred over the
+/// page's visible markup, which changes with every redesign.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldSource {
+    /// A `<script type="application/ld+json">` block.
+    JsonLd,
+    /// `itemprop` attributes.
+    Microdata,
+    /// The page's CSS classes and icons.
+    Html,
+}
+
+/// schema.org `eventStatus`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventStatus {
+    Scheduled,
+    Cancelled,
+    Postponed,
+    Rescheduled,
+    MovedOnline,
+}
+
+// Caches written before multi-source support only contain thisiseindhoven.com events.
+fn default_event_source() -> String {
+    "thisiseindhoven".to_string()
+}
+
+// Events stored before the Dutch listing was scraped are all English.
+fn default_language() -> String {
+    "en".to_string()
+}
+
 impl Default for Event {
     fn default() -> Self {
         Event {
@@ -41,10 +136,17 @@ impl Default for Event {
             title: "N/A".to_string(),
             url_suffix: None,
             full_url: None,
+            series_id: None,
+            source: default_event_source(),
+            language: default_language(),
+            translations: BTreeMap::new(),
             date_time_summary: None,
             list_date: None,
             start_datetime: None,
             end_datetime: None,
+            start_date: None,
+            end_date: None,
+            all_day: false,
             datetime_str_raw_detail: None,
             short_description: None,
             full_description: None,
@@ -52,16 +154,138 @@ impl Default for Event {
             list_specific_location: None,
             specific_location_name: None,
             address: None,
+            street: None,
+            postal_code: None,
+            locality: None,
             latitude: None,
             longitude: None,
+            venue_id: None,
             list_price: None,
             price: None,
+            parsed_price: None,
             ticket_url: None, // <-- INITIALIZE NEW FIELD
+            performers: Vec::new(),
+            event_status: None,
+            field_sources: BTreeMap::new(),
             detail_page_content: None,
         }
     }
 }
 
+impl Event {
+    /// When the event starts; all-day events start at midnight on their first day.
+    pub fn starts_at(&self) -> Option<NaiveDateTime> {
+        if self.all_day {
+            self.start_date.and_then(|date| date.and_hms_opt(0, 0, 0))
+        } else {
+            self.start_datetime
+        }
+    }
+
+    /// When the event ends; all-day events end at the last second of their last day.
+    pub fn ends_at(&self) -> Option<NaiveDateTime> {
+        if self.all_day {
+            self.end_date.or(self.start_date).and_then(|date| date.and_hms_opt(23, 59, 59))
+        } else {
+            self.end_datetime
+        }
+    }
+
+    /// Whether the listed price says the event is free, e.g. "Free", "Gratis" or "€ 0,00".
+    pub fn is_free(&self) -> bool {
+        match &self.parsed_price {
+            Some(price) => price.is_free(),
+            None => self
+                .price
+                .as_deref()
+                .or(self.list_price.as_deref())
+                .is_some_and(|text| Price::parse(text).is_free()),
+        }
+    }
+
+    /// Notes that `field` was read from `source` on the detail page.
+    pub fn set_field_source(&mut self, field: &str, source: FieldSource) {
+        self.field_sources.insert(field.to_string(), source);
+    }
+
+    fn location_name(&self) -> Option<&str> {
+        self.specific_location_name.as_deref().or(self.list_specific_location.as_deref())
+    }
+
+    /// Stores the scraped address parts and derives `address` from them. A postcode that isn't
+    /// a valid Dutch one is logged and left out.
+    pub fn set_address_parts(&mut self, street: Option<String>, postal_code: Option<String>, locality: Option<String>) {
+        self.street = street.filter(|street| !street.trim().is_empty());
+        self.postal_code = postal_code.and_then(|raw| {
+            let normalized = address::normalize_postcode(&raw);
+            if normalized.is_none() && !raw.trim().is_empty() {
+                log::warn!("Ignoring invalid postcode '{}' for event {}", raw, self.id);
+            }
+            normalized
+        });
+        self.locality = locality.filter(|locality| !locality.trim().is_empty());
+        if let Some(display) = self.structured_address() {
+            self.address = Some(display);
+        }
+    }
+
+    /// Fills the address parts of an event stored before they were kept, from `address`.
+    pub fn backfill_address_parts(&mut self) {
+        if self.street.is_some() || self.postal_code.is_some() || self.locality.is_some() {
+            return;
+        }
+        if let Some(display) = self.address.as_deref() {
+            let names: Vec<&str> = [self.specific_location_name.as_deref(), self.list_specific_location.as_deref()]
+                .into_iter()
+                .flatten()
+                .collect();
+            (self.street, self.postal_code, self.locality) = address::parse_display_address(display, &names);
+        }
+    }
+
+    fn structured_address(&self) -> Option<String> {
+        if self.street.is_none() && self.postal_code.is_none() && self.locality.is_none() {
+            return None;
+        }
+        address::display_address(
+            self.location_name(),
+            self.street.as_deref(),
+            self.postal_code.as_deref(),
+            self.locality.as_deref(),
+        )
+    }
+
+    /// Where the event is, as one line for calendars and exports: the structured address when
+    /// the detail page had one, else the stored `address`, else just the venue name.
+    pub fn location_display(&self) -> Option<String> {
+        self.structured_address()
+            .or_else(|| self.address.clone())
+            .or_else(|| self.location_name().map(str::to_string))
+    }
+}
+
+/// A place events take place at, merged from every event that mentions it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Venue {
+    pub id: i64,
+    /// As first seen on a listing, e.g. "Stage Music Café".
+    pub name: String,
+    /// `venue::normalize_name(name)`, which identifies the venue.
+    pub normalized_name: String,
+    /// The full address line as scraped.
+    pub address: Option<String>,
+    pub street: Option<String>,
+    /// Dutch postcode, e.g. "5611 ET".
+    pub postal_code: Option<String>,
+    pub locality: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// Other spellings seen for the same venue, e.g. "Stage Music Cafe".
+    pub aliases: Vec<String>,
+    /// Listed events at this venue.
+    pub event_count: u32,
+}
+
 #[derive(Debug, Serialize, Clone)]
 pub struct ScrapingProgress {
     pub current_page: u32,
@@ -69,4 +293,6 @@ pub struct ScrapingProgress {
     pub events_on_current_page: u32,
     pub total_events_scraped: u32,
     pub message: String,
+    /// Set on the last message of a scrape that was cancelled; what it found so far is kept.
+    pub cancelled: bool,
 }
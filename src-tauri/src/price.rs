@@ -0,0 +1,263 @@
+// File: src-tauri/src/price.rs
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+const FREE_WORDS: &[&str] = &["free", "gratis", "vrij entree", "vrije toegang", "no charge"];
+const DONATION_WORDS: &[&str] = &["donation", "donatie", "vrije gift", "pay what you", "pay as you"];
+const FROM_WORDS: &[&str] = &["from", "vanaf", "v.a.", "starting at"];
+
+static AMOUNT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(€|\$|£|\beur(?:o|os)?\b)?\s*(\d+(?:[.,]\d{1,2})?)(,-|,–)?").unwrap());
+// Where a line lists several prices: `€ 12,50 / € 8,50 (students)`, `adults € 10, kids € 5`.
+static SEPARATOR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\s*(?:[;/|\n]|,\s|\band\b|\ben\b)\s*").unwrap());
+
+/// What an event costs, parsed from the listing's free-text price.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Price {
+    #[serde(flatten)]
+    pub kind: PriceKind,
+    /// ISO 4217 code, e.g. "EUR"; `None` when no amount was listed.
+    pub currency: Option<String>,
+    /// Reduced prices for specific groups, when stated.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tiers: Vec<PriceTier>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PriceKind {
+    Free,
+    Amount { amount: f64 },
+    Range { min: f64, max: f64 },
+    /// "From € 20,00": the cheapest ticket.
+    From { amount: f64 },
+    Donation { suggested: Option<f64> },
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PriceTier {
+    pub audience: Audience,
+    /// 0 when the group gets in free.
+    pub amount: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Audience {
+    Student,
+    Child,
+    Senior,
+}
+
+impl Audience {
+    fn find(segment: &str) -> Option<Audience> {
+        const AUDIENCES: &[(Audience, &[&str])] = &[
+            (Audience::Student, &["student", "cjp"]),
+            (Audience::Child, &["child", "kids", "kinderen", "junior", "jeugd"]),
+            (Audience::Senior, &["senior", "65+", "ouderen"]),
+        ];
+        AUDIENCES
+            .iter()
+            .find(|(_, words)| words.iter().any(|word| segment.contains(word)))
+            .map(|(audience, _)| *audience)
+    }
+}
+
+struct Amount {
+    value: f64,
+    currency: Option<&'static str>,
+    /// Written with a currency, decimals or `,-`, so it isn't an age or a date.
+    looks_like_money: bool,
+}
+
+/// Amounts in `segment`, e.g. `€ 7,50`, `7.50`, `10,-` or `EUR 12`. With `money_only`, bare
+/// numbers are skipped: on "(4-12 years) € 5" they are ages, but "12" alone is a price.
+fn amounts(segment: &str, money_only: bool) -> Vec<Amount> {
+    AMOUNT_RE
+        .captures_iter(segment)
+        .filter_map(|caps| {
+            let number = &caps[2];
+            let value = number.replace(',', ".").parse::<f64>().ok()?;
+            let currency = caps.get(1).map(|symbol| match symbol.as_str() {
+                "$" => "USD",
+                "£" => "GBP",
+                _ => "EUR",
+            });
+            let looks_like_money = currency.is_some() || number.contains(['.', ',']) || caps.get(3).is_some();
+            (looks_like_money || !money_only).then_some(Amount {
+                value,
+                currency,
+                looks_like_money,
+            })
+        })
+        .collect()
+}
+
+fn contains_any(text: &str, words: &[&str]) -> bool {
+    words.iter().any(|word| text.contains(word))
+}
+
+impl Price {
+    /// Parses a listing's price text such as "Free", "7.50", "From 10,- p.p." or
+    /// "€ 15 / € 10 (students)". Text it can't make sense of gives `PriceKind::Unknown`.
+    pub fn parse(text: &str) -> Price {
+        // "t/m" (up to and including) would otherwise be split as two ticket types.
+        let lower = text.trim().to_lowercase().replace("t/m", "tm");
+        let money_only = amounts(&lower, false).iter().any(|amount| amount.looks_like_money);
+        let mut general: Vec<Amount> = Vec::new();
+        let mut tiers: Vec<PriceTier> = Vec::new();
+        let mut currency: Option<&'static str> = None;
+        let mut free_for_everyone = false;
+
+        for segment in SEPARATOR_RE.split(&lower).filter(|segment| !segment.is_empty()) {
+            let mut segment_amounts = amounts(segment, money_only);
+            currency = currency.or_else(|| segment_amounts.iter().find_map(|amount| amount.currency));
+            match Audience::find(segment) {
+                Some(audience) => {
+                    let amount = match segment_amounts.first() {
+                        Some(amount) => Some(amount.value),
+                        None if contains_any(segment, FREE_WORDS) => Some(0.0),
+                        None => None,
+                    };
+                    if let Some(amount) = amount {
+                        tiers.push(PriceTier { audience, amount });
+                    }
+                }
+                None => {
+                    free_for_everyone |= segment_amounts.is_empty() && contains_any(segment, FREE_WORDS);
+                    general.append(&mut segment_amounts);
+                }
+            }
+        }
+
+        let values: Vec<f64> = general.iter().map(|amount| amount.value).collect();
+        let min = values.iter().copied().reduce(f64::min);
+        let max = values.iter().copied().reduce(f64::max);
+        let kind = if contains_any(&lower, DONATION_WORDS) {
+            PriceKind::Donation { suggested: min }
+        } else {
+            match (min, max) {
+                (Some(min), _) if contains_any(&lower, FROM_WORDS) => PriceKind::From { amount: min },
+                (Some(min), Some(max)) if max > min => PriceKind::Range { min, max },
+                (Some(amount), _) if amount > 0.0 => PriceKind::Amount { amount },
+                (Some(_), _) => PriceKind::Free,
+                (None, _) if free_for_everyone => PriceKind::Free,
+                (None, _) => PriceKind::Unknown,
+            }
+        };
+
+        let has_amount = !values.is_empty() || tiers.iter().any(|tier| tier.amount > 0.0);
+        Price {
+            kind,
+            // Prices on Eindhoven listings without a symbol are in euros.
+            currency: has_amount.then(|| currency.unwrap_or("EUR").to_string()),
+            tiers,
+        }
+    }
+
+    pub fn is_free(&self) -> bool {
+        self.kind == PriceKind::Free
+    }
+
+    /// The cheapest regular ticket, for sorting and price filters. `None` when unknown.
+    pub fn lowest(&self) -> Option<f64> {
+        match self.kind {
+            PriceKind::Free => Some(0.0),
+            PriceKind::Amount { amount } | PriceKind::From { amount } => Some(amount),
+            PriceKind::Range { min, .. } => Some(min),
+            PriceKind::Donation { suggested } => Some(suggested.unwrap_or(0.0)),
+            PriceKind::Unknown => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind(text: &str) -> PriceKind {
+        Price::parse(text).kind
+    }
+
+    // Price strings as they appear in the cached thisiseindhoven.com listings.
+    #[test]
+    fn parses_cached_listing_prices() {
+        assert_eq!(kind("Free"), PriceKind::Free);
+        assert_eq!(kind("7.50"), PriceKind::Amount { amount: 7.5 });
+        assert_eq!(kind("€ 7,50"), PriceKind::Amount { amount: 7.5 });
+        assert_eq!(kind("4,-"), PriceKind::Amount { amount: 4.0 });
+        assert_eq!(kind("69.95"), PriceKind::Amount { amount: 69.95 });
+        assert_eq!(kind("From 10,- p.p."), PriceKind::From { amount: 10.0 });
+        assert_eq!(kind("From € 20,00"), PriceKind::From { amount: 20.0 });
+        assert_eq!(kind("From 37.50 p.p."), PriceKind::From { amount: 37.5 });
+        assert_eq!(kind("From 159,- p.p."), PriceKind::From { amount: 159.0 });
+    }
+
+    #[test]
+    fn cached_listing_prices_are_in_euros() {
+        assert_eq!(Price::parse("7.50").currency.as_deref(), Some("EUR"));
+        assert_eq!(Price::parse("From € 20,00").currency.as_deref(), Some("EUR"));
+        assert_eq!(Price::parse("Free").currency, None);
+    }
+
+    #[test]
+    fn zero_and_dutch_free_texts_are_free() {
+        assert_eq!(kind("€ 0,00"), PriceKind::Free);
+        assert_eq!(kind("Gratis"), PriceKind::Free);
+        assert_eq!(kind("Vrij entree"), PriceKind::Free);
+        assert_eq!(kind("Free admission, registration required"), PriceKind::Free);
+    }
+
+    #[test]
+    fn parses_ranges_and_multiple_ticket_types() {
+        assert_eq!(kind("€ 10 - € 15"), PriceKind::Range { min: 10.0, max: 15.0 });
+        assert_eq!(kind("12,50 tot 17,50"), PriceKind::Range { min: 12.5, max: 17.5 });
+        assert_eq!(kind("Regular € 25 / VIP € 45"), PriceKind::Range { min: 25.0, max: 45.0 });
+    }
+
+    #[test]
+    fn parses_donations() {
+        assert_eq!(kind("Pay what you can"), PriceKind::Donation { suggested: None });
+        assert_eq!(kind("Vrije gift, richtprijs € 5"), PriceKind::Donation { suggested: Some(5.0) });
+    }
+
+    #[test]
+    fn parses_student_and_child_tiers() {
+        let price = Price::parse("€ 12,50 / € 8,50 (students)");
+        assert_eq!(price.kind, PriceKind::Amount { amount: 12.5 });
+        assert_eq!(price.tiers, vec![PriceTier { audience: Audience::Student, amount: 8.5 }]);
+
+        let price = Price::parse("Adults € 10, children (4-12) € 5, seniors € 7,50");
+        assert_eq!(price.kind, PriceKind::Amount { amount: 10.0 });
+        assert_eq!(
+            price.tiers,
+            vec![
+                PriceTier { audience: Audience::Child, amount: 5.0 },
+                PriceTier { audience: Audience::Senior, amount: 7.5 },
+            ]
+        );
+
+        let price = Price::parse("€ 15, kinderen t/m 12 jaar gratis");
+        assert_eq!(price.kind, PriceKind::Amount { amount: 15.0 });
+        assert_eq!(price.tiers, vec![PriceTier { audience: Audience::Child, amount: 0.0 }]);
+    }
+
+    #[test]
+    fn unparseable_text_is_unknown() {
+        let price = Price::parse("Sold out");
+        assert_eq!(price.kind, PriceKind::Unknown);
+        assert_eq!(price.lowest(), None);
+        assert_eq!(kind(""), PriceKind::Unknown);
+    }
+
+    #[test]
+    fn serializes_kind_inline() {
+        let json = serde_json::to_value(Price::parse("From 10,- p.p.")).unwrap();
+        assert_eq!(json, serde_json::json!({ "kind": "from", "amount": 10.0, "currency": "EUR" }));
+        let round_tripped: Price = serde_json::from_value(json).unwrap();
+        assert_eq!(round_tripped, Price::parse("From 10,- p.p."));
+    }
+}
@@ -0,0 +1,341 @@
+// File: src-tauri/src/query.rs
+// Filtering, sorting and paging of stored events. The `query_events` command, the CLI and
+// the REST API all go through here, so "free", "from" or a venue match mean the same everywhere.
+use chrono::{NaiveDate, NaiveDateTime};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+use crate::db;
+use crate::error::AppError;
+use crate::models::Event;
+use crate::scraper;
+
+/// Which stored events to return. Every field is optional; an empty filter matches everything.
+#[derive(Debug, Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct EventFilter {
+    /// Events starting on or after this time.
+    pub from: Option<NaiveDateTime>,
+    /// Events starting on or before this time.
+    pub to: Option<NaiveDateTime>,
+    /// Price bounds on the cheapest regular ticket. Events without a known price never match
+    /// when either bound is set.
+    pub min_price: Option<f64>,
+    pub max_price: Option<f64>,
+    pub free_only: bool,
+    /// Case-insensitive substring of the venue name.
+    pub venue: Option<String>,
+    /// Case-insensitive substring of the title, descriptions or venue.
+    pub text: Option<String>,
+    pub has_coordinates: bool,
+    pub has_tickets: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortKey {
+    /// Soonest first, undated events last.
+    #[default]
+    DateAsc,
+    /// Latest first, undated events last.
+    DateDesc,
+    /// Cheapest first, events without a known price last.
+    PriceAsc,
+    /// Most expensive first, events without a known price last.
+    PriceDesc,
+    Title,
+}
+
+/// One page of matching events.
+#[derive(Debug, Serialize, Clone)]
+pub struct EventPage {
+    pub events: Vec<Event>,
+    /// Matching events across all pages.
+    pub total: usize,
+    pub offset: usize,
+}
+
+/// When the event starts, falling back to the listing's date (e.g. "09 Jul 2025") for
+/// events whose details haven't been fetched yet.
+pub(crate) fn event_date(event: &Event) -> Option<NaiveDateTime> {
+    event.starts_at().or_else(|| {
+        let list_date = scraper::translate_dutch_months(event.list_date.as_deref()?.trim());
+        ["%d %b %Y", "%d %B %Y"]
+            .iter()
+            .find_map(|format| NaiveDate::parse_from_str(&list_date, format).ok())
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+    })
+}
+
+fn venue_name(event: &Event) -> Option<&str> {
+    event
+        .specific_location_name
+        .as_deref()
+        .or(event.list_specific_location.as_deref())
+}
+
+fn contains_lowercase(haystack: Option<&str>, needle: &str) -> bool {
+    haystack.is_some_and(|text| text.to_lowercase().contains(needle))
+}
+
+// Sorts `None` after every `Some`, whichever way the values are ordered.
+fn compare_known<T: PartialOrd>(a: Option<T>, b: Option<T>, descending: bool) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            let ordering = a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        }
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+impl EventFilter {
+    pub fn matches(&self, event: &Event) -> bool {
+        if self.from.is_some() || self.to.is_some() {
+            let Some(date) = event_date(event) else {
+                return false;
+            };
+            if self.from.is_some_and(|from| date < from) || self.to.is_some_and(|to| date > to) {
+                return false;
+            }
+        }
+        if self.min_price.is_some() || self.max_price.is_some() {
+            let Some(price) = event.parsed_price.as_ref().and_then(|price| price.lowest()) else {
+                return false;
+            };
+            if self.min_price.is_some_and(|min| price < min) || self.max_price.is_some_and(|max| price > max) {
+                return false;
+            }
+        }
+        if self.free_only && !event.is_free() {
+            return false;
+        }
+        if let Some(venue) = self.venue.as_deref().map(|venue| venue.trim().to_lowercase()) {
+            if !contains_lowercase(venue_name(event), &venue) {
+                return false;
+            }
+        }
+        if let Some(text) = self.text.as_deref().map(|text| text.trim().to_lowercase()) {
+            let searched = [
+                Some(event.title.as_str()),
+                event.short_description.as_deref(),
+                event.full_description.as_deref(),
+                venue_name(event),
+            ];
+            if !searched.into_iter().any(|field| contains_lowercase(field, &text)) {
+                return false;
+            }
+        }
+        if self.has_coordinates && (event.latitude.is_none() || event.longitude.is_none()) {
+            return false;
+        }
+        if self.has_tickets && event.ticket_url.as_deref().is_none_or(|url| url.trim().is_empty()) {
+            return false;
+        }
+        true
+    }
+}
+
+impl SortKey {
+    /// Stable, so events that compare equal keep their stored (date, then title) order.
+    pub fn sort(self, events: &mut [Event]) {
+        let lowest_price = |event: &Event| event.parsed_price.as_ref().and_then(|price| price.lowest());
+        match self {
+            SortKey::DateAsc => events.sort_by(|a, b| compare_known(event_date(a), event_date(b), false)),
+            SortKey::DateDesc => events.sort_by(|a, b| compare_known(event_date(a), event_date(b), true)),
+            SortKey::PriceAsc => events.sort_by(|a, b| compare_known(lowest_price(a), lowest_price(b), false)),
+            SortKey::PriceDesc => events.sort_by(|a, b| compare_known(lowest_price(a), lowest_price(b), true)),
+            SortKey::Title => events.sort_by_key(|event| event.title.to_lowercase()),
+        }
+    }
+}
+
+/// Filters and sorts `events`, then returns `limit` of them (all when `None`) from `offset` on.
+pub fn apply(mut events: Vec<Event>, filter: &EventFilter, sort: SortKey, offset: usize, limit: Option<usize>) -> EventPage {
+    events.retain(|event| filter.matches(event));
+    sort.sort(&mut events);
+    let total = events.len();
+    EventPage {
+        events: events
+            .into_iter()
+            .skip(offset)
+            .take(limit.unwrap_or(usize::MAX))
+            .collect(),
+        total,
+        offset,
+    }
+}
+
+/// `apply` over every stored event that is still listed.
+pub fn query_events(
+    conn: &Connection,
+    filter: &EventFilter,
+    sort: SortKey,
+    offset: usize,
+    limit: Option<usize>,
+) -> Result<EventPage, AppError> {
+    let events = db::query_stored_events(conn)?;
+    Ok(apply(events, filter, sort, offset, limit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price::Price;
+
+    fn event(id: &str, list_date: &str, price: &str, venue: &str) -> Event {
+        Event {
+            id: id.to_string(),
+            title: id.to_string(),
+            list_date: Some(list_date.to_string()),
+            list_price: Some(price.to_string()),
+            parsed_price: Some(Price::parse(price)),
+            list_specific_location: Some(venue.to_string()),
+            ..Event::default()
+        }
+    }
+
+    fn ids(page: &EventPage) -> Vec<&str> {
+        page.events.iter().map(|event| event.id.as_str()).collect()
+    }
+
+    fn sample_events() -> Vec<Event> {
+        let mut with_tickets = event("c", "12 Jul 2025", "From 10,- p.p.", "Effenaar");
+        with_tickets.ticket_url = Some("https://example.com/tickets".to_string());
+        with_tickets.latitude = Some(51.44);
+        with_tickets.longitude = Some(5.48);
+        with_tickets.short_description = Some("Live techno all night".to_string());
+        vec![
+            event("a", "09 Jul 2025", "7.50", "Van Abbemuseum"),
+            event("b", "20 Jul 2025", "Free", "Stadhuisplein"),
+            with_tickets,
+            event("d", "01 Aug 2025", "Sold out", "Effenaar"),
+        ]
+    }
+
+    #[test]
+    fn filters_by_listing_date_price_and_venue() {
+        let filter = EventFilter {
+            from: NaiveDate::from_ymd_opt(2025, 7, 10).and_then(|date| date.and_hms_opt(0, 0, 0)),
+            to: NaiveDate::from_ymd_opt(2025, 7, 31).and_then(|date| date.and_hms_opt(23, 59, 59)),
+            ..EventFilter::default()
+        };
+        assert_eq!(ids(&apply(sample_events(), &filter, SortKey::DateAsc, 0, None)), ["c", "b"]);
+
+        let filter = EventFilter {
+            min_price: Some(5.0),
+            max_price: Some(20.0),
+            ..EventFilter::default()
+        };
+        assert_eq!(ids(&apply(sample_events(), &filter, SortKey::DateAsc, 0, None)), ["a", "c"]);
+
+        let filter = EventFilter {
+            free_only: true,
+            ..EventFilter::default()
+        };
+        assert_eq!(ids(&apply(sample_events(), &filter, SortKey::DateAsc, 0, None)), ["b"]);
+
+        let filter = EventFilter {
+            venue: Some("effenaar".to_string()),
+            ..EventFilter::default()
+        };
+        assert_eq!(ids(&apply(sample_events(), &filter, SortKey::DateAsc, 0, None)), ["c", "d"]);
+    }
+
+    #[test]
+    fn filters_by_text_coordinates_and_tickets() {
+        let filter = EventFilter {
+            text: Some("TECHNO".to_string()),
+            ..EventFilter::default()
+        };
+        assert_eq!(ids(&apply(sample_events(), &filter, SortKey::DateAsc, 0, None)), ["c"]);
+
+        let filter = EventFilter {
+            has_coordinates: true,
+            has_tickets: true,
+            ..EventFilter::default()
+        };
+        assert_eq!(ids(&apply(sample_events(), &filter, SortKey::DateAsc, 0, None)), ["c"]);
+    }
+
+    #[test]
+    fn sorts_unknown_values_last() {
+        let events = || {
+            let mut events = sample_events();
+            events.push(event("undated", "", "4,-", "Effenaar"));
+            events
+        };
+        let all = EventFilter::default();
+        assert_eq!(ids(&apply(events(), &all, SortKey::DateDesc, 0, None)), ["d", "b", "c", "a", "undated"]);
+        assert_eq!(ids(&apply(events(), &all, SortKey::PriceAsc, 0, None)), ["b", "undated", "a", "c", "d"]);
+        assert_eq!(ids(&apply(events(), &all, SortKey::PriceDesc, 0, None)), ["c", "a", "undated", "b", "d"]);
+    }
+
+    #[test]
+    fn stored_events_are_filtered_by_start_or_listing_date() {
+        let db_path = std::env::temp_dir().join(format!("eindhoven-events-query-dates-{}.db", std::process::id()));
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", db_path.display(), suffix));
+        }
+        let mut conn = db::open_at(&db_path).unwrap();
+        let mut detailed = event("/en/events/c", "", "Free", "Effenaar");
+        detailed.start_datetime = NaiveDate::from_ymd_opt(2025, 7, 12).and_then(|date| date.and_hms_opt(20, 0, 0));
+        let events = [
+            event("/en/events/a", "09 Jul 2025", "Free", "Effenaar"),
+            event("/en/events/b", "20 Jul 2025", "Free", "Effenaar"),
+            detailed,
+        ];
+        db::record_scrape(&mut conn, chrono::Utc::now(), 1, &events, db::ScrapeCoverage::Complete).unwrap();
+
+        let filter = EventFilter {
+            from: NaiveDate::from_ymd_opt(2025, 7, 10).and_then(|date| date.and_hms_opt(0, 0, 0)),
+            to: NaiveDate::from_ymd_opt(2025, 7, 31).and_then(|date| date.and_hms_opt(23, 59, 59)),
+            ..EventFilter::default()
+        };
+        let page = query_events(&conn, &filter, SortKey::DateAsc, 0, None).unwrap();
+        assert_eq!(ids(&page), ["/en/events/c", "/en/events/b"]);
+    }
+
+    #[test]
+    fn venue_filter_takes_wildcards_literally() {
+        let db_path = std::env::temp_dir().join(format!("eindhoven-events-query-venues-{}.db", std::process::id()));
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", db_path.display(), suffix));
+        }
+        let mut conn = db::open_at(&db_path).unwrap();
+        let events = [
+            event("/en/events/club-night", "09 Jul 2025", "Free", "100% Live"),
+            event("/en/events/quiz", "10 Jul 2025", "Free", "100 Club"),
+            event("/en/events/drinks", "11 Jul 2025", "Free", "Bar_Boulevard"),
+        ];
+        db::record_scrape(&mut conn, chrono::Utc::now(), 1, &events, db::ScrapeCoverage::Complete).unwrap();
+        let at_venue = |venue: &str| {
+            let filter = EventFilter {
+                venue: Some(venue.to_string()),
+                ..EventFilter::default()
+            };
+            let page = query_events(&conn, &filter, SortKey::DateAsc, 0, None).unwrap();
+            page.events.into_iter().map(|event| event.id).collect::<Vec<_>>()
+        };
+        assert_eq!(at_venue("100%"), ["/en/events/club-night"]);
+        assert_eq!(at_venue("100"), ["/en/events/club-night", "/en/events/quiz"]);
+        assert_eq!(at_venue("r_b"), ["/en/events/drinks"]);
+        assert!(at_venue("rxb").is_empty());
+        assert_eq!(at_venue("%"), ["/en/events/club-night"]);
+    }
+
+    #[test]
+    fn pages_after_filtering() {
+        let page = apply(sample_events(), &EventFilter::default(), SortKey::DateAsc, 1, Some(2));
+        assert_eq!(ids(&page), ["c", "b"]);
+        assert_eq!(page.total, 4);
+        assert_eq!(page.offset, 1);
+    }
+}
@@ -0,0 +1,259 @@
+// File: src-tauri/src/scraper/batch.rs
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use super::cancel::CancelToken;
+use super::crawl::CrawlClient;
+use super::parsers::fetch_event_details;
+use super::sources::SourceRegistry;
+use crate::models::{Event, ScrapingProgress};
+
+const DEFAULT_CONCURRENCY: usize = 4;
+
+pub struct DetailBatchOptions {
+    /// Number of worker threads fetching detail pages at once.
+    pub concurrency: usize,
+    /// Stops the batch between detail fetches once cancelled.
+    pub cancel: CancelToken,
+}
+
+impl Default for DetailBatchOptions {
+    fn default() -> Self {
+        DetailBatchOptions {
+            concurrency: DEFAULT_CONCURRENCY,
+            cancel: CancelToken::default(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DetailBatchResult {
+    /// Same order as the input; events whose details failed are returned unchanged.
+    pub events: Vec<Event>,
+    pub failed_ids: Vec<String>,
+    /// Events left unfetched because the batch was cancelled; returned unchanged.
+    pub cancelled_ids: Vec<String>,
+}
+
+impl DetailBatchResult {
+    /// Number of returned events that actually got their details, i.e. neither failed nor
+    /// cancelled. The id lists may repeat ids that `events` holds only once.
+    pub fn detailed_count(&self) -> usize {
+        self.events
+            .iter()
+            .filter(|e| !self.failed_ids.contains(&e.id) && !self.cancelled_ids.contains(&e.id))
+            .count()
+    }
+}
+
+/// Fetches detail pages for `events` on a bounded worker pool, reporting progress after
+/// every completed event. The workers share `client`, which keeps their requests to a host
+/// apart. `current_page`/`total_pages_estimate` in the progress
+/// messages count completed/total events. Cancelling `options.cancel` lets the fetches in
+/// flight finish and skips the rest.
+pub fn fetch_event_details_batch(
+    client: &CrawlClient,
+    registry: &SourceRegistry,
+    events: Vec<Event>,
+    options: &DetailBatchOptions,
+    progress_callback: impl Fn(ScrapingProgress) + Sync,
+) -> DetailBatchResult {
+    let total = events.len() as u32;
+    let worker_count = options.concurrency.max(1).min(events.len());
+    log::info!("Fetching details for {} events with {} workers.", total, worker_count);
+
+    let queue: Mutex<VecDeque<(usize, Event)>> = Mutex::new(events.into_iter().enumerate().collect());
+    let results: Mutex<Vec<Option<Event>>> = Mutex::new(vec![None; total as usize]);
+    let failed_ids: Mutex<Vec<String>> = Mutex::new(Vec::new());
+    let completed = AtomicU32::new(0);
+
+    thread::scope(|scope| {
+        for _ in 0..worker_count {
+            scope.spawn(|| loop {
+                if options.cancel.is_cancelled() {
+                    break;
+                }
+                let next = queue.lock().unwrap_or_else(|e| e.into_inner()).pop_front();
+                let Some((index, summary)) = next else {
+                    break;
+                };
+
+                let title = summary.title.clone();
+                let event = match fetch_event_details(client, registry, summary.clone(), &options.cancel) {
+                    Ok(detailed_event) => detailed_event,
+                    // Cancelled while waiting to retry; it goes back with the events never started.
+                    Err(e) if e.is_transient() && options.cancel.is_cancelled() => {
+                        queue.lock().unwrap_or_else(|e| e.into_inner()).push_front((index, summary));
+                        break;
+                    }
+                    Err(e) => {
+                        log::error!("Error fetching details for event '{}': {}", summary.id, e);
+                        failed_ids
+                            .lock()
+                            .unwrap_or_else(|e| e.into_inner())
+                            .push(summary.id.clone());
+                        summary
+                    }
+                };
+                results.lock().unwrap_or_else(|e| e.into_inner())[index] = Some(event);
+
+                let done = completed.fetch_add(1, Ordering::SeqCst) + 1;
+                progress_callback(ScrapingProgress {
+                    current_page: done,
+                    total_pages_estimate: total,
+                    events_on_current_page: 1,
+                    total_events_scraped: done,
+                    message: format!("Fetched details for '{}' ({}/{})", title, done, total),
+                    cancelled: false,
+                });
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap_or_else(|e| e.into_inner());
+    let mut cancelled_ids = Vec::new();
+    for (index, summary) in queue.into_inner().unwrap_or_else(|e| e.into_inner()) {
+        cancelled_ids.push(summary.id.clone());
+        results[index] = Some(summary);
+    }
+    if !cancelled_ids.is_empty() {
+        let done = completed.into_inner();
+        log::info!("Detail fetching cancelled after {} of {} events.", done, total);
+        progress_callback(ScrapingProgress {
+            current_page: done,
+            total_pages_estimate: total,
+            events_on_current_page: 0,
+            total_events_scraped: done,
+            message: format!("Fetching details cancelled; kept the {} fetched so far.", done),
+            cancelled: true,
+        });
+    }
+
+    DetailBatchResult {
+        events: results.into_iter().flatten().collect(),
+        failed_ids: failed_ids.into_inner().unwrap_or_else(|e| e.into_inner()),
+        cancelled_ids,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AppError;
+    use crate::scraper::crawl::tests::{serve, test_client};
+    use crate::scraper::sources::{EventSource, ListPage};
+
+    // Detail pages are plain text that becomes the full description.
+    struct PlainSource;
+
+    impl EventSource for PlainSource {
+        fn id(&self) -> &'static str {
+            "plain"
+        }
+
+        fn display_name(&self) -> &'static str {
+            "Plain"
+        }
+
+        fn list_page_url(&self, page: u32) -> String {
+            format!("http://127.0.0.1/events?page={}", page)
+        }
+
+        fn parse_summaries(&self, _html: &str) -> Result<ListPage, AppError> {
+            Ok(ListPage::default())
+        }
+
+        fn parse_details(&self, html: &str, event: Event) -> Result<Event, AppError> {
+            Ok(Event {
+                full_description: Some(html.to_string()),
+                ..event
+            })
+        }
+    }
+
+    const DETAIL_PAGE: &str = "HTTP/1.1 200 OK\r\nContent-Length: 7\r\nConnection: close\r\n\r\ndetails";
+    const NO_ROBOTS_TXT: &str = "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+    fn registry() -> SourceRegistry {
+        let mut registry = SourceRegistry::new();
+        registry.register(Box::new(PlainSource));
+        registry
+    }
+
+    fn summary(base_url: &str, slug: &str) -> Event {
+        Event {
+            id: format!("/events/{}", slug),
+            source: "plain".to_string(),
+            full_url: Some(format!("{}/events/{}", base_url, slug)),
+            ..Event::default()
+        }
+    }
+
+    #[test]
+    fn results_keep_input_order_and_failures_are_listed() {
+        let (base_url, server) = serve(vec![NO_ROBOTS_TXT, DETAIL_PAGE, DETAIL_PAGE, DETAIL_PAGE, DETAIL_PAGE]);
+        // The workers would race to fetch robots.txt, and the test server answers in order.
+        let client = test_client("batch");
+        client.get_text(&format!("{}/events/warm-up", base_url), &CancelToken::default()).unwrap();
+        let no_url = Event {
+            full_url: None,
+            ..summary(&base_url, "no-url")
+        };
+        let unknown_source = Event {
+            source: "elsewhere".to_string(),
+            ..summary(&base_url, "elsewhere")
+        };
+        let events = vec![
+            summary(&base_url, "jazz"),
+            no_url,
+            summary(&base_url, "open"),
+            unknown_source,
+            summary(&base_url, "cinema"),
+        ];
+        let options = DetailBatchOptions {
+            concurrency: 3,
+            ..DetailBatchOptions::default()
+        };
+        let batch = fetch_event_details_batch(&client, &registry(), events, &options, |_| {});
+        server.join().unwrap();
+
+        let ids: Vec<&str> = batch.events.iter().map(|event| event.id.as_str()).collect();
+        assert_eq!(ids, ["/events/jazz", "/events/no-url", "/events/open", "/events/elsewhere", "/events/cinema"]);
+        let described: Vec<bool> = batch.events.iter().map(|event| event.full_description.is_some()).collect();
+        assert_eq!(described, [true, false, true, false, true]);
+        let mut failed_ids = batch.failed_ids.clone();
+        failed_ids.sort();
+        assert_eq!(failed_ids, ["/events/elsewhere", "/events/no-url"]);
+        assert!(batch.cancelled_ids.is_empty());
+        assert_eq!(batch.detailed_count(), 3);
+    }
+
+    #[test]
+    fn cancelling_skips_the_remaining_events() {
+        let (base_url, server) = serve(vec![NO_ROBOTS_TXT, DETAIL_PAGE]);
+        let events = vec![summary(&base_url, "jazz"), summary(&base_url, "open"), summary(&base_url, "cinema")];
+        let options = DetailBatchOptions {
+            concurrency: 1,
+            ..DetailBatchOptions::default()
+        };
+        let last_progress = Mutex::new(None);
+        let batch = fetch_event_details_batch(&test_client("batch-cancel"), &registry(), events, &options, |progress| {
+            options.cancel.cancel();
+            *last_progress.lock().unwrap() = Some(progress);
+        });
+        server.join().unwrap();
+
+        let ids: Vec<&str> = batch.events.iter().map(|event| event.id.as_str()).collect();
+        assert_eq!(ids, ["/events/jazz", "/events/open", "/events/cinema"]);
+        assert!(batch.events[0].full_description.is_some());
+        assert!(batch.events[1].full_description.is_none());
+        assert_eq!(batch.cancelled_ids, ["/events/open", "/events/cinema"]);
+        assert!(batch.failed_ids.is_empty());
+        let last_progress = last_progress.into_inner().unwrap().unwrap();
+        assert!(last_progress.cancelled);
+        assert_eq!(last_progress.current_page, 1);
+    }
+}
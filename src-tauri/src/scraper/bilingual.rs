@@ -0,0 +1,129 @@
+// File: src-tauri/src/scraper/bilingual.rs
+// Matches the events of a translated listing (the Dutch `/nl/events`) to the English ones.
+// Slugs are translated too, so events are matched on what both pages share: the content id
+// some slugs end in, or the image, plus the date for events that repeat.
+use chrono::NaiveDate;
+use regex::Regex;
+use std::collections::HashMap;
+
+use super::utils::parse_event_datetimes;
+use crate::models::{Event, EventTranslation};
+
+// What identifies an event across languages.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum MatchKey {
+    /// The 32-digit hex id in slugs like "stage-cocktail-comedy-nights-9aeae763a30c2f7b4d9dfbe38107a9be".
+    ContentId(String, Option<NaiveDate>),
+    /// The media library id in the image URL, "/getmedia/<guid>/...".
+    Image(String, Option<NaiveDate>),
+}
+
+fn listed_date(event: &Event) -> Option<NaiveDate> {
+    parse_event_datetimes(event.list_date.as_deref(), None).start_date
+}
+
+fn match_keys(event: &Event) -> Vec<MatchKey> {
+    let content_id_re = Regex::new(r"\b([0-9a-f]{32})\b").unwrap();
+    let image_id_re = Regex::new(r"/getmedia/([0-9a-fA-F-]{36})/").unwrap();
+    let date = listed_date(event);
+    let mut keys = Vec::new();
+    if let Some(caps) = event.url_suffix.as_deref().and_then(|url| content_id_re.captures(url)) {
+        keys.push(MatchKey::ContentId(caps[1].to_string(), date));
+    }
+    if let Some(caps) = event.image_url.as_deref().and_then(|url| image_id_re.captures(url)) {
+        keys.push(MatchKey::Image(caps[1].to_lowercase(), date));
+    }
+    keys
+}
+
+/// Adds each `translated` event to the matching event in `events` as its `language`
+/// translation. Translated events without an English counterpart are kept as they are.
+pub(super) fn merge_translations(events: &mut Vec<Event>, translated: Vec<Event>, language: &str) {
+    let mut positions: HashMap<MatchKey, usize> = HashMap::new();
+    for (position, event) in events.iter().enumerate() {
+        for key in match_keys(event) {
+            positions.entry(key).or_insert(position);
+        }
+    }
+
+    let mut matched = 0;
+    let mut unmatched = Vec::new();
+    for event in translated {
+        let position = match_keys(&event).into_iter().find_map(|key| positions.get(&key).copied());
+        match position {
+            Some(position) => {
+                matched += 1;
+                events[position].translations.insert(
+                    language.to_string(),
+                    EventTranslation {
+                        title: event.title,
+                        short_description: event.short_description,
+                        full_description: event.full_description,
+                        full_url: event.full_url,
+                    },
+                );
+            }
+            None => unmatched.push(event),
+        }
+    }
+    log::info!(
+        "Matched {} '{}' events to English ones; {} are only listed in '{}'.",
+        matched,
+        language,
+        unmatched.len(),
+        language
+    );
+    events.extend(unmatched);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listed(url_suffix: &str, title: &str, list_date: &str, image: Option<&str>) -> Event {
+        Event {
+            id: url_suffix.to_string(),
+            title: title.to_string(),
+            url_suffix: Some(url_suffix.to_string()),
+            full_url: Some(format!("https://www.thisiseindhoven.com{}", url_suffix)),
+            list_date: Some(list_date.to_string()),
+            image_url: image.map(|guid| format!("https://www.thisiseindhoven.com/getmedia/{}/poster.jpg?width=720", guid)),
+            language: if url_suffix.starts_with("/nl/") { "nl" } else { "en" }.to_string(),
+            ..Event::default()
+        }
+    }
+
+    #[test]
+    fn matches_on_content_id_and_date() {
+        let id = "9aeae763a30c2f7b4d9dfbe38107a9be";
+        let mut events = vec![
+            listed(&format!("/en/events/stage-comedy-nights-{}/july-10", id), "Comedy Nights", "10 Jul 2025", None),
+            listed(&format!("/en/events/stage-comedy-nights-{}/august-14", id), "Comedy Nights", "14 Aug 2025", None),
+        ];
+        let dutch = vec![listed(&format!("/nl/events/stage-comedy-avonden-{}/14-augustus", id), "Comedy-avonden", "14 aug 2025", None)];
+        merge_translations(&mut events, dutch, "nl");
+
+        assert_eq!(events.len(), 2);
+        assert!(events[0].translations.is_empty());
+        let translation = &events[1].translations["nl"];
+        assert_eq!(translation.title, "Comedy-avonden");
+        assert!(translation.full_url.as_deref().unwrap().ends_with("/14-augustus"));
+    }
+
+    #[test]
+    fn matches_on_image_and_keeps_dutch_only_events() {
+        let guid = "99aa87eb-5409-4a94-9950-a2526b5df096";
+        let mut events = vec![listed("/en/events/glow-light-walk/12-july", "GLOW light walk", "12 Jul 2025", Some(guid))];
+        let dutch = vec![
+            listed("/nl/events/glow-lichtwandeling/12-juli", "GLOW lichtwandeling", "12 jul 2025", Some(&guid.to_uppercase())),
+            listed("/nl/events/voorleesochtend/3-mei", "Voorleesochtend", "3 mei 2025", None),
+        ];
+        merge_translations(&mut events, dutch, "nl");
+
+        assert_eq!(events[0].translations["nl"].title, "GLOW lichtwandeling");
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].title, "Voorleesochtend");
+        assert_eq!(events[1].language, "nl");
+        assert_eq!(listed_date(&events[1]), NaiveDate::from_ymd_opt(2025, 5, 3));
+    }
+}
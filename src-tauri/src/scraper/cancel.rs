@@ -0,0 +1,67 @@
+// File: src-tauri/src/scraper/cancel.rs
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often a sleeping scrape looks whether it was cancelled.
+const SLEEP_SLICE: Duration = Duration::from_millis(100);
+
+/// Asks a running scrape to stop. Clones share one flag: the caller keeps a clone and the
+/// scrape checks it between listing pages, between detail fetches and before retrying a
+/// request, keeping what it already has when it stops.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Sleeps for `duration`, waking early when cancelled. Returns whether it was.
+    pub fn sleep(&self, duration: Duration) -> bool {
+        let deadline = Instant::now() + duration;
+        while !self.is_cancelled() {
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                return false;
+            }
+            thread::sleep(left.min(SLEEP_SLICE));
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_share_the_flag() {
+        let token = CancelToken::default();
+        let scrape = token.clone();
+        assert!(!scrape.is_cancelled());
+        token.cancel();
+        assert!(scrape.is_cancelled());
+        assert!(!CancelToken::default().is_cancelled());
+    }
+
+    #[test]
+    fn cancelling_ends_a_sleep_early() {
+        let token = CancelToken::default();
+        assert!(!token.sleep(Duration::from_millis(10)));
+
+        let canceller = token.clone();
+        let started = Instant::now();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            canceller.cancel();
+        });
+        assert!(token.sleep(Duration::from_secs(30)));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
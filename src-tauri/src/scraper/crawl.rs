@@ -0,0 +1,360 @@
+// File: src-tauri/src/scraper/crawl.rs
+// Every page the scraper downloads goes through `CrawlClient::get_text`, which obeys the
+// site's robots.txt, keeps requests to a host apart, retries what failed transiently and
+// revalidates pages it fetched before instead of downloading them again.
+use chrono::{DateTime, Utc};
+use reqwest::blocking::Client;
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, RETRY_AFTER};
+use reqwest::StatusCode;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher, RandomState};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use url::Url;
+
+use super::cancel::CancelToken;
+use super::rate_limit::HostRateLimiter;
+use super::robots::RobotsTxt;
+use crate::db::{self, StoredPage};
+use crate::error::AppError;
+
+const DEFAULT_MIN_DELAY: Duration = Duration::from_millis(250);
+const DEFAULT_MAX_RETRIES: u32 = 3;
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(1);
+/// Longest wait before a retry. A server asking for a longer `Retry-After` isn't retried.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+pub struct CrawlPolicy {
+    /// Least time between two requests to the same host. A longer robots.txt Crawl-delay wins.
+    pub min_delay: Duration,
+    /// Fetch each host's robots.txt and don't request what it disallows.
+    pub respect_robots_txt: bool,
+    /// How often a request that failed transiently (see `AppError::is_transient`) is retried.
+    pub max_retries: u32,
+    /// Wait before the first retry; it doubles for every further one, with jitter.
+    pub retry_delay: Duration,
+}
+
+impl Default for CrawlPolicy {
+    fn default() -> Self {
+        CrawlPolicy {
+            min_delay: DEFAULT_MIN_DELAY,
+            respect_robots_txt: true,
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_delay: DEFAULT_RETRY_DELAY,
+        }
+    }
+}
+
+impl CrawlPolicy {
+    /// Raises `min_delay` so no host gets more than `requests_per_second`. A non-positive
+    /// rate leaves it as it is.
+    pub fn limit_requests_per_second(&mut self, requests_per_second: f64) {
+        if let Ok(interval) = Duration::try_from_secs_f64(1.0 / requests_per_second) {
+            self.min_delay = self.min_delay.max(interval);
+        }
+    }
+}
+
+// A random fraction in [0, 1), good enough to spread out retries.
+fn jitter() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// `Retry-After` as either delay seconds or an HTTP date.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    if let Ok(seconds) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = DateTime::parse_from_rfc2822(value.trim()).ok()?;
+    Some((at.with_timezone(&Utc) - Utc::now()).to_std().unwrap_or_default())
+}
+
+/// An HTTP client that crawls politely; see the module comment.
+pub struct CrawlClient {
+    client: Client,
+    // Product token robots.txt groups are matched against, e.g. "EindhovenEventViewer".
+    robots_agent: String,
+    respect_robots_txt: bool,
+    max_retries: u32,
+    retry_delay: Duration,
+    rate_limiter: HostRateLimiter,
+    // Parsed robots.txt per origin, fetched the first time a URL on it is requested.
+    robots: Mutex<HashMap<String, Arc<RobotsTxt>>>,
+    // Where validators are stored; without it pages are always downloaded in full.
+    pages: Option<Mutex<Connection>>,
+}
+
+impl CrawlClient {
+    /// `user_agent` must be the one `client` sends.
+    pub fn new(client: Client, user_agent: &str, policy: CrawlPolicy) -> Self {
+        CrawlClient {
+            client,
+            robots_agent: user_agent.split('/').next().unwrap_or_default().trim().to_string(),
+            respect_robots_txt: policy.respect_robots_txt,
+            max_retries: policy.max_retries,
+            retry_delay: policy.retry_delay,
+            rate_limiter: HostRateLimiter::with_min_interval(policy.min_delay),
+            robots: Mutex::new(HashMap::new()),
+            pages: None,
+        }
+    }
+
+    /// Sends conditional requests using the validators stored in the event database `conn`.
+    pub fn with_page_store(mut self, conn: Connection) -> Self {
+        self.pages = Some(Mutex::new(conn));
+        self
+    }
+
+    /// GETs `url`, treating error statuses as failures rather than pages to parse. A page
+    /// that hasn't changed since it was stored is answered with `304 Not Modified` and
+    /// returned from the store. Transient failures are retried with jittered exponential
+    /// backoff, or after the server's `Retry-After`, until `cancel` is cancelled.
+    pub fn get_text(&self, url: &str, cancel: &CancelToken) -> Result<String, AppError> {
+        let mut retries = 0;
+        loop {
+            let (error, retry_after) = match self.try_get_text(url) {
+                Ok(body) => return Ok(body),
+                Err(failure) => failure,
+            };
+            if retries >= self.max_retries || !error.is_transient() || cancel.is_cancelled() {
+                return Err(error);
+            }
+            let backoff = self.retry_delay.saturating_mul(1 << retries.min(16)).min(MAX_RETRY_DELAY);
+            let wait = match retry_after {
+                Some(retry_after) if retry_after > MAX_RETRY_DELAY => {
+                    log::warn!("{} asks to retry after {:?}; giving up.", url, retry_after);
+                    return Err(error);
+                }
+                Some(retry_after) => retry_after,
+                // Half the backoff plus up to half again, so parallel workers don't retry in step.
+                None => backoff.mul_f64(0.5 + jitter() / 2.0),
+            };
+            retries += 1;
+            log::warn!("{} (retry {} of {} in {:?})", error, retries, self.max_retries, wait);
+            if cancel.sleep(wait) {
+                log::info!("Not retrying {}: cancelled.", url);
+                return Err(error);
+            }
+        }
+    }
+
+    // One attempt; a failure comes with the `Retry-After` the server sent, if any.
+    fn try_get_text(&self, url: &str) -> Result<String, (AppError, Option<Duration>)> {
+        let parsed_url = Url::parse(url).map_err(|e| (AppError::parse(format!("URL {}", url), e), None))?;
+        let crawl_delay = if self.respect_robots_txt {
+            let robots = self.robots_for(&parsed_url).map_err(|e| (e, None))?;
+            let path = match parsed_url.query() {
+                Some(query) => format!("{}?{}", parsed_url.path(), query),
+                None => parsed_url.path().to_string(),
+            };
+            if !robots.is_allowed(&path) {
+                return Err((AppError::RobotsDisallowed { url: url.to_string() }, None));
+            }
+            robots.crawl_delay().unwrap_or_default()
+        } else {
+            Duration::ZERO
+        };
+
+        let stored = self.stored_page(url);
+        let mut request = self.client.get(url);
+        if let Some(stored) = &stored {
+            if let Some(etag) = &stored.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &stored.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        self.rate_limiter.acquire_spaced(url, crawl_delay);
+        let response = request.send().map_err(|e| (AppError::from(e), None))?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let Some(stored) = stored {
+                log::debug!("{} is unchanged; using the stored copy.", url);
+                return Ok(stored.body);
+            }
+        }
+
+        let header = |name| response.headers().get(name).and_then(|value| value.to_str().ok()).map(str::to_string);
+        let (etag, last_modified, retry_after) = (header(ETAG), header(LAST_MODIFIED), header(RETRY_AFTER));
+        let response = response
+            .error_for_status()
+            .map_err(|e| (AppError::from(e), retry_after.as_deref().and_then(parse_retry_after)))?;
+        let body = response.text().map_err(|e| (AppError::from(e), None))?;
+        if etag.is_some() || last_modified.is_some() {
+            self.store_page(url, &StoredPage { etag, last_modified, body: body.clone() });
+        }
+        Ok(body)
+    }
+
+    // The rules for `url`'s origin. A missing robots.txt (any 4xx) allows everything; when it
+    // can't be fetched at all the site may be down, so the error is returned and the next
+    // request tries again.
+    fn robots_for(&self, url: &Url) -> Result<Arc<RobotsTxt>, AppError> {
+        let origin = url.origin().ascii_serialization();
+        // Not held while fetching: requests to other hosts shouldn't wait on this one. Workers
+        // that start on a new host together may each fetch its robots.txt.
+        if let Some(rules) = self.robots.lock().unwrap_or_else(|e| e.into_inner()).get(&origin) {
+            return Ok(rules.clone());
+        }
+
+        let robots_url = format!("{}/robots.txt", origin);
+        self.rate_limiter.acquire(&robots_url);
+        let response = self.client.get(&robots_url).send()?;
+        let rules = if response.status().is_client_error() {
+            log::info!("No robots.txt at {} (HTTP {}); crawling without restrictions.", robots_url, response.status().as_u16());
+            RobotsTxt::allow_all()
+        } else {
+            RobotsTxt::parse(&response.error_for_status()?.text()?, &self.robots_agent)
+        };
+        if let Some(crawl_delay) = rules.crawl_delay() {
+            log::info!("{} asks for {:?} between requests.", robots_url, crawl_delay);
+        }
+        let rules = Arc::new(rules);
+        self.robots.lock().unwrap_or_else(|e| e.into_inner()).insert(origin, rules.clone());
+        Ok(rules)
+    }
+
+    fn stored_page(&self, url: &str) -> Option<StoredPage> {
+        let conn = self.pages.as_ref()?.lock().unwrap_or_else(|e| e.into_inner());
+        db::stored_page(&conn, url).unwrap_or_else(|e| {
+            log::error!("Failed to read the stored copy of {}: {}", url, e);
+            None
+        })
+    }
+
+    fn store_page(&self, url: &str, page: &StoredPage) {
+        if let Some(pages) = &self.pages {
+            let conn = pages.lock().unwrap_or_else(|e| e.into_inner());
+            if let Err(e) = db::save_page(&conn, url, page) {
+                log::error!("Failed to store {}: {}", url, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    // Answers `responses` connections in order, returning the request lines and headers it got.
+    pub(in crate::scraper) fn serve(responses: Vec<&'static str>) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = String::new();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    request.push_str(&line.to_ascii_lowercase());
+                }
+                requests.push(request);
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        (base_url, handle)
+    }
+
+    pub(in crate::scraper) fn test_client(db_name: &str) -> CrawlClient {
+        let db_path = std::env::temp_dir().join(format!("eindhoven-events-crawl-{}-{}.db", db_name, std::process::id()));
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", db_path.display(), suffix));
+        }
+        let policy = CrawlPolicy {
+            min_delay: Duration::ZERO,
+            retry_delay: Duration::from_millis(10),
+            ..CrawlPolicy::default()
+        };
+        CrawlClient::new(Client::new(), "EindhovenEventViewer/0.1", policy).with_page_store(db::open_at(&db_path).unwrap())
+    }
+
+    #[test]
+    fn unchanged_pages_are_revalidated_and_disallowed_ones_skipped() {
+        let (base_url, server) = serve(vec![
+            "HTTP/1.1 200 OK\r\nContent-Length: 27\r\nConnection: close\r\n\r\nUser-agent: *\nDisallow: /nl",
+            "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: 11\r\nConnection: close\r\n\r\n<p>page</p>",
+            "HTTP/1.1 304 Not Modified\r\nETag: \"v1\"\r\nConnection: close\r\n\r\n",
+        ]);
+        let client = test_client("revalidate");
+        let cancel = CancelToken::default();
+        let page_url = format!("{}/en/events?page=1", base_url);
+
+        assert_eq!(client.get_text(&page_url, &cancel).unwrap(), "<p>page</p>");
+        assert_eq!(client.get_text(&page_url, &cancel).unwrap(), "<p>page</p>");
+        let disallowed = client.get_text(&format!("{}/nl/events?page=1", base_url), &cancel);
+        assert_eq!(disallowed.unwrap_err().code(), "robots_disallowed");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("get /robots.txt "));
+        assert!(!requests[1].contains("if-none-match"));
+        assert!(requests[2].contains("if-none-match: \"v1\""));
+    }
+
+    #[test]
+    fn missing_robots_txt_allows_everything() {
+        let (base_url, server) = serve(vec![
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Length: 4\r\nConnection: close\r\n\r\nlist",
+        ]);
+        let client = test_client("no-robots");
+        assert_eq!(client.get_text(&format!("{}/nl/events", base_url), &CancelToken::default()).unwrap(), "list");
+        assert_eq!(server.join().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn transient_failures_are_retried_and_others_not() {
+        let (base_url, server) = serve(vec![
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Length: 6\r\nConnection: close\r\n\r\npage 2",
+            "HTTP/1.1 410 Gone\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ]);
+        let client = test_client("retry");
+        let cancel = CancelToken::default();
+        assert_eq!(client.get_text(&format!("{}/en/events?page=2", base_url), &cancel).unwrap(), "page 2");
+        let gone = client.get_text(&format!("{}/en/events?page=3", base_url), &cancel).unwrap_err();
+        assert!(matches!(gone, AppError::HttpStatus { status: 410, .. }));
+        assert_eq!(server.join().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn cancelling_stops_retries() {
+        let (base_url, server) = serve(vec![
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 503 Service Unavailable\r\nRetry-After: 30\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ]);
+        let client = test_client("retry-cancel");
+        let cancel = CancelToken::default();
+        let canceller = cancel.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            canceller.cancel();
+        });
+        let started = std::time::Instant::now();
+        let unavailable = client.get_text(&format!("{}/en/events?page=2", base_url), &cancel).unwrap_err();
+        assert!(matches!(unavailable, AppError::HttpStatus { status: 503, .. }));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(server.join().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn retry_after_accepts_seconds_and_dates() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+}
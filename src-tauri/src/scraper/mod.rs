@@ -1,11 +1,27 @@
 // File: src-tauri/src/scraper/mod.rs
 
 // Declare the sub-modules within the `scraper` module
+mod batch;
+mod bilingual;
+mod cancel;
+mod crawl;
 mod parsers;
+mod rate_limit;
+mod robots;
+mod sources;
+mod structured_data;
 mod utils;
 
+#[cfg(test)]
+mod tests;
+
 // Re-export the functions that lib.rs (and thus Tauri commands) will call
-pub use parsers::{fetch_event_details, fetch_event_list_summaries};
+pub use parsers::{fetch_event_details, fetch_event_list_summaries, fetch_event_list_summaries_at, FailedPage, ScrapeReport};
+pub use batch::{fetch_event_details_batch, DetailBatchOptions, DetailBatchResult};
+pub use cancel::CancelToken;
+pub use crawl::{CrawlClient, CrawlPolicy};
+pub use sources::SourceRegistry;
+pub(crate) use utils::translate_dutch_months;
 
 // Optionally, re-export the internal testing function if you want to call it from outside
 // for some reason, though it's typically not needed for Tauri commands.
@@ -1,460 +1,547 @@
 // File: src-tauri/src/scraper/parsers.rs
-use reqwest::blocking::Client;
-use scraper::{Html, Selector};
-use std::error::Error;
-use url::Url;
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
 
+use super::batch::{fetch_event_details_batch, DetailBatchOptions};
+use super::bilingual::merge_translations;
+use super::cancel::CancelToken;
+use super::crawl::{CrawlClient, CrawlPolicy};
+use super::sources::{EventSource, SourceRegistry};
 use super::utils::*;
+use crate::changes::EventChange;
+use crate::db;
+use crate::error::AppError;
 use crate::models::Event;
-use crate::cache::{self, CacheEntry};
+use crate::search;
+
+/// After this many listing pages in a row failed, the rest of that listing is given up on.
+const MAX_CONSECUTIVE_FAILED_PAGES: u32 = 2;
+
+/// The result of `fetch_event_list_summaries`: the events plus what changed since the
+/// previous scrape (always empty when the events came from the database).
+#[derive(Debug)]
+pub struct ScrapeReport {
+    pub events: Vec<Event>,
+    pub changes: Vec<EventChange>,
+    /// Listing pages that couldn't be scraped, even after retrying. `events` lacks theirs.
+    pub failed_pages: Vec<FailedPage>,
+    /// The scrape was cancelled; `events` is what it found until then.
+    pub cancelled: bool,
+}
+
+/// A listing page that couldn't be fetched or parsed.
+#[derive(Debug, Serialize)]
+pub struct FailedPage {
+    /// `EventSource::id()` of the listing.
+    pub source: String,
+    /// The translation language for a page of a translated listing, e.g. "nl".
+    pub language: Option<String>,
+    pub page: u32,
+    pub url: String,
+    pub error: AppError,
+}
+
+// What `fetch_source_summaries` got from one listing.
+struct ListingScrape {
+    events: Vec<Event>,
+    pages_scraped: u32,
+    failed_pages: Vec<FailedPage>,
+}
 
 pub fn fetch_event_list_summaries(
-    client: &Client,
+    client: &CrawlClient,
+    registry: &SourceRegistry,
+    page_limit: Option<u32>,
+    force_refresh: bool,
+    cancel: &CancelToken,
+    progress_callback: impl Fn(crate::models::ScrapingProgress) + Send + 'static,
+) -> Result<ScrapeReport, AppError> {
+    fetch_summaries_into(db::Store::App, client, registry, page_limit, force_refresh, cancel, progress_callback)
+}
+
+/// Like `fetch_event_list_summaries`, but reads and stores the events in the database at
+/// `db_path`. The search index is left alone.
+pub fn fetch_event_list_summaries_at(
+    db_path: &Path,
+    client: &CrawlClient,
+    registry: &SourceRegistry,
+    page_limit: Option<u32>,
+    force_refresh: bool,
+    cancel: &CancelToken,
+    progress_callback: impl Fn(crate::models::ScrapingProgress) + Send + 'static,
+) -> Result<ScrapeReport, AppError> {
+    fetch_summaries_into(db::Store::At(db_path), client, registry, page_limit, force_refresh, cancel, progress_callback)
+}
+
+fn fetch_summaries_into(
+    store: db::Store,
+    client: &CrawlClient,
+    registry: &SourceRegistry,
     page_limit: Option<u32>,
     force_refresh: bool,
+    cancel: &CancelToken,
     progress_callback: impl Fn(crate::models::ScrapingProgress) + Send + 'static,
-) -> Result<Vec<Event>, Box<dyn Error>> {
-    // Try to read from cache first, unless force_refresh is true
+) -> Result<ScrapeReport, AppError> {
+    // Try the stored events from a recent scrape first, unless force_refresh is true
     if !force_refresh {
-        if let Some(cached_entry) = cache::read_cache::<Vec<Event>>() {
-            if cached_entry.is_fresh() {
-                log::info!("Returning events from cache.");
+        match store.open().and_then(|conn| db::load_fresh_events(&conn)) {
+            Ok(Some(stored_events)) => {
+                log::info!("Returning {} events from the database.", stored_events.len());
                 progress_callback(crate::models::ScrapingProgress {
                     current_page: 0,
                     total_pages_estimate: 0,
-                    events_on_current_page: cached_entry.data.len() as u32,
-                    total_events_scraped: cached_entry.data.len() as u32,
+                    events_on_current_page: stored_events.len() as u32,
+                    total_events_scraped: stored_events.len() as u32,
                     message: "Loaded from cache.".to_string(),
+                    cancelled: false,
+                });
+                return Ok(ScrapeReport {
+                    events: stored_events,
+                    changes: Vec::new(),
+                    failed_pages: Vec::new(),
+                    cancelled: false,
                 });
-                return Ok(cached_entry.data);
             }
+            Ok(None) => log::info!("No recent scrape stored; scraping."),
+            Err(e) => log::error!("Failed to read stored events: {}", e),
         }
     }
 
+    let started_at = Utc::now();
     let mut all_events: Vec<Event> = Vec::new();
-    let mut page = 1;
-    let mut has_more_pages = true;
+    let mut seen_ids: HashSet<String> = HashSet::new();
     let mut total_events_scraped = 0;
+    let mut total_pages_scraped = 0;
+    let mut failed_pages: Vec<FailedPage> = Vec::new();
 
-    while has_more_pages {
-        if let Some(limit) = page_limit {
-            if page > limit {
-                log::info!("Page limit ({}) reached. Stopping scraping.", limit);
+    for source in registry.iter() {
+        if cancel.is_cancelled() {
+            break;
+        }
+        let listing = fetch_source_summaries(client, source, None, page_limit, total_events_scraped, cancel, &progress_callback);
+        total_pages_scraped += listing.pages_scraped;
+        failed_pages.extend(listing.failed_pages);
+        if listing.pages_scraped == 0 {
+            // One broken venue site shouldn't throw away everything the other sources found.
+            log::error!("Failed to scrape source '{}'.", source.id());
+            continue;
+        }
+        let mut source_events = listing.events;
+        let languages = if registry.includes_translations() { source.translation_languages() } else { &[] };
+        for &language in languages {
+            if cancel.is_cancelled() {
                 break;
             }
+            let translated = fetch_source_summaries(
+                client,
+                source,
+                Some(language),
+                page_limit,
+                total_events_scraped + source_events.len() as u32,
+                cancel,
+                &progress_callback,
+            );
+            total_pages_scraped += translated.pages_scraped;
+            // The English events are still fine when some of these pages failed, but the
+            // scrape is then incomplete: events only listed in this language must not be
+            // marked removed.
+            failed_pages.extend(translated.failed_pages);
+            merge_translations(&mut source_events, translated.events, language);
         }
-        let page_url = format!("{}/en/events?page={}", BASE_URL, page);
-        log::info!("Fetching event list summaries from: {}", page_url);
+        for event in source_events {
+            if seen_ids.insert(event.id.clone()) {
+                all_events.push(event);
+            } else {
+                log::debug!("Skipping duplicate event '{}' from source '{}'.", event.id, source.id());
+            }
+        }
+        total_events_scraped = all_events.len() as u32;
+    }
 
-        let response_text = client.get(&page_url).send()?.text()?;
-        let document = Html::parse_document(&response_text);
-
-        let card_selector = Selector::parse("a.result-card.result-card-generic")
-            .map_err(|e| format!("Failed to parse card_selector: {:?}", e))?;
-        
-        let mut page_events_found = 0;
-        for (_index, card_element) in document.select(&card_selector).enumerate() {
-            page_events_found += 1;
-            let mut event = Event::default();
-        event.url_suffix = card_element.value().attr("href").map(str::to_string);
-        if event.url_suffix.is_none()
-            || !event
-                .url_suffix
-                .as_ref()
-                .unwrap()
-                .starts_with("/en/events/")
-        {
-            continue;
+    let cancelled = cancel.is_cancelled();
+    if cancelled {
+        log::info!("Scrape cancelled after {} pages.", total_pages_scraped);
+    }
+    if total_pages_scraped == 0 && cancelled {
+        // Nothing to store: an empty run would pass for a fresh scrape.
+        progress_callback(crate::models::ScrapingProgress {
+            current_page: 0,
+            total_pages_estimate: 0,
+            events_on_current_page: 0,
+            total_events_scraped: 0,
+            message: "Scraping cancelled before any page was loaded.".to_string(),
+            cancelled: true,
+        });
+        return Ok(ScrapeReport {
+            events: Vec::new(),
+            changes: Vec::new(),
+            failed_pages,
+            cancelled,
+        });
+    }
+    if total_pages_scraped == 0 {
+        let error = failed_pages
+            .pop()
+            .map(|failed| failed.error)
+            .unwrap_or_else(|| AppError::internal("No event sources are registered."));
+        if let Err(e) = store.open().and_then(|conn| db::record_failed_scrape(&conn, started_at, &error.to_string())) {
+            log::error!("Failed to record failed scrape: {}", e);
         }
-        event.full_url = event
-            .url_suffix
-            .as_ref()
-            .map(|s| format!("{}{}", BASE_URL, s));
-        event.id = event
-            .url_suffix
-            .clone()
-            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
-
-        let mut image_found_url: Option<String> = None;
-        let picture_selector = Selector::parse("picture.result-card-generic__picture")
-            .map_err(|e| format!("Failed to parse picture_selector: {:?}", e))?;
-        if let Some(picture_element) = card_element.select(&picture_selector).next() {
-            let source_selector = Selector::parse("source[srcset]")
-                .map_err(|e| format!("Failed to parse source_selector: {:?}", e))?;
-            for source_element in picture_element.select(&source_selector) {
-                if let Some(srcset) = source_element.value().attr("srcset") {
-                    image_found_url = parse_image_url_from_srcset(srcset, BASE_URL);
-                    if image_found_url.is_some() {
-                        break;
-                    }
-                }
-            }
+        return Err(error);
+    }
+
+    // Store the scrape; the stored versions keep any details fetched earlier.
+    let coverage = if cancelled {
+        db::ScrapeCoverage::Cancelled
+    } else if page_limit.is_none() && failed_pages.is_empty() {
+        if registry.includes_translations() {
+            db::ScrapeCoverage::Complete
+        } else {
+            db::ScrapeCoverage::Untranslated
         }
-        if image_found_url.is_none() {
-            let img_selectors = ["img.result-card-generic__image", "img"];
-            for img_selector_str in &img_selectors {
-                let img_selector = Selector::parse(img_selector_str)
-                    .map_err(|e| format!("Failed to parse img_selector: {:?}", e))?;
-                if let Some(img_element) = card_element.select(&img_selector).next() {
-                    if let Some(srcset) = img_element
-                        .value()
-                        .attr("data-srcset")
-                        .or_else(|| img_element.value().attr("srcset"))
-                    {
-                        image_found_url = parse_image_url_from_srcset(srcset, BASE_URL);
-                    } else if let Some(src) = img_element.value().attr("src") {
-                        image_found_url = make_absolute_url(BASE_URL, src);
-                    }
-                    if (event.title == "N/A" || event.title.is_empty()) {
-                        if let Some(alt_text) = img_element.value().attr("alt") {
-                            if !alt_text.trim().is_empty() {
-                                event.title = alt_text.trim().to_string();
-                            }
-                        }
-                    }
-                    if image_found_url.is_some() {
-                        break;
-                    }
-                }
+    } else {
+        db::ScrapeCoverage::Partial
+    };
+    let mut changes = Vec::new();
+    let stored = store.open().and_then(|mut conn| {
+        let stored = db::record_scrape(&mut conn, started_at, total_pages_scraped, &all_events, coverage)?;
+        if let db::Store::App = store {
+            search::update_default_index(&conn);
+        }
+        Ok(stored)
+    });
+    match stored {
+        Ok((stored_events, event_changes)) => {
+            all_events = stored_events;
+            changes = event_changes;
+        }
+        Err(e) => log::error!("Failed to store scraped events: {}", e),
+    }
+
+    progress_callback(crate::models::ScrapingProgress {
+        current_page: total_pages_scraped,
+        total_pages_estimate: total_pages_scraped,
+        events_on_current_page: 0,
+        total_events_scraped,
+        message: match failed_pages.len() {
+            _ if cancelled => format!("Scraping cancelled; kept the {} events found so far.", total_events_scraped),
+            0 => "Scraping complete.".to_string(),
+            failed => format!("Scraping complete, but {} pages could not be loaded.", failed),
+        },
+        cancelled,
+    });
+
+    Ok(ScrapeReport {
+        events: all_events,
+        changes,
+        failed_pages,
+        cancelled,
+    })
+}
+
+/// Pages through one source's listing, or its listing in a translation `language`. A page
+/// that fails is recorded and the next one tried, since without it there's no telling
+/// whether it was the last.
+fn fetch_source_summaries(
+    client: &CrawlClient,
+    source: &dyn EventSource,
+    language: Option<&str>,
+    page_limit: Option<u32>,
+    events_scraped_before: u32,
+    cancel: &CancelToken,
+    progress_callback: &impl Fn(crate::models::ScrapingProgress),
+) -> ListingScrape {
+    let mut listing = ListingScrape {
+        events: Vec::new(),
+        pages_scraped: 0,
+        failed_pages: Vec::new(),
+    };
+    let mut page = 1;
+    let mut consecutive_failures = 0;
+    let listing_name = match language {
+        Some(language) => format!("{} ({})", source.display_name(), language),
+        None => source.display_name().to_string(),
+    };
+
+    loop {
+        if cancel.is_cancelled() {
+            log::info!("Stopping the {} listing before page {}: cancelled.", listing_name, page);
+            break;
+        }
+        if let Some(limit) = page_limit {
+            if page > limit {
+                log::info!("Page limit ({}) reached for source '{}'. Stopping scraping.", limit, source.id());
+                break;
             }
         }
-        event.image_url = image_found_url;
-
-        let content_selector = Selector::parse("div.result-card-generic__content")
-            .map_err(|e| format!("Failed to parse content_selector: {:?}", e))?;
-        if let Some(content_div) = card_element.select(&content_selector).next() {
-            if event.title == "N/A" || event.title.is_empty() {
-                event.title = content_div
-                    .select(&Selector::parse("h3.result-card-generic__title").unwrap())
-                    .next()
-                    .map_or("Title N/A".to_string(), |el| get_element_text(&el));
+        let page_url = match language {
+            Some(language) => source.translated_list_page_url(language, page),
+            None => source.list_page_url(page),
+        };
+        log::info!("Fetching event list summaries from: {}", page_url);
+
+        let list_page = match client.get_text(&page_url, cancel).and_then(|html| source.parse_summaries(&html)) {
+            Ok(list_page) => list_page,
+            // Cancelled while waiting to retry: the page didn't fail, it was given up on.
+            Err(error) if error.is_transient() && cancel.is_cancelled() => {
+                log::info!("Stopping the {} listing at page {}: cancelled.", listing_name, page);
+                break;
             }
-            event.short_description = content_div
-                .select(&Selector::parse("p").unwrap())
-                .next()
-                .map(|p_el| get_element_text(&p_el));
-            event.date_time_summary = content_div
-                .select(&Selector::parse("span.tag > span").unwrap())
-                .next()
-                .map(|span_el| get_element_text(&span_el));
-
-            let meta_wrap_selector = Selector::parse("div.meta-labels-wrap")
-                .map_err(|e| format!("Failed to parse meta_wrap_selector: {:?}", e))?;
-            if let Some(meta_wrap_div) = content_div.select(&meta_wrap_selector).next() {
-                let meta_label_selector = Selector::parse("div.meta-label")
-                    .map_err(|e| format!("Failed to parse meta_label_selector: {:?}", e))?;
-                for meta_label_div in meta_wrap_div.select(&meta_label_selector) {
-                    let text = get_element_text(&meta_label_div);
-                    if meta_label_div
-                        .select(&Selector::parse("span.tie-icon-pin").unwrap())
-                        .next()
-                        .is_some()
-                    {
-                        event.list_specific_location = Some(text.clone());
-                    } else if meta_label_div
-                        .select(&Selector::parse("span.tie-icon-calendar").unwrap())
-                        .next()
-                        .is_some()
-                    {
-                        event.list_date = Some(text.clone());
-                        if event.date_time_summary.is_none() {
-                            event.date_time_summary = Some(text.clone());
-                        }
-                    } else if meta_label_div
-                        .select(&Selector::parse("span.tie-icon-euro").unwrap())
-                        .next()
-                        .is_some()
-                    {
-                        event.list_price = Some(text.clone());
-                    }
+            Err(error) => {
+                log::error!("Failed to scrape {}: {}", page_url, error);
+                progress_callback(crate::models::ScrapingProgress {
+                    current_page: page,
+                    total_pages_estimate: page,
+                    events_on_current_page: 0,
+                    total_events_scraped: events_scraped_before + listing.events.len() as u32,
+                    message: format!("Could not load {} page {}: {}", listing_name, page, error),
+                    cancelled: false,
+                });
+                listing.failed_pages.push(FailedPage {
+                    source: source.id().to_string(),
+                    language: language.map(str::to_string),
+                    page,
+                    url: page_url,
+                    error,
+                });
+                consecutive_failures += 1;
+                if consecutive_failures >= MAX_CONSECUTIVE_FAILED_PAGES {
+                    log::error!("Giving up on the {} listing after {} failed pages in a row.", listing_name, consecutive_failures);
+                    break;
                 }
+                page += 1;
+                continue;
             }
-        }
-        all_events.push(event);
-        }
-        total_events_scraped += page_events_found;
+        };
+        consecutive_failures = 0;
+        listing.pages_scraped += 1;
+
+        let page_events_found = list_page.events.len() as u32;
+        listing.events.extend(list_page.events);
 
-        let total_pages_estimate = if page_events_found == 0 { page } else { page + 5 }; // Rough estimate
+        let total_pages_estimate = if list_page.has_next_page { page + 5 } else { page }; // Rough estimate
 
         progress_callback(crate::models::ScrapingProgress {
             current_page: page,
             total_pages_estimate,
             events_on_current_page: page_events_found,
-            total_events_scraped,
-            message: format!("Scraping page {}...", page),
+            total_events_scraped: events_scraped_before + listing.events.len() as u32,
+            message: format!("Scraping {} page {}...", listing_name, page),
+            cancelled: false,
         });
 
-        if page_events_found == 0 {
-            log::info!("No event cards found on page {}. Assuming last page.", page);
-            has_more_pages = false;
-        } else {
-            // Check for a 'next page' link to determine if there are more pages
-            // This selector might need adjustment based on the actual HTML structure
-            let next_page_selector = Selector::parse("a.pagination__next")
-                .map_err(|e| format!("Failed to parse next_page_selector: {:?}", e))?;
-            if document.select(&next_page_selector).next().is_some() {
-                page += 1;
-            } else {
-                has_more_pages = false;
-            }
+        if !list_page.has_next_page {
+            log::info!("No further pages after page {} for source '{}'.", page, source.id());
+            break;
         }
+        page += 1;
     }
-    // Write to cache before returning
-    let cache_entry = CacheEntry::new(all_events.clone());
-    if let Err(e) = cache::write_cache(&cache_entry) {
-        log::error!("Failed to write events to cache: {}", e);
-    }
-
-    progress_callback(crate::models::ScrapingProgress {
-        current_page: page - 1, // Last successfully scraped page
-        total_pages_estimate: page - 1,
-        events_on_current_page: 0,
-        total_events_scraped,
-        message: "Scraping complete.".to_string(),
-    });
 
-    Ok(all_events)
+    listing
 }
 
-pub fn fetch_event_details(client: &Client, mut event: Event) -> Result<Event, Box<dyn Error>> {
-    let detail_url = event
-        .full_url
-        .as_ref()
-        .ok_or_else(|| "Missing full_url for detail fetching")?;
+pub fn fetch_event_details(
+    client: &CrawlClient,
+    registry: &SourceRegistry,
+    event: Event,
+    cancel: &CancelToken,
+) -> Result<Event, AppError> {
+    let source = registry.get(&event.source).ok_or_else(|| AppError::InvalidRequest {
+        message: format!("Unknown event source '{}'", event.source),
+    })?;
+    let detail_url = event.full_url.clone().ok_or_else(|| AppError::MissingField {
+        event_id: event.id.clone(),
+        title: event.title.clone(),
+        field: "full_url",
+    })?;
     log::info!(
         "Fetching details for event '{}' from URL: {}",
         event.title,
         detail_url
     );
-    let response_text = client.get(detail_url).send()?.text()?;
-    let document = Html::parse_document(&response_text);
-
-    // --- Scrape Main Content (Title, Description, Date/Time, Price, Location Name from list-with-icons) ---
-    let content_container_selector = Selector::parse("div.card-hero-metadata__content")
-        .map_err(|e| format!("Failed to parse detail_container: {:?}", e))?;
-    if let Some(content_container) = document.select(&content_container_selector).next() {
-        if let Some(title_el) = content_container
-            .select(&Selector::parse("h1").unwrap())
-            .next()
-        {
-            event.title = get_element_text(&title_el);
-        }
-
-        let text_div_selector = Selector::parse("div.text")
-            .map_err(|e| format!("Failed to parse text_div: {:?}", e))?;
-        if let Some(text_div) = content_container.select(&text_div_selector).next() {
-            if let Some(p_el) = text_div.select(&Selector::parse("p").unwrap()).next() {
-                event.full_description = Some(get_element_text(&p_el));
-            }
-
-            let list_icons_selector = Selector::parse("ul.list-with-icons > li")
-                .map_err(|e| format!("Failed to parse list_icons: {:?}", e))?;
-            for li_element in text_div.select(&list_icons_selector) {
-                let text_content = li_element
-                    .children()
-                    .filter_map(|node| node.value().as_text().map(|t| t.trim()))
-                    .filter(|s| !s.is_empty())
-                    .collect::<Vec<&str>>()
-                    .join(" ");
-                if text_content.is_empty() {
-                    continue;
-                }
+    let response_text = client.get_text(&detail_url, cancel)?;
+    let mut detailed = source.parse_details(&response_text, event)?;
+    fetch_translated_details(client, source, &mut detailed, cancel);
+    Ok(detailed)
+}
 
-                if li_element
-                    .select(&Selector::parse("span.tie-icon-calendar").unwrap())
-                    .next()
-                    .is_some()
-                {
-                    event.datetime_str_raw_detail = Some(text_content.clone());
-                    let (start_dt, end_dt) = parse_event_datetimes(
-                        event.list_date.as_deref(),
-                        event.datetime_str_raw_detail.as_deref(),
-                    );
-                    event.start_datetime = start_dt;
-                    event.end_datetime = end_dt;
-                } else if li_element
-                    .select(&Selector::parse("span.tie-icon-euro").unwrap())
-                    .next()
-                    .is_some()
-                {
-                    event.price = Some(text_content.clone());
-                } else if li_element
-                    .select(&Selector::parse("span.tie-icon-pin").unwrap())
-                    .next()
-                    .is_some()
-                {
-                    event.specific_location_name = Some(text_content.clone());
+// Fills in the full descriptions of the event's translations from their own detail pages.
+// A translation that can't be fetched keeps what the listing had.
+fn fetch_translated_details(client: &CrawlClient, source: &dyn EventSource, event: &mut Event, cancel: &CancelToken) {
+    for (language, translation) in event.translations.iter_mut() {
+        let Some(url) = translation.full_url.clone() else {
+            continue;
+        };
+        let translated_page = Event {
+            language: language.clone(),
+            full_url: Some(url.clone()),
+            ..Event::default()
+        };
+        match client.get_text(&url, cancel).and_then(|html| source.parse_details(&html, translated_page)) {
+            Ok(translated) => {
+                if translated.title != Event::default().title {
+                    translation.title = translated.title;
                 }
+                translation.full_description = translated.full_description.or(translation.full_description.take());
             }
+            Err(e) => log::warn!("Could not fetch the '{}' details of '{}' from {}: {}", language, event.id, url, e),
         }
     }
+}
 
-    // --- Scrape Address Block ---
-    let address_block_selector =
-        Selector::parse("div[itemprop='address'][itemtype='https://schema.org/PostalAddress']")
-            .map_err(|e| format!("Failed to parse address_block: {:?}", e))?;
-    if let Some(address_block) = document.select(&address_block_selector).next() {
-        let street = address_block
-            .select(&Selector::parse("span[itemprop='streetAddress']").unwrap())
-            .next()
-            .map(|el| get_element_text(&el));
-        let postal_code = address_block
-            .select(&Selector::parse("span[itemprop='postalCode']").unwrap())
-            .next()
-            .map(|el| get_element_text(&el));
-        let locality = address_block
-            .select(&Selector::parse("span[itemprop='addressLocality']").unwrap())
-            .next()
-            .map(|el| get_element_text(&el));
-
-        let mut address_parts: Vec<String> = Vec::new();
-        if let Some(loc_name) = &event.specific_location_name {
-            address_parts.push(loc_name.clone());
-        } else if let Some(list_loc) = &event.list_specific_location {
-            address_parts.push(list_loc.clone());
+#[allow(dead_code)] // Manual debugging helper, see scraper/mod.rs
+pub(super) fn get_all_events_with_details_internal_testing() -> Result<Vec<Event>, AppError> {
+    log::info!("Starting to fetch all events with details...");
+    let client = reqwest::blocking::Client::builder()
+        .user_agent(USER_AGENT_FOR_SCRAPING_INTERNAL_TEST)
+        .timeout(std::time::Duration::from_secs(15))
+        .build()?;
+    let client = CrawlClient::new(client, USER_AGENT_FOR_SCRAPING_INTERNAL_TEST, CrawlPolicy::default());
+    let registry = SourceRegistry::with_default_sources();
+    let event_summaries = fetch_event_list_summaries(&client, &registry, None, false, &CancelToken::default(), |_| {})?.events;
+    let batch = fetch_event_details_batch(
+        &client,
+        &registry,
+        event_summaries,
+        &DetailBatchOptions::default(),
+        |_| {},
+    );
+    for failed_id in &batch.failed_ids {
+        log::error!("Error fetching details for {}", failed_id);
+    }
+    log::info!(
+        "Finished. Total detailed events: {}",
+        batch.detailed_count()
+    );
+    Ok(batch.events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scraper::crawl::tests::{serve, test_client};
+    use crate::scraper::sources::ListPage;
+
+    // Listing pages are plain text: the event slugs, then "more" when another page follows.
+    struct PlainSource(String);
+
+    impl EventSource for PlainSource {
+        fn id(&self) -> &'static str {
+            "plain"
         }
-        if let Some(s) = street {
-            if !address_parts.contains(&s) {
-                address_parts.push(s);
-            }
+
+        fn display_name(&self) -> &'static str {
+            "Plain"
         }
-        if let Some(pc) = postal_code {
-            address_parts.push(pc);
+
+        fn list_page_url(&self, page: u32) -> String {
+            format!("{}/events?page={}", self.0, page)
         }
-        if let Some(l) = locality {
-            address_parts.push(l);
+
+        fn parse_summaries(&self, html: &str) -> Result<ListPage, AppError> {
+            let words: Vec<&str> = html.split_whitespace().collect();
+            Ok(ListPage {
+                events: words
+                    .iter()
+                    .filter(|word| **word != "more")
+                    .map(|slug| Event {
+                        id: format!("/events/{}", slug),
+                        source: "plain".to_string(),
+                        ..Event::default()
+                    })
+                    .collect(),
+                has_next_page: words.contains(&"more"),
+            })
         }
 
-        if !address_parts.is_empty() {
-            event.address = Some(address_parts.join(", "));
+        fn parse_details(&self, _html: &str, event: Event) -> Result<Event, AppError> {
+            Ok(event)
         }
     }
 
-    // --- Scrape Coordinates from div's data-src attribute ---
-    let maps_container_selector_str = "div.maps-container[data-src]";
-    let maps_container_selector = Selector::parse(maps_container_selector_str).map_err(|e| {
-        format!(
-            "Failed to parse maps_container selector '{}': {:?}",
-            maps_container_selector_str, e
-        )
-    })?;
-
-    if let Some(maps_container_element) = document.select(&maps_container_selector).next() {
-        if let Some(data_src_attr) = maps_container_element.value().attr("data-src") {
-            match Url::parse(data_src_attr) {
-                Ok(parsed_url) => {
-                    for (key, value) in parsed_url.query_pairs() {
-                        if key == "center" {
-                            let coords: Vec<&str> = value.split(',').collect();
-                            if coords.len() == 2 {
-                                if let (Ok(lat), Ok(lon)) =
-                                    (coords[0].parse::<f64>(), coords[1].parse::<f64>())
-                                {
-                                    event.latitude = Some(lat);
-                                    event.longitude = Some(lon);
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                    if event.latitude.is_none() {
-                        // Fallback to 'q' parameter
-                        for (key, value) in parsed_url.query_pairs() {
-                            if key == "q" {
-                                let coords: Vec<&str> = value.split(',').collect();
-                                if coords.len() == 2 {
-                                    if let (Ok(lat), Ok(lon)) =
-                                        (coords[0].parse::<f64>(), coords[1].parse::<f64>())
-                                    {
-                                        if (-90.0..=90.0).contains(&lat)
-                                            && (-180.0..=180.0).contains(&lon)
-                                        {
-                                            event.latitude = Some(lat);
-                                            event.longitude = Some(lon);
-                                            break;
-                                        }
-                                    }
-                                }
-                                break;
-                            }
-                        }
-                    }
-                }
-                Err(e) => log::warn!(
-                    "Failed to parse maps_container data-src URL '{}': {:?}",
-                    data_src_attr,
-                    e
-                ),
-            }
+    // A fresh database path in the temp directory, unique to this test run.
+    fn temp_db_path(name: &str) -> std::path::PathBuf {
+        let db_path = std::env::temp_dir().join(format!("eindhoven-events-parsers-{}-{}.db", name, std::process::id()));
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", db_path.display(), suffix));
         }
+        db_path
     }
 
-    // --- Scrape Ticket URL ---
-    let ticket_button_container_selector = Selector::parse("div.card-hero-metadata__buttons-inner")
-        .map_err(|e| format!("Failed to parse ticket_button_container_selector: {:?}", e))?;
-
-    if let Some(buttons_inner_div) = document.select(&ticket_button_container_selector).next() {
-        let ticket_link_selector =
-            Selector::parse("a.button[href]")
-                .map_err(|e| format!("Failed to parse ticket_link_selector: {:?}", e))?;
-
-        for link_element in buttons_inner_div.select(&ticket_link_selector) {
-            let link_text = get_element_text(&link_element).to_lowercase();
-            if link_text.contains("buy ticket") || link_text.contains("tickets") {
-                if let Some(href) = link_element.value().attr("href") {
-                    if !href.trim().is_empty() {
-                        event.ticket_url = Some(href.trim().to_string());
-                        log::info!(
-                            "Found ticket URL for '{}': {}",
-                            event.title,
-                            event.ticket_url.as_ref().unwrap()
-                        );
-                        break; 
-                    }
-                }
-            }
-        }
-        // Fallback removed:
-        // // Fallback if the text check didn't work but there's only one prominent button
-        // if event.ticket_url.is_none() {
-        //     if let Some(link_element) = buttons_inner_div.select(&ticket_link_selector).next() {
-        //         if let Some(href) = link_element.value().attr("href") {
-        //             if !href.trim().is_empty() {
-        //                 event.ticket_url = Some(href.trim().to_string());
-        //                 log::info!(
-        //                     "Found fallback ticket URL for '{}': {}",
-        //                     event.title,
-        //                     event.ticket_url.as_ref().unwrap()
-        //                 );
-        //             }
-        //         }
-        //     }
-        // }
-    } else {
-        log::warn!("Ticket button container 'div.card-hero-metadata__buttons-inner' not found for event '{}'", event.title);
+    #[test]
+    fn scrapes_are_stored_in_the_given_database() {
+        let (base_url, server) = serve(vec![
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Length: 9\r\nConnection: close\r\n\r\njazz open",
+        ]);
+        let mut registry = SourceRegistry::new();
+        registry.register(Box::new(PlainSource(base_url)));
+        let db_path = temp_db_path("store");
+        let report =
+            fetch_event_list_summaries_at(&db_path, &test_client("store"), &registry, None, true, &CancelToken::default(), |_| {})
+                .unwrap();
+        server.join().unwrap();
+
+        assert_eq!(report.events.len(), 2);
+        let conn = db::open_at(&db_path).unwrap();
+        assert_eq!(db::load_fresh_events(&conn).unwrap().map(|events| events.len()), Some(2));
     }
-    // --- End Scrape Ticket URL ---
 
-    Ok(event)
-}
+    #[test]
+    fn failed_pages_are_reported_and_paging_goes_on() {
+        let unavailable = "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+        let (base_url, server) = serve(vec![
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Length: 14\r\nConnection: close\r\n\r\njazz open more",
+            unavailable,
+            unavailable,
+            unavailable,
+            unavailable,
+            "HTTP/1.1 200 OK\r\nContent-Length: 6\r\nConnection: close\r\n\r\ncinema",
+        ]);
+        let listing = fetch_source_summaries(
+            &test_client("paging"),
+            &PlainSource(base_url.clone()),
+            None,
+            None,
+            0,
+            &CancelToken::default(),
+            &|_| {},
+        );
+        server.join().unwrap();
 
-pub(super) fn get_all_events_with_details_internal_testing() -> Result<Vec<Event>, Box<dyn Error>> {
-    log::info!("INTERNAL TESTING: Starting to fetch all events with details...");
-    let client = Client::builder()
-        .user_agent(USER_AGENT_FOR_SCRAPING_INTERNAL_TEST)
-        .timeout(std::time::Duration::from_secs(15))
-        .build()?;
-    let event_summaries = fetch_event_list_summaries(&client, None, false, |_| {})?;
-    let mut detailed_events = Vec::new();
-    for summary in event_summaries {
-        match fetch_event_details(&client, summary) {
-            Ok(detailed_event) => detailed_events.push(detailed_event),
-            Err(e) => log::error!("INTERNAL TESTING: Error fetching details: {}", e),
-        }
+        let ids: Vec<&str> = listing.events.iter().map(|event| event.id.as_str()).collect();
+        assert_eq!(ids, ["/events/jazz", "/events/open", "/events/cinema"]);
+        assert_eq!(listing.pages_scraped, 2);
+        assert_eq!(listing.failed_pages.len(), 1);
+        assert_eq!(listing.failed_pages[0].page, 2);
+        assert_eq!(listing.failed_pages[0].url, format!("{}/events?page=2", base_url));
+        assert!(matches!(listing.failed_pages[0].error, AppError::HttpStatus { status: 503, .. }));
     }
-    log::info!(
-        "INTERNAL TESTING: Finished. Total detailed events: {}",
-        detailed_events.len()
-    );
-    Ok(detailed_events)
-}
\ No newline at end of file
+
+    #[test]
+    fn cancelling_stops_paging_and_keeps_what_was_scraped() {
+        let (base_url, server) = serve(vec![
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Length: 14\r\nConnection: close\r\n\r\njazz open more",
+        ]);
+        let cancel = CancelToken::default();
+        let listing = fetch_source_summaries(
+            &test_client("cancel"),
+            &PlainSource(base_url),
+            None,
+            None,
+            0,
+            &cancel,
+            &|_| cancel.cancel(),
+        );
+        // Only robots.txt and the first page were requested.
+        assert_eq!(server.join().unwrap().len(), 2);
+
+        assert_eq!(listing.events.len(), 2);
+        assert_eq!(listing.pages_scraped, 1);
+        assert!(listing.failed_pages.is_empty());
+    }
+}
@@ -0,0 +1,77 @@
+// File: src-tauri/src/scraper/rate_limit.rs
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// Spaces out requests to the same host so that concurrent workers together stay
+/// under a requests-per-second budget. Different hosts don't wait on each other.
+pub struct HostRateLimiter {
+    min_interval: Duration,
+    next_slot: Mutex<HashMap<String, Instant>>,
+}
+
+impl HostRateLimiter {
+    pub fn with_min_interval(min_interval: Duration) -> Self {
+        HostRateLimiter {
+            min_interval,
+            next_slot: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Blocks until a request to `url`'s host may be sent.
+    pub fn acquire(&self, url: &str) {
+        self.acquire_spaced(url, Duration::ZERO);
+    }
+
+    /// Like `acquire`, but keeps requests to this host at least `interval` apart even when
+    /// that's longer than the limiter's own interval, e.g. for a robots.txt Crawl-delay.
+    pub fn acquire_spaced(&self, url: &str, interval: Duration) {
+        let interval = self.min_interval.max(interval);
+        if interval.is_zero() {
+            return;
+        }
+        let host = Url::parse(url)
+            .ok()
+            .and_then(|parsed| parsed.host_str().map(str::to_string))
+            .unwrap_or_default();
+
+        // Reserve the next free slot while holding the lock, then sleep without it.
+        let slot = {
+            let mut next_slot = self.next_slot.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let slot = next_slot
+                .get(&host)
+                .copied()
+                .filter(|reserved| *reserved > now)
+                .unwrap_or(now);
+            next_slot.insert(host, slot + interval);
+            slot
+        };
+
+        let now = Instant::now();
+        if slot > now {
+            thread::sleep(slot - now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_to_one_host_are_spaced_out() {
+        let limiter = HostRateLimiter::with_min_interval(Duration::from_millis(40));
+        let started = Instant::now();
+        limiter.acquire("https://thisiseindhoven.com/en/events");
+        limiter.acquire("https://example.com/robots.txt");
+        assert!(started.elapsed() < Duration::from_millis(40));
+
+        limiter.acquire("https://thisiseindhoven.com/en/events?page=2");
+        assert!(started.elapsed() >= Duration::from_millis(40));
+        limiter.acquire_spaced("https://thisiseindhoven.com/en/events?page=3", Duration::from_millis(80));
+        assert!(started.elapsed() >= Duration::from_millis(80));
+    }
+}
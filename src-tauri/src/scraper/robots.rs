@@ -0,0 +1,168 @@
+// File: src-tauri/src/scraper/robots.rs
+// The parts of robots.txt (RFC 9309) the scraper obeys: the Allow/Disallow rules of the group
+// for our user agent, or the `*` group, and the non-standard but common Crawl-delay.
+use regex::Regex;
+use std::time::Duration;
+
+/// Longest Crawl-delay obeyed; a site asking for more gets this.
+const MAX_CRAWL_DELAY: Duration = Duration::from_secs(60);
+
+struct Rule {
+    allow: bool,
+    // Length of the pattern as written; the longest matching rule wins.
+    specificity: usize,
+    pattern: Regex,
+}
+
+pub(super) struct RobotsTxt {
+    rules: Vec<Rule>,
+    crawl_delay: Option<Duration>,
+}
+
+// "/events*" and "/*.pdf$": `*` matches anything, a trailing `$` anchors the end.
+fn pattern_regex(pattern: &str) -> Option<Regex> {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let parts: Vec<String> = pattern.split('*').map(regex::escape).collect();
+    Regex::new(&format!("^{}{}", parts.join(".*"), if anchored { "$" } else { "" })).ok()
+}
+
+// Seconds, possibly fractional. Values too large for a `Duration` ("inf", "1e20") are capped
+// like any other long delay; negative and unparsable ones are ignored.
+fn parse_crawl_delay(value: &str) -> Option<Duration> {
+    let seconds = value.parse::<f64>().ok().filter(|seconds| *seconds >= 0.0)?;
+    let delay = Duration::try_from_secs_f64(seconds).unwrap_or(MAX_CRAWL_DELAY);
+    Some(delay.min(MAX_CRAWL_DELAY))
+}
+
+impl RobotsTxt {
+    /// No robots.txt, or one that couldn't be found: everything may be fetched.
+    pub fn allow_all() -> Self {
+        RobotsTxt {
+            rules: Vec::new(),
+            crawl_delay: None,
+        }
+    }
+
+    /// The rules for `user_agent` (the product token, e.g. "EindhovenEventViewer") in
+    /// `text`. Groups naming it win over the `*` group.
+    pub fn parse(text: &str, user_agent: &str) -> Self {
+        // (agents, rules, crawl delay) per group.
+        let mut groups: Vec<(Vec<String>, Vec<Rule>, Option<Duration>)> = Vec::new();
+        let mut in_agent_lines = false;
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let (key, value) = (key.trim().to_ascii_lowercase(), value.trim());
+            if key == "user-agent" {
+                if !in_agent_lines {
+                    groups.push((Vec::new(), Vec::new(), None));
+                }
+                in_agent_lines = true;
+                if let Some(group) = groups.last_mut() {
+                    group.0.push(value.to_ascii_lowercase());
+                }
+                continue;
+            }
+            in_agent_lines = false;
+            let Some(group) = groups.last_mut() else {
+                continue;
+            };
+            match key.as_str() {
+                // An empty Disallow allows everything, which is also what no rule does.
+                "allow" | "disallow" if !value.is_empty() => {
+                    if let Some(pattern) = pattern_regex(value) {
+                        group.1.push(Rule {
+                            allow: key == "allow",
+                            specificity: value.len(),
+                            pattern,
+                        });
+                    }
+                }
+                "crawl-delay" => group.2 = parse_crawl_delay(value),
+                _ => {}
+            }
+        }
+
+        let user_agent = user_agent.to_ascii_lowercase();
+        let ours = groups.iter().any(|(agents, _, _)| agents.contains(&user_agent));
+        let mut robots = RobotsTxt::allow_all();
+        for (agents, rules, crawl_delay) in groups {
+            let applies = if ours { agents.contains(&user_agent) } else { agents.iter().any(|agent| agent == "*") };
+            if applies {
+                robots.rules.extend(rules);
+                robots.crawl_delay = robots.crawl_delay.max(crawl_delay);
+            }
+        }
+        robots
+    }
+
+    /// Whether `path` (with its query string) may be fetched. The longest matching rule
+    /// decides; Allow wins a tie.
+    pub fn is_allowed(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|rule| rule.pattern.is_match(path))
+            .max_by_key(|rule| (rule.specificity, rule.allow))
+            .is_none_or(|rule| rule.allow)
+    }
+
+    pub fn crawl_delay(&self) -> Option<Duration> {
+        self.crawl_delay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROBOTS_TXT: &str = "\
+# Kentico sites keep their admin and search out of crawlers' way
+User-agent: *
+Disallow: /admin
+Disallow: /*?search=
+Allow: /admin/public$
+Crawl-delay: 2
+
+User-agent: BadBot
+User-agent: OtherBot
+Disallow: /
+";
+
+    #[test]
+    fn wildcard_group_applies_to_us() {
+        let robots = RobotsTxt::parse(ROBOTS_TXT, "EindhovenEventViewer");
+        assert!(robots.is_allowed("/en/events?page=2"));
+        assert!(!robots.is_allowed("/admin/login"));
+        assert!(robots.is_allowed("/admin/public"));
+        assert!(!robots.is_allowed("/admin/public/more"));
+        assert!(!robots.is_allowed("/en/events?search=jazz"));
+        assert_eq!(robots.crawl_delay(), Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn named_group_replaces_the_wildcard_group() {
+        let robots = RobotsTxt::parse(ROBOTS_TXT, "otherbot");
+        assert!(!robots.is_allowed("/en/events"));
+        assert_eq!(robots.crawl_delay(), None);
+
+        let robots = RobotsTxt::parse("User-agent: EindhovenEventViewer\nDisallow:\n\nUser-agent: *\nDisallow: /\n", "EindhovenEventViewer");
+        assert!(robots.is_allowed("/en/events"));
+    }
+
+    #[test]
+    fn crawl_delay_is_capped_and_bad_values_ignored() {
+        let delay = |value: &str| RobotsTxt::parse(&format!("User-agent: *\nCrawl-delay: {}\n", value), "EindhovenEventViewer").crawl_delay();
+        assert_eq!(delay("0.5"), Some(Duration::from_millis(500)));
+        assert_eq!(delay("inf"), Some(MAX_CRAWL_DELAY));
+        assert_eq!(delay("1e20"), Some(MAX_CRAWL_DELAY));
+        assert_eq!(delay("86400"), Some(MAX_CRAWL_DELAY));
+        assert_eq!(delay("-1"), None);
+        assert_eq!(delay("NaN"), None);
+        assert_eq!(delay("soon"), None);
+    }
+}
@@ -0,0 +1,110 @@
+// File: src-tauri/src/scraper/sources/mod.rs
+use crate::error::AppError;
+use crate::models::Event;
+
+mod thisiseindhoven;
+
+pub use thisiseindhoven::ThisIsEindhoven;
+
+/// The events found on one listing page, plus whether the paging loop should continue.
+#[derive(Debug, Default)]
+pub struct ListPage {
+    pub events: Vec<Event>,
+    pub has_next_page: bool,
+}
+
+/// A website we can scrape events from.
+///
+/// Implementations only deal with URLs and HTML; fetching, paging and caching are
+/// handled by `fetch_event_list_summaries` / `fetch_event_details` so every source
+/// gets the same behaviour. Events returned by a source must have `source` set to
+/// its `id()` and an `id` that won't collide with other sources' events.
+pub trait EventSource: Send + Sync {
+    /// Stable identifier stored in `Event.source`, e.g. "thisiseindhoven".
+    fn id(&self) -> &'static str;
+
+    /// Human-readable name used in progress messages.
+    fn display_name(&self) -> &'static str;
+
+    /// URL of the 1-based listing page `page`.
+    fn list_page_url(&self, page: u32) -> String;
+
+    /// Parses a listing page into event summaries.
+    fn parse_summaries(&self, html: &str) -> Result<ListPage, AppError>;
+
+    /// Fills in detail fields on `event` from its detail page.
+    fn parse_details(&self, html: &str, event: Event) -> Result<Event, AppError>;
+
+    /// Languages besides English the listing is published in, e.g. `&["nl"]`. Those listings
+    /// are only scraped when the registry asks for translations.
+    fn translation_languages(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// URL of the 1-based listing page `page` in one of `translation_languages`. Its events
+    /// must have `language` set.
+    fn translated_list_page_url(&self, _language: &str, page: u32) -> String {
+        self.list_page_url(page)
+    }
+}
+
+/// The set of sources a scrape merges events from, in priority order: when two
+/// sources report the same event id, the first registered source wins.
+pub struct SourceRegistry {
+    sources: Vec<Box<dyn EventSource>>,
+    include_translations: bool,
+}
+
+impl SourceRegistry {
+    pub fn new() -> Self {
+        SourceRegistry {
+            sources: Vec::new(),
+            include_translations: false,
+        }
+    }
+
+    /// Also scrape each source's listings in its `translation_languages`, e.g. the Dutch
+    /// `/nl/events`, and merge them into the English events.
+    pub fn with_translations(mut self, include_translations: bool) -> Self {
+        self.include_translations = include_translations;
+        self
+    }
+
+    pub fn includes_translations(&self) -> bool {
+        self.include_translations
+    }
+
+    /// Registry with every source the app ships with.
+    // Venue sites (Muziekgebouw, Effenaar, Van Abbemuseum, ...) get registered here
+    // once they have an `EventSource` implementation.
+    pub fn with_default_sources() -> Self {
+        let mut registry = SourceRegistry::new();
+        registry.register(Box::new(ThisIsEindhoven));
+        registry
+    }
+
+    pub fn register(&mut self, source: Box<dyn EventSource>) {
+        if self.get(source.id()).is_some() {
+            log::warn!("Event source '{}' is already registered; ignoring duplicate.", source.id());
+            return;
+        }
+        self.sources.push(source);
+    }
+
+    pub fn get(&self, id: &str) -> Option<&dyn EventSource> {
+        self.sources
+            .iter()
+            .find(|source| source.id() == id)
+            .map(|source| source.as_ref())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn EventSource> {
+        self.sources.iter().map(|source| source.as_ref())
+    }
+}
+
+impl Default for SourceRegistry {
+    fn default() -> Self {
+        SourceRegistry::with_default_sources()
+    }
+}
@@ -0,0 +1,403 @@
+// File: src-tauri/src/scraper/sources/thisiseindhoven.rs
+use scraper::{Html, Selector};
+use url::Url;
+
+use super::{EventSource, ListPage};
+use crate::error::AppError;
+use crate::models::{Event, FieldSource};
+use crate::price::Price;
+use crate::scraper::structured_data::StructuredEvent;
+use crate::series::series_id;
+use crate::scraper::utils::*;
+
+pub const SOURCE_ID: &str = "thisiseindhoven";
+
+/// The city-wide agenda at thisiseindhoven.com, the app's original (and default) source.
+pub struct ThisIsEindhoven;
+
+impl EventSource for ThisIsEindhoven {
+    fn id(&self) -> &'static str {
+        SOURCE_ID
+    }
+
+    fn display_name(&self) -> &'static str {
+        "This is Eindhoven"
+    }
+
+    fn list_page_url(&self, page: u32) -> String {
+        format!("{}/en/events?page={}", BASE_URL, page)
+    }
+
+    fn parse_summaries(&self, html: &str) -> Result<ListPage, AppError> {
+        parse_event_list_page(html)
+    }
+
+    fn parse_details(&self, html: &str, event: Event) -> Result<Event, AppError> {
+        parse_event_detail_page(html, event)
+    }
+
+    fn translation_languages(&self) -> &'static [&'static str] {
+        &["nl"]
+    }
+
+    fn translated_list_page_url(&self, language: &str, page: u32) -> String {
+        format!("{}/{}/events?page={}", BASE_URL, language, page)
+    }
+}
+
+// "/en/events/..." and "/nl/events/..." are event pages; the language is the first segment.
+fn event_page_language(url_suffix: &str) -> Option<&'static str> {
+    ["en", "nl"]
+        .into_iter()
+        .find(|language| url_suffix.starts_with(&format!("/{}/events/", language)))
+}
+
+fn parse_event_list_page(html: &str) -> Result<ListPage, AppError> {
+    let document = Html::parse_document(html);
+
+    let card_selector = Selector::parse("a.result-card.result-card-generic")
+        .map_err(|e| AppError::selector("card_selector", e))?;
+
+    let mut events: Vec<Event> = Vec::new();
+    let mut cards_found = 0;
+    for card_element in document.select(&card_selector) {
+        cards_found += 1;
+        let mut event = Event {
+            source: SOURCE_ID.to_string(),
+            url_suffix: card_element.value().attr("href").map(str::to_string),
+            ..Event::default()
+        };
+        let Some(language) = event.url_suffix.as_deref().and_then(event_page_language) else {
+            continue;
+        };
+        event.language = language.to_string();
+        event.series_id = event.url_suffix.as_deref().and_then(series_id);
+        event.full_url = event
+            .url_suffix
+            .as_ref()
+            .map(|s| format!("{}{}", BASE_URL, s));
+        event.id = event
+            .url_suffix
+            .clone()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+        let mut image_found_url: Option<String> = None;
+        let picture_selector = Selector::parse("picture.result-card-generic__picture")
+            .map_err(|e| AppError::selector("picture_selector", e))?;
+        if let Some(picture_element) = card_element.select(&picture_selector).next() {
+            let source_selector = Selector::parse("source[srcset]")
+                .map_err(|e| AppError::selector("source_selector", e))?;
+            for source_element in picture_element.select(&source_selector) {
+                if let Some(srcset) = source_element.value().attr("srcset") {
+                    image_found_url = parse_image_url_from_srcset(srcset, BASE_URL);
+                    if image_found_url.is_some() {
+                        break;
+                    }
+                }
+            }
+        }
+        if image_found_url.is_none() {
+            let img_selectors = ["img.result-card-generic__image", "img"];
+            for img_selector_str in &img_selectors {
+                let img_selector = Selector::parse(img_selector_str)
+                    .map_err(|e| AppError::selector("img_selector", e))?;
+                if let Some(img_element) = card_element.select(&img_selector).next() {
+                    if let Some(srcset) = img_element
+                        .value()
+                        .attr("data-srcset")
+                        .or_else(|| img_element.value().attr("srcset"))
+                    {
+                        image_found_url = parse_image_url_from_srcset(srcset, BASE_URL);
+                    } else if let Some(src) = img_element.value().attr("src") {
+                        image_found_url = make_absolute_url(BASE_URL, src);
+                    }
+                    if event.title == "N/A" || event.title.is_empty() {
+                        if let Some(alt_text) = img_element.value().attr("alt") {
+                            if !alt_text.trim().is_empty() {
+                                event.title = alt_text.trim().to_string();
+                            }
+                        }
+                    }
+                    if image_found_url.is_some() {
+                        break;
+                    }
+                }
+            }
+        }
+        event.image_url = image_found_url;
+
+        let content_selector = Selector::parse("div.result-card-generic__content")
+            .map_err(|e| AppError::selector("content_selector", e))?;
+        if let Some(content_div) = card_element.select(&content_selector).next() {
+            if event.title == "N/A" || event.title.is_empty() {
+                event.title = content_div
+                    .select(&Selector::parse("h3.result-card-generic__title").unwrap())
+                    .next()
+                    .map_or("Title N/A".to_string(), |el| get_element_text(&el));
+            }
+            event.short_description = content_div
+                .select(&Selector::parse("p").unwrap())
+                .next()
+                .map(|p_el| get_element_text(&p_el));
+            event.date_time_summary = content_div
+                .select(&Selector::parse("span.tag > span").unwrap())
+                .next()
+                .map(|span_el| get_element_text(&span_el));
+
+            let meta_wrap_selector = Selector::parse("div.meta-labels-wrap")
+                .map_err(|e| AppError::selector("meta_wrap_selector", e))?;
+            if let Some(meta_wrap_div) = content_div.select(&meta_wrap_selector).next() {
+                let meta_label_selector = Selector::parse("div.meta-label")
+                    .map_err(|e| AppError::selector("meta_label_selector", e))?;
+                for meta_label_div in meta_wrap_div.select(&meta_label_selector) {
+                    let text = get_element_text(&meta_label_div);
+                    if meta_label_div
+                        .select(&Selector::parse("span.tie-icon-pin").unwrap())
+                        .next()
+                        .is_some()
+                    {
+                        event.list_specific_location = Some(text.clone());
+                    } else if meta_label_div
+                        .select(&Selector::parse("span.tie-icon-calendar").unwrap())
+                        .next()
+                        .is_some()
+                    {
+                        event.list_date = Some(text.clone());
+                        if event.date_time_summary.is_none() {
+                            event.date_time_summary = Some(text.clone());
+                        }
+                    } else if meta_label_div
+                        .select(&Selector::parse("span.tie-icon-euro").unwrap())
+                        .next()
+                        .is_some()
+                    {
+                        event.parsed_price = Some(Price::parse(&text));
+                        event.list_price = Some(text.clone());
+                    }
+                }
+            }
+        }
+        events.push(event);
+    }
+
+    // An empty page means we ran past the end; otherwise follow the 'next page' link.
+    // This selector might need adjustment based on the actual HTML structure
+    let has_next_page = if cards_found == 0 {
+        false
+    } else {
+        let next_page_selector = Selector::parse("a.pagination__next")
+            .map_err(|e| AppError::selector("next_page_selector", e))?;
+        document.select(&next_page_selector).next().is_some()
+    };
+
+    Ok(ListPage {
+        events,
+        has_next_page,
+    })
+}
+
+fn parse_event_detail_page(html: &str, mut event: Event) -> Result<Event, AppError> {
+    let document = Html::parse_document(html);
+
+    // --- Scrape Main Content (Title, Description, Date/Time, Price, Location Name from list-with-icons) ---
+    let content_container_selector = Selector::parse("div.card-hero-metadata__content")
+        .map_err(|e| AppError::selector("detail_container", e))?;
+    if let Some(content_container) = document.select(&content_container_selector).next() {
+        if let Some(title_el) = content_container
+            .select(&Selector::parse("h1").unwrap())
+            .next()
+        {
+            event.title = get_element_text(&title_el);
+            event.set_field_source("title", FieldSource::Html);
+        }
+
+        let text_div_selector = Selector::parse("div.text")
+            .map_err(|e| AppError::selector("text_div", e))?;
+        if let Some(text_div) = content_container.select(&text_div_selector).next() {
+            if let Some(p_el) = text_div.select(&Selector::parse("p").unwrap()).next() {
+                event.full_description = Some(get_element_text(&p_el));
+                event.set_field_source("full_description", FieldSource::Html);
+            }
+
+            let list_icons_selector = Selector::parse("ul.list-with-icons > li")
+                .map_err(|e| AppError::selector("list_icons", e))?;
+            for li_element in text_div.select(&list_icons_selector) {
+                let text_content = li_element
+                    .children()
+                    .filter_map(|node| node.value().as_text().map(|t| t.trim()))
+                    .filter(|s| !s.is_empty())
+                    .collect::<Vec<&str>>()
+                    .join(" ");
+                if text_content.is_empty() {
+                    continue;
+                }
+
+                if li_element
+                    .select(&Selector::parse("span.tie-icon-calendar").unwrap())
+                    .next()
+                    .is_some()
+                {
+                    event.datetime_str_raw_detail = Some(text_content.clone());
+                    parse_event_datetimes(
+                        event.list_date.as_deref(),
+                        event.datetime_str_raw_detail.as_deref(),
+                    )
+                    .apply_to(&mut event);
+                    event.set_field_source("dates", FieldSource::Html);
+                } else if li_element
+                    .select(&Selector::parse("span.tie-icon-euro").unwrap())
+                    .next()
+                    .is_some()
+                {
+                    event.parsed_price = Some(Price::parse(&text_content));
+                    event.price = Some(text_content.clone());
+                    event.set_field_source("price", FieldSource::Html);
+                } else if li_element
+                    .select(&Selector::parse("span.tie-icon-pin").unwrap())
+                    .next()
+                    .is_some()
+                {
+                    event.specific_location_name = Some(text_content.clone());
+                    event.set_field_source("specific_location_name", FieldSource::Html);
+                }
+            }
+        }
+    }
+
+    // --- Scrape Address Block ---
+    let address_block_selector =
+        Selector::parse("div[itemprop='address'][itemtype='https://schema.org/PostalAddress']")
+            .map_err(|e| AppError::selector("address_block", e))?;
+    if let Some(address_block) = document.select(&address_block_selector).next() {
+        let street = address_block
+            .select(&Selector::parse("span[itemprop='streetAddress']").unwrap())
+            .next()
+            .map(|el| get_element_text(&el));
+        let postal_code = address_block
+            .select(&Selector::parse("span[itemprop='postalCode']").unwrap())
+            .next()
+            .map(|el| get_element_text(&el));
+        let locality = address_block
+            .select(&Selector::parse("span[itemprop='addressLocality']").unwrap())
+            .next()
+            .map(|el| get_element_text(&el));
+
+        event.set_address_parts(street, postal_code, locality);
+        event.set_field_source("address", FieldSource::Microdata);
+    }
+
+    // --- Scrape Coordinates from div's data-src attribute ---
+    let maps_container_selector_str = "div.maps-container[data-src]";
+    let maps_container_selector = Selector::parse(maps_container_selector_str)
+        .map_err(|e| AppError::selector("maps_container", e))?;
+
+    if let Some(maps_container_element) = document.select(&maps_container_selector).next() {
+        if let Some(data_src_attr) = maps_container_element.value().attr("data-src") {
+            match Url::parse(data_src_attr) {
+                Ok(parsed_url) => {
+                    for (key, value) in parsed_url.query_pairs() {
+                        if key == "center" {
+                            let coords: Vec<&str> = value.split(',').collect();
+                            if coords.len() == 2 {
+                                if let (Ok(lat), Ok(lon)) =
+                                    (coords[0].parse::<f64>(), coords[1].parse::<f64>())
+                                {
+                                    event.latitude = Some(lat);
+                                    event.longitude = Some(lon);
+                                    event.set_field_source("coordinates", FieldSource::Html);
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    if event.latitude.is_none() {
+                        // Fallback to 'q' parameter
+                        for (key, value) in parsed_url.query_pairs() {
+                            if key == "q" {
+                                let coords: Vec<&str> = value.split(',').collect();
+                                if coords.len() == 2 {
+                                    if let (Ok(lat), Ok(lon)) =
+                                        (coords[0].parse::<f64>(), coords[1].parse::<f64>())
+                                    {
+                                        if (-90.0..=90.0).contains(&lat)
+                                            && (-180.0..=180.0).contains(&lon)
+                                        {
+                                            event.latitude = Some(lat);
+                                            event.longitude = Some(lon);
+                                            event.set_field_source("coordinates", FieldSource::Html);
+                                            break;
+                                        }
+                                    }
+                                }
+                                break;
+                            }
+                        }
+                    }
+                }
+                Err(e) => log::warn!(
+                    "Failed to parse maps_container data-src URL '{}': {:?}",
+                    data_src_attr,
+                    e
+                ),
+            }
+        }
+    }
+
+    // --- Scrape Ticket URL ---
+    let ticket_button_container_selector = Selector::parse("div.card-hero-metadata__buttons-inner")
+        .map_err(|e| AppError::selector("ticket_button_container_selector", e))?;
+
+    if let Some(buttons_inner_div) = document.select(&ticket_button_container_selector).next() {
+        let ticket_link_selector =
+            Selector::parse("a.button[href]")
+                .map_err(|e| AppError::selector("ticket_link_selector", e))?;
+
+        for link_element in buttons_inner_div.select(&ticket_link_selector) {
+            let link_text = get_element_text(&link_element).to_lowercase();
+            if link_text.contains("buy ticket") || link_text.contains("tickets") {
+                if let Some(href) = link_element.value().attr("href") {
+                    if !href.trim().is_empty() {
+                        event.ticket_url = Some(href.trim().to_string());
+                        event.set_field_source("ticket_url", FieldSource::Html);
+                        log::info!(
+                            "Found ticket URL for '{}': {}",
+                            event.title,
+                            event.ticket_url.as_ref().unwrap()
+                        );
+                        break; 
+                    }
+                }
+            }
+        }
+        // Fallback removed:
+        // // Fallback if the text check didn't work but there's only one prominent button
+        // if event.ticket_url.is_none() {
+        //     if let Some(link_element) = buttons_inner_div.select(&ticket_link_selector).next() {
+        //         if let Some(href) = link_element.value().attr("href") {
+        //             if !href.trim().is_empty() {
+        //                 event.ticket_url = Some(href.trim().to_string());
+        //                 log::info!(
+        //                     "Found fallback ticket URL for '{}': {}",
+        //                     event.title,
+        //                     event.ticket_url.as_ref().unwrap()
+        //                 );
+        //             }
+        //         }
+        //     }
+        // }
+    } else {
+        log::warn!("Ticket button container 'div.card-hero-metadata__buttons-inner' not found for event '{}'", event.title);
+    }
+    // --- End Scrape Ticket URL ---
+
+    // --- Embedded schema.org data wins over everything above: microdata, then JSON-LD ---
+    for structured in [StructuredEvent::from_microdata(&document), StructuredEvent::from_json_ld(&document)]
+        .into_iter()
+        .flatten()
+    {
+        structured.apply_to(&mut event);
+    }
+
+    Ok(event)
+}
+
@@ -0,0 +1,460 @@
+// File: src-tauri/src/scraper/structured_data.rs
+// schema.org `Event` data embedded in detail pages, as JSON-LD or as microdata. Microdata items
+// are first turned into the same JSON shape, so both are read by `StructuredEvent::from_json`.
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use chrono_tz::Europe::Amsterdam;
+use scraper::{ElementRef, Html, Selector};
+use serde_json::{Map, Value};
+
+use crate::models::{Event, EventStatus, FieldSource};
+use crate::price::{Price, PriceKind};
+
+use super::utils::get_element_text;
+
+/// `startDate`/`endDate`: a moment, or a whole day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SchemaDate {
+    DateTime(NaiveDateTime),
+    Date(NaiveDate),
+}
+
+impl SchemaDate {
+    // Times with an offset are converted to Eindhoven's wall clock, like the rest of the app stores them.
+    fn parse(text: &str) -> Option<SchemaDate> {
+        let text = text.trim();
+        if let Ok(moment) = DateTime::parse_from_rfc3339(text) {
+            return Some(SchemaDate::DateTime(moment.with_timezone(&Amsterdam).naive_local()));
+        }
+        ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+            .map(SchemaDate::DateTime)
+            .or_else(|| NaiveDate::parse_from_str(text, "%Y-%m-%d").ok().map(SchemaDate::Date))
+    }
+
+    fn date(self) -> NaiveDate {
+        match self {
+            SchemaDate::DateTime(moment) => moment.date(),
+            SchemaDate::Date(date) => date,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Offer {
+    low: Option<f64>,
+    high: Option<f64>,
+    currency: Option<String>,
+    url: Option<String>,
+}
+
+/// What a page's schema.org `Event` says, before it's merged into an `Event`.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct StructuredEvent {
+    source: FieldSource,
+    name: Option<String>,
+    description: Option<String>,
+    image: Option<String>,
+    start: Option<SchemaDate>,
+    end: Option<SchemaDate>,
+    offers: Vec<Offer>,
+    performers: Vec<String>,
+    status: Option<EventStatus>,
+    location_name: Option<String>,
+    street: Option<String>,
+    postal_code: Option<String>,
+    locality: Option<String>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+}
+
+// "https://schema.org/MusicEvent" and "schema:MusicEvent" are both "MusicEvent".
+fn short_type(schema_type: &str) -> &str {
+    schema_type.rsplit(['/', ':', '#']).next().unwrap_or(schema_type)
+}
+
+fn is_event_type(value: &Value) -> bool {
+    let is_event = |schema_type: &str| short_type(schema_type).ends_with("Event");
+    match value {
+        Value::String(schema_type) => is_event(schema_type),
+        Value::Array(types) => types.iter().filter_map(Value::as_str).any(is_event),
+        _ => false,
+    }
+}
+
+// Every value of a property, whether given once or as an array.
+fn values(value: Option<&Value>) -> Vec<&Value> {
+    match value {
+        Some(Value::Array(items)) => items.iter().collect(),
+        Some(Value::Null) | None => Vec::new(),
+        Some(item) => vec![item],
+    }
+}
+
+fn text(value: Option<&Value>) -> Option<String> {
+    values(value).into_iter().find_map(|value| match value {
+        Value::String(text) => Some(text.trim().to_string()).filter(|text| !text.is_empty()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Object(object) => text(object.get("@value").or(object.get("name")).or(object.get("url"))),
+        _ => None,
+    })
+}
+
+fn number(value: Option<&Value>) -> Option<f64> {
+    values(value).into_iter().find_map(|value| match value {
+        Value::Number(number) => number.as_f64(),
+        // "12.50", "12,50" and "€ 12,50" all occur.
+        Value::String(text) => text
+            .trim()
+            .trim_start_matches(|c: char| !c.is_ascii_digit())
+            .replace(',', ".")
+            .parse()
+            .ok(),
+        _ => None,
+    })
+}
+
+fn object(value: Option<&Value>) -> Option<&Map<String, Value>> {
+    values(value).into_iter().find_map(Value::as_object)
+}
+
+fn event_status(value: Option<&Value>) -> Option<EventStatus> {
+    match short_type(&text(value)?) {
+        "EventScheduled" => Some(EventStatus::Scheduled),
+        "EventCancelled" => Some(EventStatus::Cancelled),
+        "EventPostponed" => Some(EventStatus::Postponed),
+        "EventRescheduled" => Some(EventStatus::Rescheduled),
+        "EventMovedOnline" => Some(EventStatus::MovedOnline),
+        _ => None,
+    }
+}
+
+impl StructuredEvent {
+    fn from_json(event: &Map<String, Value>, source: FieldSource) -> StructuredEvent {
+        let offers = values(event.get("offers"))
+            .into_iter()
+            .filter_map(Value::as_object)
+            .map(|offer| {
+                let price = number(offer.get("price"));
+                Offer {
+                    low: number(offer.get("lowPrice")).or(price),
+                    high: number(offer.get("highPrice")).or(price),
+                    currency: text(offer.get("priceCurrency")),
+                    url: text(offer.get("url")),
+                }
+            })
+            .collect();
+        let performers = values(event.get("performer"))
+            .into_iter()
+            .filter_map(|performer| text(Some(performer)))
+            .collect();
+
+        let location = object(event.get("location"));
+        let address = location.and_then(|location| location.get("address"));
+        let postal_address = object(address);
+        let geo = location.and_then(|location| object(location.get("geo")));
+        StructuredEvent {
+            source,
+            name: text(event.get("name")),
+            description: text(event.get("description")),
+            image: text(event.get("image")),
+            start: text(event.get("startDate")).as_deref().and_then(SchemaDate::parse),
+            end: text(event.get("endDate")).as_deref().and_then(SchemaDate::parse),
+            offers,
+            performers,
+            status: event_status(event.get("eventStatus")),
+            location_name: location
+                .and_then(|location| text(location.get("name")))
+                .or_else(|| text(event.get("location")).filter(|_| location.is_none())),
+            street: postal_address
+                .and_then(|address| text(address.get("streetAddress")))
+                .or_else(|| address.and_then(Value::as_str).map(|address| address.trim().to_string())),
+            postal_code: postal_address.and_then(|address| text(address.get("postalCode"))),
+            locality: postal_address.and_then(|address| text(address.get("addressLocality"))),
+            latitude: geo.and_then(|geo| number(geo.get("latitude"))),
+            longitude: geo.and_then(|geo| number(geo.get("longitude"))),
+        }
+    }
+
+    /// The first schema.org `Event` in the page's JSON-LD blocks, also looking inside arrays and `@graph`.
+    pub(super) fn from_json_ld(document: &Html) -> Option<StructuredEvent> {
+        let script_selector = Selector::parse("script[type='application/ld+json']").unwrap();
+        document.select(&script_selector).find_map(|script| {
+            let json = script.text().collect::<String>();
+            let parsed: Value = match serde_json::from_str(json.trim()) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    log::debug!("Skipping unparseable JSON-LD block: {}", e);
+                    return None;
+                }
+            };
+            let mut candidates = values(Some(&parsed));
+            let graphs: Vec<&Value> = candidates
+                .iter()
+                .flat_map(|candidate| values(candidate.get("@graph")))
+                .collect();
+            candidates.extend(graphs);
+            candidates
+                .into_iter()
+                .filter_map(Value::as_object)
+                .find(|candidate| candidate.get("@type").is_some_and(is_event_type))
+                .map(|event| StructuredEvent::from_json(event, FieldSource::JsonLd))
+        })
+    }
+
+    /// The first microdata item typed as a schema.org `Event`.
+    pub(super) fn from_microdata(document: &Html) -> Option<StructuredEvent> {
+        let item_selector = Selector::parse("[itemscope][itemtype]").unwrap();
+        document
+            .select(&item_selector)
+            .find(|item| item.value().attr("itemtype").is_some_and(|itemtype| {
+                itemtype.split_whitespace().any(|schema_type| short_type(schema_type).ends_with("Event"))
+            }))
+            .map(|item| StructuredEvent::from_json(&microdata_item(item), FieldSource::Microdata))
+    }
+
+    /// Overwrites `event`'s fields with the ones found here, noting where they came from. A
+    /// price the page already listed keeps its text and tiers.
+    pub(super) fn apply_to(self, event: &mut Event) {
+        let source = self.source;
+        if let Some(name) = self.name {
+            event.title = name;
+            event.set_field_source("title", source);
+        }
+        if let Some(description) = self.description {
+            event.full_description = Some(description);
+            event.set_field_source("full_description", source);
+        }
+        if let Some(image) = self.image {
+            event.image_url = Some(image);
+            event.set_field_source("image_url", source);
+        }
+        if let Some(start) = self.start {
+            let end = self.end.filter(|end| end.date() >= start.date());
+            match start {
+                SchemaDate::DateTime(start_datetime) => {
+                    event.all_day = false;
+                    event.start_datetime = Some(start_datetime);
+                    event.end_datetime = match end {
+                        Some(SchemaDate::DateTime(end_datetime)) => Some(end_datetime),
+                        _ => None,
+                    };
+                }
+                SchemaDate::Date(_) => {
+                    event.all_day = true;
+                    event.start_datetime = None;
+                    event.end_datetime = None;
+                }
+            }
+            event.start_date = Some(start.date());
+            event.end_date = Some(end.unwrap_or(start).date());
+            event.set_field_source("dates", source);
+        }
+        if let Some(offered) = offers_price(&self.offers) {
+            match event.parsed_price.as_mut().filter(|_| event.price.is_some()) {
+                // The page's own price line can say more, e.g. what students pay; only its
+                // amounts are taken from the offers.
+                Some(parsed) => {
+                    parsed.kind = offered.kind;
+                    parsed.currency = offered.currency;
+                }
+                None => {
+                    event.price = Some(price_text(&offered));
+                    event.parsed_price = Some(offered);
+                }
+            }
+            event.set_field_source("price", source);
+        }
+        if let Some(ticket_url) = self.offers.iter().find_map(|offer| offer.url.clone()) {
+            event.ticket_url = Some(ticket_url);
+            event.set_field_source("ticket_url", source);
+        }
+        if !self.performers.is_empty() {
+            event.performers = self.performers;
+            event.set_field_source("performers", source);
+        }
+        if let Some(status) = self.status {
+            event.event_status = Some(status);
+            event.set_field_source("event_status", source);
+        }
+        if let Some(location_name) = self.location_name {
+            event.specific_location_name = Some(location_name);
+            event.set_field_source("specific_location_name", source);
+        }
+        if self.street.is_some() || self.postal_code.is_some() || self.locality.is_some() {
+            event.set_address_parts(self.street, self.postal_code, self.locality);
+            event.set_field_source("address", source);
+        }
+        if let (Some(latitude), Some(longitude)) = (self.latitude, self.longitude) {
+            event.latitude = Some(latitude);
+            event.longitude = Some(longitude);
+            event.set_field_source("coordinates", source);
+        }
+    }
+}
+
+fn offers_price(offers: &[Offer]) -> Option<Price> {
+    let amounts: Vec<f64> = offers.iter().flat_map(|offer| [offer.low, offer.high]).flatten().collect();
+    let min = amounts.iter().copied().reduce(f64::min)?;
+    let max = amounts.iter().copied().reduce(f64::max)?;
+    let kind = if max > min {
+        PriceKind::Range { min, max }
+    } else if min > 0.0 {
+        PriceKind::Amount { amount: min }
+    } else {
+        PriceKind::Free
+    };
+    let currency = offers.iter().find_map(|offer| offer.currency.clone()).unwrap_or_else(|| "EUR".to_string());
+    Some(Price {
+        currency: (kind != PriceKind::Free).then_some(currency),
+        kind,
+        tiers: Vec::new(),
+    })
+}
+
+// Written like the site's own price lines, e.g. "€ 12,50" or "€ 10,00 - € 25,00".
+fn price_text(price: &Price) -> String {
+    let amount = |value: f64| match price.currency.as_deref() {
+        Some("EUR") | None => format!("€ {:.2}", value).replace('.', ","),
+        Some(currency) => format!("{} {:.2}", currency, value),
+    };
+    match price.kind {
+        PriceKind::Range { min, max } => format!("{} - {}", amount(min), amount(max)),
+        PriceKind::Amount { amount: value } | PriceKind::From { amount: value } => amount(value),
+        _ => "Free".to_string(),
+    }
+}
+
+// A microdata item as a JSON-LD-like object: nested items become objects, repeated
+// properties arrays.
+fn microdata_item(item: ElementRef) -> Map<String, Value> {
+    let mut properties = Map::new();
+    if let Some(itemtype) = item.value().attr("itemtype") {
+        properties.insert("@type".to_string(), Value::String(itemtype.to_string()));
+    }
+    collect_properties(item, &mut properties);
+    properties
+}
+
+fn collect_properties(element: ElementRef, properties: &mut Map<String, Value>) {
+    for child in element.children().filter_map(ElementRef::wrap) {
+        let is_item = child.value().attr("itemscope").is_some();
+        if let Some(names) = child.value().attr("itemprop") {
+            let value = if is_item {
+                Value::Object(microdata_item(child))
+            } else {
+                Value::String(property_value(child))
+            };
+            for name in names.split_whitespace() {
+                match properties.get_mut(name) {
+                    Some(Value::Array(existing)) => existing.push(value.clone()),
+                    Some(existing) => *existing = Value::Array(vec![existing.take(), value.clone()]),
+                    None => {
+                        properties.insert(name.to_string(), value.clone());
+                    }
+                }
+            }
+        }
+        // A nested item's properties belong to that item.
+        if !is_item {
+            collect_properties(child, properties);
+        }
+    }
+}
+
+fn property_value(element: ElementRef) -> String {
+    let attribute = match element.value().name() {
+        "meta" => "content",
+        "time" => "datetime",
+        "a" | "link" => "href",
+        "img" | "source" => "src",
+        "data" | "meter" => "value",
+        _ => "content",
+    };
+    element
+        .value()
+        .attr(attribute)
+        .or_else(|| element.value().attr("content"))
+        .map(|value| value.trim().to_string())
+        .unwrap_or_else(|| get_element_text(&element))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datetime(text: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn parses_schema_dates_as_eindhoven_time() {
+        assert_eq!(SchemaDate::parse("2025-07-09T10:00:00+02:00"), Some(SchemaDate::DateTime(datetime("2025-07-09 10:00"))));
+        assert_eq!(SchemaDate::parse("2025-07-09T08:00:00Z"), Some(SchemaDate::DateTime(datetime("2025-07-09 10:00"))));
+        assert_eq!(SchemaDate::parse("2025-12-01T20:30"), Some(SchemaDate::DateTime(datetime("2025-12-01 20:30"))));
+        assert_eq!(SchemaDate::parse("2025-10-18"), Some(SchemaDate::Date(NaiveDate::from_ymd_opt(2025, 10, 18).unwrap())));
+        assert_eq!(SchemaDate::parse("soon"), None);
+    }
+
+    #[test]
+    fn finds_event_in_json_ld_graph() {
+        let html = r#"<html><head>
+            <script type="application/ld+json">{ not json</script>
+            <script type="application/ld+json">
+            {"@context": "https://schema.org", "@graph": [
+                {"@type": "WebPage", "name": "Page"},
+                {"@type": ["MusicEvent"], "name": "Jazz in the Park",
+                 "startDate": "2025-08-02T15:00:00+02:00", "endDate": "2025-08-02T18:00:00+02:00",
+                 "eventStatus": "https://schema.org/EventPostponed",
+                 "performer": [{"@type": "MusicGroup", "name": "The Dommel Trio"}, "Anna K"],
+                 "offers": {"@type": "AggregateOffer", "lowPrice": "10", "highPrice": 25.5, "priceCurrency": "EUR"},
+                 "location": {"@type": "Place", "name": "Stadswandelpark",
+                    "address": {"@type": "PostalAddress", "streetAddress": "Leenderweg", "postalCode": "5615AA", "addressLocality": "Eindhoven"}}}
+            ]}
+            </script></head><body></body></html>"#;
+        let structured = StructuredEvent::from_json_ld(&Html::parse_document(html)).unwrap();
+        assert_eq!(structured.name.as_deref(), Some("Jazz in the Park"));
+        assert_eq!(structured.start, Some(SchemaDate::DateTime(datetime("2025-08-02 15:00"))));
+        assert_eq!(structured.status, Some(EventStatus::Postponed));
+        assert_eq!(structured.performers, ["The Dommel Trio", "Anna K"]);
+        assert_eq!(structured.location_name.as_deref(), Some("Stadswandelpark"));
+
+        let mut event = Event::default();
+        structured.apply_to(&mut event);
+        assert_eq!(event.price.as_deref(), Some("€ 10,00 - € 25,50"));
+        assert_eq!(event.parsed_price.unwrap().kind, PriceKind::Range { min: 10.0, max: 25.5 });
+        assert_eq!(event.postal_code.as_deref(), Some("5615 AA"));
+        assert_eq!(event.field_sources.get("dates"), Some(&FieldSource::JsonLd));
+    }
+
+    #[test]
+    fn reads_microdata_event_with_nested_items() {
+        let html = r#"<html><body>
+            <div itemscope itemtype="https://schema.org/TheaterEvent">
+              <h1 itemprop="name">De Gebroeders Leeuwenhart</h1>
+              <time itemprop="startDate" datetime="2025-11-14">14 November</time>
+              <link itemprop="eventStatus" href="https://schema.org/EventCancelled">
+              <div itemprop="location" itemscope itemtype="https://schema.org/Place">
+                <span itemprop="name">Parktheater</span>
+                <div itemprop="geo" itemscope itemtype="https://schema.org/GeoCoordinates">
+                  <meta itemprop="latitude" content="51.4311"><meta itemprop="longitude" content="5.4738">
+                </div>
+              </div>
+              <div itemprop="offers" itemscope itemtype="https://schema.org/Offer">
+                <meta itemprop="price" content="0"><a itemprop="url" href="https://tickets.example/gebroeders">Tickets</a>
+              </div>
+            </div></body></html>"#;
+        let structured = StructuredEvent::from_microdata(&Html::parse_document(html)).unwrap();
+        let mut event = Event::default();
+        structured.apply_to(&mut event);
+        assert_eq!(event.title, "De Gebroeders Leeuwenhart");
+        assert!(event.all_day);
+        assert_eq!(event.start_date, NaiveDate::from_ymd_opt(2025, 11, 14));
+        assert_eq!(event.event_status, Some(EventStatus::Cancelled));
+        assert_eq!(event.specific_location_name.as_deref(), Some("Parktheater"));
+        assert_eq!((event.latitude, event.longitude), (Some(51.4311), Some(5.4738)));
+        assert_eq!(event.price.as_deref(), Some("Free"));
+        assert_eq!(event.ticket_url.as_deref(), Some("https://tickets.example/gebroeders"));
+        assert_eq!(event.field_sources.get("title"), Some(&FieldSource::Microdata));
+    }
+}
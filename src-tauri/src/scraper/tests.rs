@@ -0,0 +1,213 @@
+// File: src-tauri/src/scraper/tests.rs
+//! Offline parser tests driven by the HTML snapshots in `tests/fixtures/<source id>/`.
+//!
+//! Each `<name>.html` fixture is parsed and compared against `<name>.golden.json`.
+//! When the site's markup changes on purpose, update the fixture and regenerate the
+//! golden files with `UPDATE_GOLDEN=1 cargo test`, then review the diff.
+
+use chrono::NaiveDate;
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+
+use super::bilingual::merge_translations;
+use super::sources::{EventSource, ThisIsEindhoven};
+use super::utils::parse_event_datetimes;
+use crate::models::{Event, EventStatus, FieldSource};
+use crate::price::{Audience, PriceKind, PriceTier};
+
+fn fixture_path(source: &dyn EventSource, file_name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(source.id())
+        .join(file_name)
+}
+
+fn read_fixture(source: &dyn EventSource, file_name: &str) -> String {
+    let path = fixture_path(source, file_name);
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("Failed to read fixture {:?}: {}", path, e))
+}
+
+fn assert_matches_golden(source: &dyn EventSource, name: &str, actual: &Value) {
+    let path = fixture_path(source, &format!("{}.golden.json", name));
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        let contents = serde_json::to_string_pretty(actual).unwrap() + "\n";
+        fs::write(&path, contents).unwrap_or_else(|e| panic!("Failed to write golden {:?}: {}", path, e));
+        return;
+    }
+    let expected: Value = serde_json::from_str(&read_fixture(source, &format!("{}.golden.json", name)))
+        .unwrap_or_else(|e| panic!("Golden file {:?} is not valid JSON: {}", path, e));
+    assert_eq!(
+        actual,
+        &expected,
+        "Parsed output for fixture '{}' no longer matches {:?}.\nActual:\n{}",
+        name,
+        path,
+        serde_json::to_string_pretty(actual).unwrap()
+    );
+}
+
+fn parse_list_fixture(source: &dyn EventSource, name: &str) -> (Vec<Event>, bool) {
+    let html = read_fixture(source, &format!("{}.html", name));
+    let page = source
+        .parse_summaries(&html)
+        .unwrap_or_else(|e| panic!("Failed to parse list fixture '{}': {}", name, e));
+    (page.events, page.has_next_page)
+}
+
+fn parse_detail_fixture(source: &dyn EventSource, name: &str) -> Event {
+    let summary: Event = serde_json::from_str(&read_fixture(source, &format!("{}.input.json", name)))
+        .unwrap_or_else(|e| panic!("Failed to read summary for detail fixture '{}': {}", name, e));
+    let html = read_fixture(source, &format!("{}.html", name));
+    source
+        .parse_details(&html, summary)
+        .unwrap_or_else(|e| panic!("Failed to parse detail fixture '{}': {}", name, e))
+}
+
+#[test]
+fn list_page_with_next_link_matches_golden() {
+    let source = ThisIsEindhoven;
+    let (events, has_next_page) = parse_list_fixture(&source, "list_page_1");
+    assert!(has_next_page, "page 1 links to page 2");
+    assert_matches_golden(&source, "list_page_1", &serde_json::to_value(&events).unwrap());
+}
+
+#[test]
+fn list_page_skips_non_event_cards() {
+    let source = ThisIsEindhoven;
+    let (events, _) = parse_list_fixture(&source, "list_page_1");
+    assert_eq!(events.len(), 3);
+    assert!(events.iter().all(|e| e.id.starts_with("/en/events/")));
+    assert!(events.iter().all(|e| e.source == source.id()));
+}
+
+#[test]
+fn last_list_page_matches_golden() {
+    let source = ThisIsEindhoven;
+    let (events, has_next_page) = parse_list_fixture(&source, "list_page_last");
+    assert!(!has_next_page, "the last page has no next link");
+    assert_matches_golden(&source, "list_page_last", &serde_json::to_value(&events).unwrap());
+}
+
+#[test]
+fn empty_list_page_stops_paging() {
+    let source = ThisIsEindhoven;
+    let (events, has_next_page) = parse_list_fixture(&source, "list_page_empty");
+    assert!(events.is_empty());
+    assert!(!has_next_page, "an empty page ends paging even if a next link is present");
+}
+
+#[test]
+fn dutch_list_page_merges_into_english_events() {
+    let source = ThisIsEindhoven;
+    let (dutch, has_next_page) = parse_list_fixture(&source, "list_page_nl");
+    assert!(!has_next_page);
+    assert!(dutch.iter().all(|e| e.language == "nl" && e.id.starts_with("/nl/events/")));
+    assert_matches_golden(&source, "list_page_nl", &serde_json::to_value(&dutch).unwrap());
+
+    let (mut events, _) = parse_list_fixture(&source, "list_page_1");
+    merge_translations(&mut events, dutch, "nl");
+    assert_eq!(events.len(), 4);
+    let comedy = events.iter().find(|e| e.title == "Stage Cocktail Comedy Nights").unwrap();
+    assert_eq!(comedy.translations["nl"].title, "Stage Cocktail Comedy Avonden");
+    let dutch_only = &events[3];
+    assert_eq!(dutch_only.title, "Voorleesochtend in de bibliotheek");
+    assert_eq!(
+        crate::query::event_date(dutch_only),
+        NaiveDate::from_ymd_opt(2025, 5, 3).and_then(|date| date.and_hms_opt(0, 0, 0))
+    );
+}
+
+#[test]
+fn detail_page_with_tickets_matches_golden() {
+    let source = ThisIsEindhoven;
+    let event = parse_detail_fixture(&source, "detail_summer_art_club");
+    assert_matches_golden(&source, "detail_summer_art_club", &serde_json::to_value(&event).unwrap());
+}
+
+#[test]
+fn detail_page_past_midnight_matches_golden() {
+    let source = ThisIsEindhoven;
+    let event = parse_detail_fixture(&source, "detail_comedy_night");
+    assert_matches_golden(&source, "detail_comedy_night", &serde_json::to_value(&event).unwrap());
+}
+
+#[test]
+fn detail_page_date_range_matches_golden() {
+    let source = ThisIsEindhoven;
+    let event = parse_detail_fixture(&source, "detail_dutch_design_week");
+    assert!(event.all_day);
+    assert_eq!(event.start_date, NaiveDate::from_ymd_opt(2025, 10, 18));
+    assert_eq!(event.end_date, NaiveDate::from_ymd_opt(2025, 10, 26));
+    assert_eq!(event.start_datetime, None);
+    assert_matches_golden(&source, "detail_dutch_design_week", &serde_json::to_value(&event).unwrap());
+}
+
+#[test]
+fn detail_page_json_ld_wins_over_markup() {
+    let source = ThisIsEindhoven;
+    let event = parse_detail_fixture(&source, "detail_jazz_in_the_park");
+    assert_eq!(event.title, "Jazz in the Park 2025");
+    assert_eq!(event.end_datetime, NaiveDate::from_ymd_opt(2025, 8, 2).and_then(|date| date.and_hms_opt(22, 30, 0)));
+    assert_eq!(event.street.as_deref(), Some("Leenderweg 65"));
+    assert_eq!(event.performers, ["The Dommel Trio", "Anna Kuipers"]);
+    assert_eq!(event.event_status, Some(EventStatus::Scheduled));
+    assert_eq!(event.field_sources.get("dates"), Some(&FieldSource::JsonLd));
+    assert_matches_golden(&source, "detail_jazz_in_the_park", &serde_json::to_value(&event).unwrap());
+}
+
+#[test]
+fn detail_page_offers_keep_the_listed_price_tiers() {
+    let source = ThisIsEindhoven;
+    let event = parse_detail_fixture(&source, "detail_parktheater_family_show");
+    assert_eq!(event.price.as_deref(), Some("€ 12,50 / € 8,50 (students)"));
+    let price = event.parsed_price.as_ref().unwrap();
+    assert_eq!(price.kind, PriceKind::Amount { amount: 12.5 });
+    assert_eq!(price.tiers, [PriceTier { audience: Audience::Student, amount: 8.5 }]);
+    assert_eq!(event.field_sources.get("price"), Some(&FieldSource::JsonLd));
+    assert_matches_golden(&source, "detail_parktheater_family_show", &serde_json::to_value(&event).unwrap());
+}
+
+#[test]
+fn date_ranges_and_dates_without_time_are_all_day() {
+    let range = parse_event_datetimes(None, Some("9 July 2025 - 14 July"));
+    assert!(range.all_day);
+    assert_eq!(range.start_date, NaiveDate::from_ymd_opt(2025, 7, 9));
+    assert_eq!(range.end_date, NaiveDate::from_ymd_opt(2025, 7, 14));
+
+    let new_year = parse_event_datetimes(None, Some("Sun 28 December 2025 to Sat 3 January"));
+    assert_eq!(new_year.start_date, NaiveDate::from_ymd_opt(2025, 12, 28));
+    assert_eq!(new_year.end_date, NaiveDate::from_ymd_opt(2026, 1, 3));
+
+    let single_day = parse_event_datetimes(Some("12 Jul 2025"), None);
+    assert!(single_day.all_day);
+    assert_eq!(single_day.start_date, NaiveDate::from_ymd_opt(2025, 7, 12));
+    assert_eq!(single_day.end_date, single_day.start_date);
+    assert_eq!(single_day.start_datetime, None);
+
+    let timed = parse_event_datetimes(None, Some("Thursday 10 July 2025, Starts at 21:00 - 01:00"));
+    assert!(!timed.all_day);
+    assert_eq!(timed.start_date, NaiveDate::from_ymd_opt(2025, 7, 10));
+    assert_eq!(timed.end_date, NaiveDate::from_ymd_opt(2025, 7, 11));
+}
+
+#[test]
+fn dutch_month_and_weekday_names_are_understood() {
+    let timed = parse_event_datetimes(None, Some("za 12 juli 2025, Aanvang 21:00 - 23:30"));
+    assert!(!timed.all_day);
+    assert_eq!(timed.start_datetime, NaiveDate::from_ymd_opt(2025, 7, 12).and_then(|date| date.and_hms_opt(21, 0, 0)));
+    assert_eq!(timed.end_datetime, NaiveDate::from_ymd_opt(2025, 7, 12).and_then(|date| date.and_hms_opt(23, 30, 0)));
+
+    let may = parse_event_datetimes(Some("3 mei 2025"), None);
+    assert!(may.all_day);
+    assert_eq!(may.start_date, NaiveDate::from_ymd_opt(2025, 5, 3));
+
+    let range = parse_event_datetimes(None, Some("vrijdag 28 maart t/m zondag 6 april 2025"));
+    assert_eq!(range.start_date, NaiveDate::from_ymd_opt(2025, 3, 28));
+    assert_eq!(range.end_date, NaiveDate::from_ymd_opt(2025, 4, 6));
+
+    let october = parse_event_datetimes(None, Some("18 okt tot en met 26 okt 2025"));
+    assert_eq!(october.start_date, NaiveDate::from_ymd_opt(2025, 10, 18));
+    assert_eq!(october.end_date, NaiveDate::from_ymd_opt(2025, 10, 26));
+}
@@ -3,6 +3,8 @@ use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use regex::Regex;
 use scraper::ElementRef;
 use url::Url;
+
+use crate::models::Event;
 // Remove these lines:
 // use geocoding::{Forward, Point, Nominatim};
 
@@ -70,11 +72,110 @@ pub(super) fn parse_image_url_from_srcset(
     }
 }
 
+/// Dates parsed from an event's listing. Timed events get `start_datetime`/`end_datetime`;
+/// events listed without a time, or spanning several days, are `all_day` and only have dates.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(super) struct EventDates {
+    pub start_datetime: Option<NaiveDateTime>,
+    pub end_datetime: Option<NaiveDateTime>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub all_day: bool,
+}
+
+impl EventDates {
+    fn all_day(start_date: NaiveDate, end_date: NaiveDate) -> Self {
+        EventDates {
+            start_date: Some(start_date),
+            end_date: Some(end_date),
+            all_day: true,
+            ..EventDates::default()
+        }
+    }
+
+    pub(super) fn apply_to(self, event: &mut Event) {
+        event.start_datetime = self.start_datetime;
+        event.end_datetime = self.end_datetime;
+        event.start_date = self.start_date;
+        event.end_date = self.end_date;
+        event.all_day = self.all_day;
+    }
+}
+
+// Dutch month names chrono doesn't know; the others ("april", "jul", "dec", ...) are spelled
+// like the English ones.
+const DUTCH_MONTHS: &[(&str, &str)] = &[
+    ("januari", "January"),
+    ("februari", "February"),
+    ("maart", "March"),
+    ("mrt", "Mar"),
+    ("mei", "May"),
+    ("juni", "June"),
+    ("juli", "July"),
+    ("augustus", "August"),
+    ("oktober", "October"),
+    ("okt", "Oct"),
+];
+
+/// Replaces Dutch month names with English ones, so "za 12 juli" parses like "Sat 12 July".
+pub(crate) fn translate_dutch_months(text: &str) -> String {
+    let word_re = Regex::new(r"\p{L}+\.?").unwrap();
+    word_re
+        .replace_all(text, |caps: &regex::Captures| {
+            let word = caps[0].trim_end_matches('.').to_lowercase();
+            DUTCH_MONTHS
+                .iter()
+                .find(|(dutch, _)| *dutch == word)
+                .map_or_else(|| caps[0].to_string(), |(_, english)| english.to_string())
+        })
+        .into_owned()
+}
+
+fn parse_day_month(date_base: &str, year: i32) -> Option<NaiveDate> {
+    let date_with_year = format!("{} {}", translate_dutch_months(date_base), year);
+    let date_formats = ["%d %B %Y", "%d %b %Y"];
+    date_formats
+        .iter()
+        .find_map(|fmt| NaiveDate::parse_from_str(&date_with_year, fmt).ok())
+}
+
+// English and Dutch weekday names, written out ("zaterdag") or abbreviated ("Sat", "za").
+const WEEKDAY_PATTERN: &str = r"\b(?:Mon|Tue|Wed|Thu|Fri|Sat|Sun|ma|di|wo|do|vr|za|zo)\w*\.?";
+
+/// Recognises date ranges such as "9 July - 14 July", "18 - 26 October 2025",
+/// "Fri 28 December 2025 to Sun 4 January 2026" or "za 12 juli t/m zo 20 juli". Times next to a range are daily opening
+/// hours, so they are left in the raw string.
+fn parse_date_range(text: &str, current_year: i32) -> Option<(NaiveDate, NaiveDate)> {
+    let range_re = Regex::new(&format!(r"(?i)\b(\d{{1,2}})(?:\s+([a-z]{{3,}})\.?)?(?:\s+(\d{{4}}))?\s*(?:-|–|—|\bto\b|\buntil\b|\bt/m\b|\btot en met\b|\btot\b)\s*(?:{}\s+)?(\d{{1,2}})\s+([a-z]{{3,}})\.?(?:\s+(\d{{4}}))?", WEEKDAY_PATTERN)).unwrap();
+    let caps = range_re.captures(text)?;
+    let year_at = |index: usize| caps.get(index).and_then(|m| m.as_str().parse::<i32>().ok());
+    let (start_year, end_year) = match (year_at(3), year_at(6)) {
+        (Some(start), Some(end)) => (start, Some(end)),
+        (Some(start), None) => (start, None),
+        (None, Some(end)) => (end, Some(end)),
+        (None, None) => (current_year, None),
+    };
+    let end_month = caps.get(5)?.as_str();
+    let start_month = caps.get(2).map_or(end_month, |m| m.as_str());
+
+    let start_date = parse_day_month(&format!("{} {}", &caps[1], start_month), start_year)?;
+    let mut end_date = parse_day_month(&format!("{} {}", &caps[4], end_month), end_year.unwrap_or(start_year))?;
+    // "28 December - 3 January" runs into the next year.
+    if end_date < start_date && end_year.is_none() {
+        end_date = parse_day_month(&format!("{} {}", &caps[4], end_month), start_year + 1)?;
+    }
+    if end_date < start_date {
+        log::warn!("Date range '{}' ends before it starts; ignoring it.", text);
+        return None;
+    }
+    Some((start_date, end_date))
+}
+
 // --- (parse_event_datetimes function) ---
 pub(super) fn parse_event_datetimes(
     list_date_opt: Option<&str>,
     datetime_str_raw_detail_opt: Option<&str>,
-) -> (Option<NaiveDateTime>, Option<NaiveDateTime>) {
+) -> EventDates {
     let current_year = Utc::now().year();
     log::debug!(
         "Attempting to parse datetimes with list_date: {:?}, detail_str: {:?}, current_year: {}",
@@ -82,11 +183,17 @@ pub(super) fn parse_event_datetimes(
         datetime_str_raw_detail_opt,
         current_year
     );
+    for text in datetime_str_raw_detail_opt.into_iter().chain(list_date_opt) {
+        if let Some((start_date, end_date)) = parse_date_range(text, current_year) {
+            return EventDates::all_day(start_date, end_date);
+        }
+    }
+
     let mut final_date_str_base: Option<String> = None;
     let mut final_year_override: Option<i32> = None;
     let mut start_time_str: Option<String> = None;
     let mut end_time_str: Option<String> = None;
-    let detail_re = Regex::new( r"(?i)(?:(?:Mon|Tue|Wed|Thu|Fri|Sat|Sun)\w*\s+)?(\d{1,2}\s+\w+)(?:\s+(\d{4}))?(?:\s*,\s*)?(?:(?:Starts at\s+)?(\d{2}:\d{2}))?(?:\s*-\s*(\d{2}:\d{2}))?" ).unwrap();
+    let detail_re = Regex::new(&format!(r"(?i)(?:{}\s+)?(\d{{1,2}}\s+\w+)(?:\s+(\d{{4}}))?(?:\s*,\s*)?(?:(?:Starts at\s+|Aanvang\s+|Begint om\s+)?(\d{{2}}:\d{{2}}))?(?:\s*-\s*(\d{{2}}:\d{{2}}))?", WEEKDAY_PATTERN)).unwrap();
     if let Some(detail_str) = datetime_str_raw_detail_opt {
         if let Some(caps) = detail_re.captures(detail_str) {
             if let Some(date_match) = caps.get(1) {
@@ -143,36 +250,33 @@ pub(super) fn parse_event_datetimes(
         }
     }
     let year_to_use = final_year_override.unwrap_or(current_year);
-    let mut parsed_start_datetime: Option<NaiveDateTime> = None;
+    let naive_date = match final_date_str_base.and_then(|date_base_val| parse_day_month(&date_base_val, year_to_use)) {
+        Some(naive_date) => naive_date,
+        None => return EventDates::default(),
+    };
+    let naive_start_time = match start_time_str.and_then(|st_str| NaiveTime::parse_from_str(&st_str, "%H:%M").ok()) {
+        Some(naive_start_time) => naive_start_time,
+        // A date without a start time is an all-day event.
+        None => return EventDates::all_day(naive_date, naive_date),
+    };
+
     let mut parsed_end_datetime: Option<NaiveDateTime> = None;
-    if let Some(date_base_val) = final_date_str_base {
-        let date_with_year = format!("{} {}", date_base_val, year_to_use);
-        let date_formats = ["%d %B %Y", "%d %b %Y"];
-        let mut naive_date_opt: Option<NaiveDate> = None;
-        for fmt in &date_formats {
-            if let Ok(parsed_date) = NaiveDate::parse_from_str(&date_with_year, fmt) {
-                naive_date_opt = Some(parsed_date);
-                break;
-            }
-        }
-        if let Some(naive_date) = naive_date_opt {
-            if let Some(st_str) = start_time_str {
-                if let Ok(naive_start_time) = NaiveTime::parse_from_str(&st_str, "%H:%M") {
-                    parsed_start_datetime = Some(naive_date.and_time(naive_start_time));
-                    if let Some(et_str) = end_time_str {
-                        if let Ok(naive_end_time) = NaiveTime::parse_from_str(&et_str, "%H:%M") {
-                            let mut end_date_to_use = naive_date;
-                            if naive_end_time < naive_start_time {
-                                if let Some(next_day) = naive_date.succ_opt() {
-                                    end_date_to_use = next_day;
-                                }
-                            }
-                            parsed_end_datetime = Some(end_date_to_use.and_time(naive_end_time));
-                        }
-                    }
+    if let Some(et_str) = end_time_str {
+        if let Ok(naive_end_time) = NaiveTime::parse_from_str(&et_str, "%H:%M") {
+            let mut end_date_to_use = naive_date;
+            if naive_end_time < naive_start_time {
+                if let Some(next_day) = naive_date.succ_opt() {
+                    end_date_to_use = next_day;
                 }
             }
+            parsed_end_datetime = Some(end_date_to_use.and_time(naive_end_time));
         }
     }
-    (parsed_start_datetime, parsed_end_datetime)
+    EventDates {
+        start_datetime: Some(naive_date.and_time(naive_start_time)),
+        end_datetime: parsed_end_datetime,
+        start_date: Some(naive_date),
+        end_date: Some(parsed_end_datetime.map_or(naive_date, |end| end.date())),
+        all_day: false,
+    }
 }
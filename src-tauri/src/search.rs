@@ -0,0 +1,401 @@
+// File: src-tauri/src/search.rs
+// Full-text search over the stored events. The index lives next to the database in `cache/`
+// and is derived from it: `sync` indexes whatever was written since the last sync, and
+// deleting the directory just means the next sync rebuilds it.
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tantivy::collector::TopDocs;
+use tantivy::directory::MmapDirectory;
+use tantivy::query::QueryParser;
+use tantivy::schema::{Field, IndexRecordOption, Schema, TextFieldIndexing, TextOptions, Value, STORED, STRING};
+use tantivy::snippet::SnippetGenerator;
+use tantivy::tokenizer::{AsciiFoldingFilter, Language, LowerCaser, RemoveLongFilter, SimpleTokenizer, Stemmer, TextAnalyzer};
+use tantivy::{doc, Index, IndexWriter, TantivyDocument, TantivyError, Term};
+
+use crate::cache;
+use crate::db;
+use crate::error::AppError;
+use crate::models::Event;
+
+const INDEX_DIR_NAME: &str = "search_index";
+/// Bump when the fields or analyzers change; an index built by another version is rebuilt.
+const INDEX_VERSION: u32 = 1;
+const WRITER_MEMORY_BYTES: usize = 15_000_000;
+const SNIPPET_MAX_CHARS: usize = 160;
+/// Results returned when the caller doesn't ask for a number.
+pub const DEFAULT_LIMIT: usize = 50;
+
+const ENGLISH_TOKENIZER: &str = "english_stem";
+const DUTCH_TOKENIZER: &str = "dutch_stem";
+
+/// One search result, best match first.
+#[derive(Debug, Serialize, Clone)]
+pub struct SearchHit {
+    pub event: Event,
+    pub score: f32,
+    /// The title with matched words in `<b>` tags, when the title matched.
+    pub title_html: Option<String>,
+    /// A passage of the description with matched words in `<b>` tags, when it matched.
+    pub snippet_html: Option<String>,
+}
+
+// Stored in the commit payload, so it only changes together with the documents.
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct SyncState {
+    version: u32,
+    synced_until: Option<DateTime<Utc>>,
+}
+
+// Each text is indexed twice, once stemmed as English and once as Dutch, since listings mix both.
+struct Fields {
+    id: Field,
+    title: Field,
+    title_nl: Field,
+    description: Field,
+    description_nl: Field,
+    venue: Field,
+    venue_nl: Field,
+}
+
+impl Fields {
+    fn schema() -> (Schema, Fields) {
+        let text = |tokenizer: &str| {
+            TextOptions::default().set_indexing_options(
+                TextFieldIndexing::default()
+                    .set_tokenizer(tokenizer)
+                    .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+            )
+        };
+        let mut builder = Schema::builder();
+        let fields = Fields {
+            id: builder.add_text_field("id", STRING | STORED),
+            // The English copies are stored so snippets can be cut from them.
+            title: builder.add_text_field("title", text(ENGLISH_TOKENIZER).set_stored()),
+            title_nl: builder.add_text_field("title_nl", text(DUTCH_TOKENIZER)),
+            description: builder.add_text_field("description", text(ENGLISH_TOKENIZER).set_stored()),
+            description_nl: builder.add_text_field("description_nl", text(DUTCH_TOKENIZER)),
+            venue: builder.add_text_field("venue", text(ENGLISH_TOKENIZER).set_stored()),
+            venue_nl: builder.add_text_field("venue_nl", text(DUTCH_TOKENIZER)),
+        };
+        (builder.build(), fields)
+    }
+
+    fn document(&self, event: &Event) -> TantivyDocument {
+        let description = [event.short_description.as_deref(), event.full_description.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let venue = [
+            event.specific_location_name.as_deref().or(event.list_specific_location.as_deref()),
+            event.address.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(", ");
+        // The Dutch page's text is only searched with Dutch stemming.
+        let dutch = event.translations.get("nl");
+        let title_nl = [Some(event.title.as_str()), dutch.map(|translation| translation.title.as_str())]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join("\n");
+        let description_nl = [
+            Some(description.as_str()),
+            dutch.and_then(|translation| translation.short_description.as_deref()),
+            dutch.and_then(|translation| translation.full_description.as_deref()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n\n");
+        doc!(
+            self.id => event.id.as_str(),
+            self.title => event.title.as_str(),
+            self.title_nl => title_nl.as_str(),
+            self.description => description.as_str(),
+            self.description_nl => description_nl.as_str(),
+            self.venue => venue.as_str(),
+            self.venue_nl => venue.as_str(),
+        )
+    }
+}
+
+fn index_error(context: &str) -> impl Fn(TantivyError) -> AppError + '_ {
+    move |e| AppError::cache(format!("search index: {}", context), e)
+}
+
+fn stemming_analyzer(language: Language) -> TextAnalyzer {
+    TextAnalyzer::builder(SimpleTokenizer::default())
+        .filter(RemoveLongFilter::limit(40))
+        .filter(LowerCaser)
+        .filter(AsciiFoldingFilter)
+        .filter(Stemmer::new(language))
+        .build()
+}
+
+pub struct SearchIndex {
+    index: Index,
+    fields: Fields,
+}
+
+impl SearchIndex {
+    /// The index in the cache directory, created on first use.
+    pub fn open_default() -> Result<SearchIndex, AppError> {
+        SearchIndex::open(&cache::get_cache_path(INDEX_DIR_NAME)?)
+    }
+
+    /// Opens or creates the index in `dir`. An index with an outdated schema is thrown away.
+    pub fn open(dir: &Path) -> Result<SearchIndex, AppError> {
+        let (schema, fields) = Fields::schema();
+        fs::create_dir_all(dir).map_err(|e| AppError::cache("create search index directory", e))?;
+        let open = || Index::open_or_create(MmapDirectory::open(dir)?, schema.clone());
+        let index = match open() {
+            Ok(index) => index,
+            // Built with other fields; it's derived data, so start over.
+            Err(TantivyError::SchemaError(message)) => {
+                log::warn!("Rebuilding the search index at {:?}: {}", dir, message);
+                fs::remove_dir_all(dir).map_err(|e| AppError::cache("remove outdated search index", e))?;
+                fs::create_dir_all(dir).map_err(|e| AppError::cache("create search index directory", e))?;
+                open().map_err(index_error("create"))?
+            }
+            Err(e) => return Err(index_error("open")(e)),
+        };
+        Ok(SearchIndex::with_tokenizers(index, fields))
+    }
+
+    /// An index that only lives as long as the value, for tests and one-off searches.
+    pub fn in_memory() -> SearchIndex {
+        let (schema, fields) = Fields::schema();
+        SearchIndex::with_tokenizers(Index::create_in_ram(schema), fields)
+    }
+
+    // Tokenizers aren't saved with the index, so they're registered on every open.
+    fn with_tokenizers(index: Index, fields: Fields) -> SearchIndex {
+        index.tokenizers().register(ENGLISH_TOKENIZER, stemming_analyzer(Language::English));
+        index.tokenizers().register(DUTCH_TOKENIZER, stemming_analyzer(Language::Dutch));
+        SearchIndex { index, fields }
+    }
+
+    fn sync_state(&self) -> Result<SyncState, AppError> {
+        let metas = self.index.load_metas().map_err(index_error("read metadata"))?;
+        Ok(metas
+            .payload
+            .and_then(|payload| serde_json::from_str(&payload).ok())
+            .unwrap_or_default())
+    }
+
+    /// Indexes the events written to the database since the last sync and drops removed ones.
+    /// Returns how many documents were added or removed.
+    pub fn sync(&self, conn: &Connection) -> Result<usize, AppError> {
+        let state = self.sync_state()?;
+        let rebuild = state.version != INDEX_VERSION;
+        let since = if rebuild { None } else { state.synced_until };
+        let changed = db::events_changed_since(conn, since)?;
+        if !rebuild && changed.listed.is_empty() && changed.removed_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let mut writer: IndexWriter = self
+            .index
+            .writer_with_num_threads(1, WRITER_MEMORY_BYTES)
+            .map_err(index_error("open writer"))?;
+        if rebuild {
+            writer.delete_all_documents().map_err(index_error("clear"))?;
+        }
+        for id in &changed.removed_ids {
+            writer.delete_term(Term::from_field_text(self.fields.id, id));
+        }
+        for event in &changed.listed {
+            writer.delete_term(Term::from_field_text(self.fields.id, &event.id));
+            writer
+                .add_document(self.fields.document(event))
+                .map_err(index_error("add event"))?;
+        }
+
+        let state = SyncState {
+            version: INDEX_VERSION,
+            synced_until: changed.until.or(since),
+        };
+        let payload = serde_json::to_string(&state).map_err(|e| AppError::internal(format!("Failed to serialize search index state: {}", e)))?;
+        let mut commit = writer.prepare_commit().map_err(index_error("prepare commit"))?;
+        commit.set_payload(&payload);
+        commit.commit().map_err(index_error("commit"))?;
+
+        let updated = changed.listed.len() + changed.removed_ids.len();
+        log::info!("Search index updated: {} events indexed, {} removed.", changed.listed.len(), changed.removed_ids.len());
+        Ok(updated)
+    }
+
+    /// Stored events matching `text`, best first. Words are stemmed (so "concerts" finds
+    /// "concert" and "wandelingen" finds "wandeling") and all of them must match; titles weigh most.
+    pub fn search(&self, conn: &Connection, text: &str, limit: usize) -> Result<Vec<SearchHit>, AppError> {
+        if text.trim().is_empty() {
+            return Err(AppError::InvalidRequest {
+                message: "Enter something to search for.".to_string(),
+            });
+        }
+        let fields = &self.fields;
+        let mut parser = QueryParser::for_index(
+            &self.index,
+            vec![
+                fields.title,
+                fields.title_nl,
+                fields.description,
+                fields.description_nl,
+                fields.venue,
+                fields.venue_nl,
+            ],
+        );
+        parser.set_conjunction_by_default();
+        parser.set_field_boost(fields.title, 3.0);
+        parser.set_field_boost(fields.title_nl, 3.0);
+        parser.set_field_boost(fields.venue, 1.5);
+        parser.set_field_boost(fields.venue_nl, 1.5);
+        // Typed queries aren't meant as query syntax; stray quotes or colons are ignored.
+        let (query, _) = parser.parse_query_lenient(text);
+
+        let searcher = self.index.reader().map_err(index_error("open reader"))?.searcher();
+        let top_docs = searcher
+            .search(&query, &TopDocs::with_limit(limit.max(1)))
+            .map_err(index_error("search"))?;
+        let mut title_snippets = SnippetGenerator::create(&searcher, &*query, fields.title).map_err(index_error("highlight"))?;
+        title_snippets.set_max_num_chars(usize::MAX);
+        let mut description_snippets =
+            SnippetGenerator::create(&searcher, &*query, fields.description).map_err(index_error("highlight"))?;
+        description_snippets.set_max_num_chars(SNIPPET_MAX_CHARS);
+
+        let mut hits = Vec::with_capacity(top_docs.len());
+        for (score, address) in top_docs {
+            let document: TantivyDocument = searcher.doc(address).map_err(index_error("read document"))?;
+            let Some(id) = document.get_first(fields.id).and_then(|value| value.as_str()) else {
+                continue;
+            };
+            // The index can briefly lag behind the database; skip what's no longer stored.
+            let Some(event) = db::find_event(conn, id)? else {
+                continue;
+            };
+            let highlighted = |generator: &SnippetGenerator| {
+                let snippet = generator.snippet_from_doc(&document);
+                (!snippet.highlighted().is_empty()).then(|| snippet.to_html())
+            };
+            hits.push(SearchHit {
+                event,
+                score,
+                title_html: highlighted(&title_snippets),
+                snippet_html: highlighted(&description_snippets),
+            });
+        }
+        Ok(hits)
+    }
+}
+
+/// Syncs the default index, then searches it. A failed sync is logged and the possibly
+/// slightly stale index is searched anyway.
+pub fn search_stored_events(conn: &Connection, text: &str, limit: usize) -> Result<Vec<SearchHit>, AppError> {
+    let index = SearchIndex::open_default()?;
+    if let Err(e) = index.sync(conn) {
+        log::warn!("Searching without updating the search index: {}", e);
+    }
+    index.search(conn, text, limit)
+}
+
+/// Brings the default index up to date after the database changed. Failures are only logged:
+/// the next search syncs again.
+pub fn update_default_index(conn: &Connection) {
+    if let Err(e) = SearchIndex::open_default().and_then(|index| index.sync(conn)) {
+        log::warn!("Failed to update the search index: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(slug: &str, title: &str, description: &str, venue: &str) -> Event {
+        Event {
+            id: format!("/en/events/{}", slug),
+            title: title.to_string(),
+            short_description: Some(description.to_string()),
+            list_specific_location: Some(venue.to_string()),
+            ..Event::default()
+        }
+    }
+
+    fn test_database(name: &str, events: &[Event]) -> Connection {
+        let path = std::env::temp_dir().join(format!("eindhoven-events-search-{}-{}.db", name, std::process::id()));
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+        let mut conn = db::open_at(&path).expect("open test database");
+        db::record_scrape(&mut conn, Utc::now(), 1, events, db::ScrapeCoverage::Complete).expect("record scrape");
+        conn
+    }
+
+    fn ids(hits: &[SearchHit]) -> Vec<&str> {
+        hits.iter().map(|hit| hit.event.id.as_str()).collect()
+    }
+
+    fn sample_events() -> Vec<Event> {
+        vec![
+            event("jazz", "Jazz in the Park", "Live concerts every Sunday afternoon.", "Stadswandelpark"),
+            event("wandeling", "Stadswandeling Strijp-S", "Een wandeling langs de oude fabrieken van Philips.", "Strijp-S"),
+            event("expo", "Summer Expo", "Paintings inspired by jazz records.", "Van Abbemuseum"),
+        ]
+    }
+
+    #[test]
+    fn stems_english_and_dutch_words() {
+        let conn = test_database("stem", &sample_events());
+        let index = SearchIndex::in_memory();
+        index.sync(&conn).unwrap();
+
+        assert_eq!(ids(&index.search(&conn, "concert", 10).unwrap()), ["/en/events/jazz"]);
+        assert_eq!(ids(&index.search(&conn, "wandelingen", 10).unwrap()), ["/en/events/wandeling"]);
+        assert_eq!(ids(&index.search(&conn, "fabriek", 10).unwrap()), ["/en/events/wandeling"]);
+        assert_eq!(ids(&index.search(&conn, "abbemuseum", 10).unwrap()), ["/en/events/expo"]);
+    }
+
+    #[test]
+    fn ranks_title_matches_first_and_highlights_them() {
+        let conn = test_database("rank", &sample_events());
+        let index = SearchIndex::in_memory();
+        index.sync(&conn).unwrap();
+
+        let hits = index.search(&conn, "jazz", 10).unwrap();
+        assert_eq!(ids(&hits), ["/en/events/jazz", "/en/events/expo"]);
+        assert_eq!(hits[0].title_html.as_deref(), Some("<b>Jazz</b> in the Park"));
+        assert_eq!(hits[1].title_html, None);
+        assert_eq!(hits[1].snippet_html.as_deref(), Some("Paintings inspired by <b>jazz</b> records"));
+    }
+
+    #[test]
+    fn sync_only_applies_new_writes() {
+        let mut conn = test_database("sync", &sample_events());
+        let index = SearchIndex::in_memory();
+        assert_eq!(index.sync(&conn).unwrap(), 3);
+        assert_eq!(index.sync(&conn).unwrap(), 0);
+
+        // A complete scrape without "expo" marks it removed; "jazz" gets a new description.
+        let mut events = sample_events();
+        events.pop();
+        events[0].short_description = Some("Big band swing on the lawn.".to_string());
+        db::record_scrape(&mut conn, Utc::now(), 1, &events, db::ScrapeCoverage::Complete).unwrap();
+        index.sync(&conn).unwrap();
+
+        assert!(index.search(&conn, "paintings", 10).unwrap().is_empty());
+        assert!(index.search(&conn, "concerts", 10).unwrap().is_empty());
+        assert_eq!(ids(&index.search(&conn, "swing", 10).unwrap()), ["/en/events/jazz"]);
+    }
+
+    #[test]
+    fn empty_query_is_rejected() {
+        let conn = test_database("empty", &sample_events());
+        let error = SearchIndex::in_memory().search(&conn, "  ", 10).unwrap_err();
+        assert_eq!(error.code(), "invalid_request");
+    }
+}
@@ -0,0 +1,181 @@
+// File: src-tauri/src/series.rs
+// The site publishes a page per date of a recurring event, e.g.
+// "/en/events/summer-art-club-creative-with-the-garden/9-july" and ".../16-july". Events whose
+// URLs differ only in that last date segment are grouped into one `EventSeries`.
+use chrono::{Duration, NaiveDateTime};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::models::Event;
+
+// A URL's last segment when it's a date like "9-july", "july-10", "12-juli" or "3-mei-2026".
+static OCCURRENCE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    let month = "jan(?:uary|uari)?|feb(?:ruary|ruari)?|mar(?:ch)?|maart|mrt|apr(?:il)?|ma[yi]|mei|jun[ei]?|jul[yi]?\
+                 |aug(?:ust|ustus)?|sept?(?:ember)?|o[ck]t(?:ober)?|nov(?:ember)?|dec(?:ember)?";
+    Regex::new(&format!(r"^(?:\d{{1,2}}-(?:{m})|(?:{m})-\d{{1,2}})(?:-\d{{4}})?$", m = month)).unwrap()
+});
+
+/// The part of an event URL that all of a series' dates share, or `None` when the URL doesn't
+/// end in a date segment like "9-july", "july-10", "12-juli" or "3-mei-2026".
+pub fn series_id(url_suffix: &str) -> Option<String> {
+    let (series, occurrence) = url_suffix.trim_end_matches('/').rsplit_once('/')?;
+    if !OCCURRENCE_RE.is_match(&occurrence.to_lowercase()) || series.is_empty() || series.ends_with("/events") {
+        return None;
+    }
+    Some(series.to_string())
+}
+
+/// The dates of one event, each its own `Event` with its own page.
+#[derive(Debug, Clone, Serialize)]
+pub struct EventSeries {
+    /// `Event::series_id` of the occurrences, or the event id for an event that doesn't repeat.
+    pub id: String,
+    pub title: String,
+    /// Earliest first; occurrences without a start go last.
+    pub occurrences: Vec<Event>,
+}
+
+/// How a recurring series' dates follow from its first occurrence.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Recurrence {
+    /// Every `interval` days, `count` times including the first.
+    Daily { interval: i64, count: usize },
+    /// Every `interval` weeks, `count` times including the first.
+    Weekly { interval: i64, count: usize },
+    /// Irregular: the starts of the occurrences after the first.
+    Dates(Vec<NaiveDateTime>),
+}
+
+impl EventSeries {
+    pub fn is_recurring(&self) -> bool {
+        self.occurrences.len() > 1
+    }
+
+    /// How the occurrences repeat. `None` for a single event, and when the occurrences can't
+    /// be described as repeats of the first: one has no start, or they differ in length or in
+    /// being all-day.
+    pub fn recurrence(&self) -> Option<Recurrence> {
+        let (first, rest) = self.occurrences.split_first()?;
+        if rest.is_empty() {
+            return None;
+        }
+        let length = |event: &Event| event.ends_at().zip(event.starts_at()).map(|(end, start)| end - start);
+        if rest.iter().any(|event| event.all_day != first.all_day || length(event) != length(first)) {
+            return None;
+        }
+        let starts = self.occurrences.iter().map(Event::starts_at).collect::<Option<Vec<_>>>()?;
+        let gap = starts[1] - starts[0];
+        let regular = gap > Duration::zero()
+            && gap.num_seconds() % Duration::days(1).num_seconds() == 0
+            && starts.windows(2).all(|pair| pair[1] - pair[0] == gap);
+        if !regular {
+            return Some(Recurrence::Dates(starts[1..].to_vec()));
+        }
+        let (days, count) = (gap.num_days(), starts.len());
+        Some(if days % 7 == 0 {
+            Recurrence::Weekly { interval: days / 7, count }
+        } else {
+            Recurrence::Daily { interval: days, count }
+        })
+    }
+}
+
+/// Groups `events` by `series_id`, keeping the order in which each series first appears.
+/// Events that aren't part of a series become a series of one.
+pub fn group_into_series(events: Vec<Event>) -> Vec<EventSeries> {
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut grouped: Vec<EventSeries> = Vec::new();
+    for event in events {
+        let id = event.series_id.clone().unwrap_or_else(|| event.id.clone());
+        match positions.get(&id) {
+            Some(&position) => grouped[position].occurrences.push(event),
+            None => {
+                positions.insert(id.clone(), grouped.len());
+                grouped.push(EventSeries {
+                    id,
+                    title: event.title.clone(),
+                    occurrences: vec![event],
+                });
+            }
+        }
+    }
+    for series in &mut grouped {
+        series.occurrences.sort_by_key(|event| (event.starts_at().is_none(), event.starts_at()));
+        series.title = series.occurrences[0].title.clone();
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn occurrence(day: u32, month: u32, hour: u32) -> Event {
+        let start = NaiveDate::from_ymd_opt(2025, month, day).unwrap().and_hms_opt(hour, 0, 0).unwrap();
+        let url_suffix = format!("/en/events/summer-art-club/{}-{}", day, ["july", "august"][month as usize - 7]);
+        Event {
+            id: url_suffix.clone(),
+            title: "Summer Art Club".to_string(),
+            series_id: series_id(&url_suffix),
+            url_suffix: Some(url_suffix),
+            start_datetime: Some(start),
+            end_datetime: Some(start + Duration::minutes(150)),
+            start_date: Some(start.date()),
+            end_date: Some(start.date()),
+            ..Event::default()
+        }
+    }
+
+    #[test]
+    fn series_id_strips_the_date_segment() {
+        assert_eq!(series_id("/en/events/summer-art-club/9-july").as_deref(), Some("/en/events/summer-art-club"));
+        assert_eq!(
+            series_id("/en/events/stage-comedy-9aeae763a30c2f7b4d9dfbe38107a9be/july-10").as_deref(),
+            Some("/en/events/stage-comedy-9aeae763a30c2f7b4d9dfbe38107a9be")
+        );
+        assert_eq!(series_id("/nl/events/voorleesochtend/3-mei-2026/").as_deref(), Some("/nl/events/voorleesochtend"));
+        assert_eq!(series_id("/en/events/dutch-design-week-2025"), None);
+        assert_eq!(series_id("/en/events/9-july"), None);
+        assert_eq!(series_id("/en/events/route/stop-12"), None);
+    }
+
+    #[test]
+    fn groups_occurrences_in_date_order() {
+        let mut single = occurrence(12, 7, 21);
+        single.id = "/en/events/glow-light-walk".to_string();
+        single.series_id = None;
+        let grouped = group_into_series(vec![occurrence(16, 7, 10), single, occurrence(9, 7, 10)]);
+
+        assert_eq!(grouped.len(), 2);
+        assert_eq!(grouped[0].id, "/en/events/summer-art-club");
+        let ids: Vec<&str> = grouped[0].occurrences.iter().map(|event| event.id.as_str()).collect();
+        assert_eq!(ids, ["/en/events/summer-art-club/9-july", "/en/events/summer-art-club/16-july"]);
+        assert!(!grouped[1].is_recurring());
+        assert_eq!(grouped[1].recurrence(), None);
+    }
+
+    #[test]
+    fn regular_dates_become_a_rule_and_irregular_ones_a_list() {
+        let weekly = group_into_series(vec![occurrence(9, 7, 10), occurrence(16, 7, 10), occurrence(23, 7, 10)]);
+        assert_eq!(weekly[0].recurrence(), Some(Recurrence::Weekly { interval: 1, count: 3 }));
+
+        let daily = group_into_series(vec![occurrence(9, 7, 10), occurrence(11, 7, 10)]);
+        assert_eq!(daily[0].recurrence(), Some(Recurrence::Daily { interval: 2, count: 2 }));
+
+        let irregular = group_into_series(vec![occurrence(9, 7, 10), occurrence(16, 7, 10), occurrence(6, 8, 14)]);
+        assert_eq!(
+            irregular[0].recurrence(),
+            Some(Recurrence::Dates(vec![
+                NaiveDate::from_ymd_opt(2025, 7, 16).unwrap().and_hms_opt(10, 0, 0).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 8, 6).unwrap().and_hms_opt(14, 0, 0).unwrap(),
+            ]))
+        );
+
+        let mut longer = occurrence(16, 7, 10);
+        longer.end_datetime = longer.start_datetime.map(|start| start + Duration::hours(4));
+        assert_eq!(group_into_series(vec![occurrence(9, 7, 10), longer])[0].recurrence(), None);
+    }
+}
@@ -0,0 +1,184 @@
+// File: src-tauri/src/server/api.rs
+// Read-only JSON API over the stored events, plus `POST /refresh` to rescrape the listings.
+// Responses use the same serde models as the Tauri commands.
+use axum::extract::{Path, Query, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::Ordering;
+
+use super::{blocking, flag_set, AppState, EventFilter};
+use crate::error::AppError;
+use crate::models::{Event, Venue};
+use crate::query::{self, SortKey};
+use crate::{build_scraping_client_at, changes, db, fetch_event_details_with_store_at, scraper, venue};
+
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 500;
+
+/// `?page=2&per_page=100&sort=price-asc`; pages start at 1 and events come soonest first.
+#[derive(Debug, Deserialize)]
+pub(super) struct Pagination {
+    page: Option<u32>,
+    per_page: Option<u32>,
+    sort: Option<SortKey>,
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct EventPage {
+    events: Vec<Event>,
+    page: u32,
+    per_page: u32,
+    /// Matching events across all pages.
+    total: usize,
+}
+
+/// `GET /events`: stored events matching the `EventFilter` query, one page at a time.
+pub(super) async fn list_events(
+    State(state): State<AppState>,
+    Query(filter): Query<EventFilter>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<EventPage>, AppError> {
+    let page = pagination.page.unwrap_or(1).max(1);
+    let per_page = pagination.per_page.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let offset = (page as usize - 1).saturating_mul(per_page as usize);
+    let sort = pagination.sort.unwrap_or_default();
+    let result = blocking(move || {
+        let conn = db::open_at(&state.db_path)?;
+        query::query_events(&conn, &filter.to_query(), sort, offset, Some(per_page as usize))
+    })
+    .await?;
+
+    Ok(Json(EventPage {
+        events: result.events,
+        page,
+        per_page,
+        total: result.total,
+    }))
+}
+
+/// `GET /events/{id}`, e.g. `/events/en/events/dutch-design-week-2025`. Fetches the
+/// details first when they're missing or stale; if that fails the stored summary is returned.
+pub(super) async fn get_event(State(state): State<AppState>, Path(id): Path<String>) -> Result<Json<Event>, AppError> {
+    // Event ids are URL paths like `/en/events/...`, but the route already eats the leading slash.
+    let candidate_ids = [format!("/{}", id.trim_start_matches('/')), id];
+    let db_path = state.db_path.clone();
+    let lookup_ids = candidate_ids.clone();
+    let (fresh, stored) = blocking(move || {
+        let conn = db::open_at(&db_path)?;
+        for id in &lookup_ids {
+            if let Some(event) = db::fresh_event_details(&conn, id)? {
+                return Ok((Some(event), None));
+            }
+            if let Some(event) = db::find_event(&conn, id)? {
+                return Ok((None, Some(event)));
+            }
+        }
+        Ok((None, None))
+    })
+    .await?;
+
+    if let Some(event) = fresh {
+        return Ok(Json(event));
+    }
+    let Some(stored) = stored else {
+        return Err(AppError::NotFound {
+            what: format!("Stored event '{}'", candidate_ids[0]),
+        });
+    };
+    if stored.full_url.is_none() {
+        return Ok(Json(stored));
+    }
+
+    let summary = stored.clone();
+    let fetched = tokio::task::spawn_blocking(move || {
+        let client = build_scraping_client_at(&state.db_path, scraper::CrawlPolicy::default())?;
+        fetch_event_details_with_store_at(&state.db_path, &client, summary)
+    });
+    match fetched.await {
+        Ok(Ok(detailed)) => Ok(Json(detailed)),
+        Ok(Err(e)) => {
+            log::warn!("Could not fetch details for {}, returning the stored summary: {}", stored.id, e);
+            Ok(Json(stored))
+        }
+        Err(join_error) => Err(AppError::internal(format!("Task panic (details): {}", join_error))),
+    }
+}
+
+/// `GET /venues`: every venue with its aliases and number of listed events.
+pub(super) async fn list_venues(State(state): State<AppState>) -> Result<Json<Vec<Venue>>, AppError> {
+    let venues = blocking(move || db::list_venues(&db::open_at(&state.db_path)?)).await?;
+    Ok(Json(venues))
+}
+
+/// `GET /venues/{id}/events`: the venue's events that haven't ended yet.
+pub(super) async fn list_venue_events(
+    State(state): State<AppState>,
+    Path(venue_id): Path<i64>,
+) -> Result<Json<Vec<Event>>, AppError> {
+    let events = blocking(move || venue::upcoming_events_at_venue(&db::open_at(&state.db_path)?, venue_id)).await?;
+    Ok(Json(events))
+}
+
+/// `?pages=3` limits a refresh to the first listing pages (removed events are then not detected);
+/// `?dutch=1` also scrapes the Dutch listing.
+#[derive(Debug, Deserialize)]
+pub(super) struct RefreshOptions {
+    pages: Option<u32>,
+    dutch: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct RefreshSummary {
+    event_count: usize,
+    #[serde(flatten)]
+    changes: changes::EventsChanged,
+    /// Listing pages that failed even after retrying; the refresh still stored the rest.
+    failed_pages: Vec<scraper::FailedPage>,
+}
+
+// Clears `AppState::refreshing` however the refresh ends.
+struct RefreshGuard(AppState);
+
+impl Drop for RefreshGuard {
+    fn drop(&mut self) {
+        self.0.refreshing.store(false, Ordering::SeqCst);
+    }
+}
+
+/// `POST /refresh`: scrapes the listings now and reports what changed. Only one runs at a time.
+pub(super) async fn refresh(
+    State(state): State<AppState>,
+    Query(options): Query<RefreshOptions>,
+) -> Result<Json<RefreshSummary>, AppError> {
+    if state.refreshing.swap(true, Ordering::SeqCst) {
+        return Err(AppError::Busy {
+            task: "A refresh".to_string(),
+        });
+    }
+    let guard = RefreshGuard(state);
+    log::info!("REST API refresh started (pages: {:?}, dutch: {:?})", options.pages, options.dutch);
+
+    let report = blocking(move || {
+        // Held by the scrape itself: it keeps running when the client disconnects and this
+        // handler is dropped, and no other refresh may start until it's done.
+        let guard = guard;
+        let db_path = guard.0.db_path.as_path();
+        let client = build_scraping_client_at(db_path, scraper::CrawlPolicy::default())?;
+        let registry = scraper::SourceRegistry::with_default_sources().with_translations(flag_set(options.dutch.as_deref()));
+        scraper::fetch_event_list_summaries_at(
+            db_path,
+            &client,
+            &registry,
+            options.pages,
+            true,
+            &scraper::CancelToken::default(),
+            |progress| log::info!("{}", progress.message),
+        )
+    })
+    .await?;
+    Ok(Json(RefreshSummary {
+        event_count: report.events.len(),
+        changes: changes::EventsChanged::new(report.changes),
+        failed_pages: report.failed_pages,
+    }))
+}
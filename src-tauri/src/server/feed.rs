@@ -0,0 +1,99 @@
+// File: src-tauri/src/server/feed.rs
+use axum::extract::{Query, State};
+use axum::http::header::{CACHE_CONTROL, CONTENT_TYPE, IF_MODIFIED_SINCE, LAST_MODIFIED};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use std::time::Instant;
+
+use super::{blocking, AppState, EventFilter};
+use crate::error::AppError;
+use crate::{db, ics};
+
+const HTTP_DATE_FORMAT: &str = "%a, %d %b %Y %H:%M:%S GMT";
+/// Feeds kept per server. Filters come from the query string, so without a bound any client
+/// could grow the cache without limit.
+pub(super) const MAX_CACHED_FEEDS: usize = 32;
+
+#[derive(Clone)]
+pub(super) struct CachedFeed {
+    last_modified: DateTime<Utc>,
+    last_served: Instant,
+    ics: String,
+}
+
+enum FeedResponse {
+    NotModified(DateTime<Utc>),
+    Calendar(CachedFeed),
+}
+
+fn format_http_date(time: DateTime<Utc>) -> String {
+    time.format(HTTP_DATE_FORMAT).to_string()
+}
+
+fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value.trim())
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+fn load_feed(state: &AppState, query: &EventFilter, if_modified_since: Option<DateTime<Utc>>) -> Result<FeedResponse, AppError> {
+    let conn = db::open_at(&state.db_path)?;
+    let last_modified = db::events_last_modified(&conn)?.unwrap_or(DateTime::UNIX_EPOCH);
+    // HTTP dates only have whole seconds.
+    if matches!(if_modified_since, Some(since) if last_modified.timestamp() <= since.timestamp()) {
+        return Ok(FeedResponse::NotModified(last_modified));
+    }
+
+    let key = query.cache_key();
+    let mut feeds = state.feeds.lock().map_err(|_| AppError::internal("Feed cache lock poisoned"))?;
+    if let Some(cached) = feeds.get_mut(&key).filter(|cached| cached.last_modified == last_modified) {
+        cached.last_served = Instant::now();
+        return Ok(FeedResponse::Calendar(cached.clone()));
+    }
+
+    let events = key.stored_events(&conn)?;
+    let feed = CachedFeed {
+        last_modified,
+        last_served: Instant::now(),
+        ics: ics::feed_calendar(&events)?,
+    };
+    log::info!("Regenerated calendar feed for {:?} with {} events.", key, events.len());
+    // Feeds of older data are never served again; past that, the least recently served goes.
+    feeds.retain(|_, cached| cached.last_modified == last_modified);
+    if feeds.len() >= MAX_CACHED_FEEDS {
+        let least_recent = feeds.iter().min_by_key(|(_, cached)| cached.last_served).map(|(key, _)| key.clone());
+        if let Some(least_recent) = least_recent {
+            feeds.remove(&least_recent);
+        }
+    }
+    feeds.insert(key, feed.clone());
+    Ok(FeedResponse::Calendar(feed))
+}
+
+pub(super) async fn calendar_feed(
+    State(state): State<AppState>,
+    Query(query): Query<EventFilter>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let if_modified_since = headers
+        .get(IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_http_date);
+
+    let response = match blocking(move || load_feed(&state, &query, if_modified_since)).await? {
+        FeedResponse::NotModified(last_modified) => {
+            (StatusCode::NOT_MODIFIED, [(LAST_MODIFIED, format_http_date(last_modified))]).into_response()
+        }
+        FeedResponse::Calendar(feed) => (
+            [
+                (CONTENT_TYPE, HeaderValue::from_static("text/calendar; charset=utf-8")),
+                (CACHE_CONTROL, HeaderValue::from_static("no-cache")),
+            ],
+            [(LAST_MODIFIED, format_http_date(feed.last_modified))],
+            feed.ics,
+        )
+            .into_response(),
+    };
+    Ok(response)
+}
@@ -0,0 +1,163 @@
+// File: src-tauri/src/server/mod.rs
+// Embedded HTTP server so calendar apps can subscribe to the stored events instead of
+// importing exported files one at a time, and dashboards can read them as JSON. Off unless
+// started from the CLI (`serve`) or, in the desktop app, by setting `BIND_ADDRESS_ENV`.
+mod api;
+mod feed;
+#[cfg(test)]
+mod tests;
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+
+use crate::error::AppError;
+use crate::models::Event;
+use crate::query;
+
+/// Set to an address such as `127.0.0.1:8787` to run the server inside the desktop app.
+pub const BIND_ADDRESS_ENV: &str = "EINDHOVEN_EVENTS_SERVER_ADDR";
+pub const DEFAULT_BIND_ADDRESS: &str = "127.0.0.1:8787";
+
+#[derive(Clone)]
+struct AppState {
+    db_path: Arc<PathBuf>,
+    // Generated feeds per normalized filter, reused until the stored events change. At most
+    // `feed::MAX_CACHED_FEEDS` of them.
+    feeds: Arc<Mutex<HashMap<EventFilter, feed::CachedFeed>>>,
+    // Set while a `POST /refresh` scrape runs, so a second one is turned away.
+    refreshing: Arc<AtomicBool>,
+}
+
+/// Filters shared by `/calendar.ics` and `/events`, e.g. `?free=1&venue=Effenaar&from=2025-07-01`.
+#[derive(Debug, Deserialize, Default, Clone, PartialEq, Eq, Hash)]
+struct EventFilter {
+    /// `1`/`true`/`yes` to only include free events.
+    free: Option<String>,
+    /// Case-insensitive substring of the venue name.
+    venue: Option<String>,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    /// Case-insensitive text search in titles, descriptions and venues.
+    q: Option<String>,
+}
+
+/// Whether a query-string flag is set: `1`, `true` or `yes`.
+fn flag_set(value: Option<&str>) -> bool {
+    matches!(value.map(str::to_ascii_lowercase).as_deref(), Some("1" | "true" | "yes"))
+}
+
+impl EventFilter {
+    fn free_only(&self) -> bool {
+        flag_set(self.free.as_deref())
+    }
+
+    /// The filter with its text trimmed and lowercased and `free` reduced to set or not, so
+    /// filters selecting the same events share one cached feed.
+    fn cache_key(&self) -> EventFilter {
+        let normalize = |text: &Option<String>| text.as_deref().map(|text| text.trim().to_lowercase());
+        EventFilter {
+            free: self.free_only().then(|| "1".to_string()),
+            venue: normalize(&self.venue),
+            from: self.from,
+            to: self.to,
+            q: normalize(&self.q),
+        }
+    }
+
+    fn to_query(&self) -> query::EventFilter {
+        query::EventFilter {
+            from: self.from.and_then(|date| date.and_hms_opt(0, 0, 0)),
+            to: self.to.and_then(|date| date.and_hms_opt(23, 59, 59)),
+            free_only: self.free_only(),
+            venue: self.venue.clone(),
+            text: self.q.clone(),
+            ..query::EventFilter::default()
+        }
+    }
+
+    fn stored_events(&self, conn: &Connection) -> Result<Vec<Event>, AppError> {
+        query::query_events(conn, &self.to_query(), query::SortKey::DateAsc, 0, None).map(|page| page.events)
+    }
+}
+
+// Errors go out as the serialized `AppError`, with a status matching its code.
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            AppError::NotFound { .. } => StatusCode::NOT_FOUND,
+            AppError::Busy { .. } => StatusCode::CONFLICT,
+            AppError::InvalidRequest { .. } | AppError::MissingField { .. } | AppError::Timezone { .. } => {
+                StatusCode::BAD_REQUEST
+            }
+            AppError::Network { .. }
+            | AppError::HttpStatus { .. }
+            | AppError::RobotsDisallowed { .. }
+            | AppError::Parse { .. } => StatusCode::BAD_GATEWAY,
+            AppError::Timeout { .. } => StatusCode::GATEWAY_TIMEOUT,
+            AppError::Cache { .. } | AppError::Io { .. } | AppError::Internal { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        if status.is_server_error() {
+            log::error!("HTTP server error: {}", self);
+        }
+        (status, Json(self)).into_response()
+    }
+}
+
+// Runs database or scraping work off the async runtime.
+async fn blocking<T: Send + 'static>(work: impl FnOnce() -> Result<T, AppError> + Send + 'static) -> Result<T, AppError> {
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|join_error| AppError::internal(format!("Task panic (HTTP server): {}", join_error)))?
+}
+
+/// All routes, reading events from the database at `db_path`. Scrapes started through the
+/// API (`POST /refresh`, detail fetches) store into it as well, but leave the app's search
+/// index alone.
+pub fn router(db_path: PathBuf) -> Router {
+    routes(AppState::new(db_path))
+}
+
+impl AppState {
+    fn new(db_path: PathBuf) -> Self {
+        AppState {
+            db_path: Arc::new(db_path),
+            feeds: Arc::new(Mutex::new(HashMap::new())),
+            refreshing: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+fn routes(state: AppState) -> Router {
+    Router::new()
+        .route("/calendar.ics", get(feed::calendar_feed))
+        .route("/events", get(api::list_events))
+        .route("/events/{*id}", get(api::get_event))
+        .route("/venues", get(api::list_venues))
+        .route("/venues/{id}/events", get(api::list_venue_events))
+        .route("/refresh", post(api::refresh))
+        .with_state(state)
+}
+
+/// Serves `router(db_path)` on `bind_address` until the process exits.
+pub async fn serve(bind_address: SocketAddr, db_path: PathBuf) -> Result<(), AppError> {
+    let listener = tokio::net::TcpListener::bind(bind_address)
+        .await
+        .map_err(|e| AppError::Io {
+            context: format!("bind HTTP server to {}", bind_address),
+            message: e.to_string(),
+        })?;
+    log::info!("Serving the event calendar feed at http://{}/calendar.ics and the REST API at http://{}/events", bind_address, bind_address);
+    axum::serve(listener, router(db_path))
+        .await
+        .map_err(|e| AppError::internal(format!("HTTP server error: {}", e)))
+}
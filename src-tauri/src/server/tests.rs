@@ -0,0 +1,228 @@
+// File: src-tauri/src/server/tests.rs
+// Drives the router in-process against a throwaway database; nothing here touches the network.
+use axum::body::{to_bytes, Body};
+use axum::http::header::{CONTENT_TYPE, IF_MODIFIED_SINCE, LAST_MODIFIED};
+use axum::http::{Request, StatusCode};
+use axum::response::Response;
+use chrono::{NaiveDate, Utc};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use tower::ServiceExt;
+
+use super::{feed, router, routes, AppState};
+use crate::db;
+use crate::models::Event;
+
+fn event(slug: &str, day: u32, venue: &str, price: &str) -> Event {
+    Event {
+        id: format!("/en/events/{}", slug),
+        title: slug.replace('-', " "),
+        url_suffix: Some(format!("/en/events/{}", slug)),
+        full_url: Some(format!("https://thisiseindhoven.com/en/events/{}", slug)),
+        start_datetime: NaiveDate::from_ymd_opt(2025, 7, day).and_then(|date| date.and_hms_opt(20, 0, 0)),
+        start_date: NaiveDate::from_ymd_opt(2025, 7, day),
+        end_date: NaiveDate::from_ymd_opt(2025, 7, day),
+        list_specific_location: Some(venue.to_string()),
+        list_price: Some(price.to_string()),
+        ..Event::default()
+    }
+}
+
+// A database holding three listed events, the second one with fetched details.
+fn test_database(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("eindhoven-events-server-{}-{}.db", name, std::process::id()));
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+    }
+    let mut conn = db::open_at(&path).expect("open test database");
+    let summaries = vec![
+        event("jazz-night", 3, "Effenaar", "€ 12,50"),
+        event("open-air-cinema", 10, "Stadswandelpark", "Free"),
+        event("techno-weekender", 17, "Effenaar", "€ 35"),
+    ];
+    db::record_scrape(&mut conn, Utc::now(), 1, &summaries, db::ScrapeCoverage::Complete).expect("record scrape");
+    let detailed = Event {
+        full_description: Some("Films under the stars.".to_string()),
+        ..summaries[1].clone()
+    };
+    db::save_event_details(&mut conn, &[detailed]).expect("save details");
+    path
+}
+
+async fn get(db_path: &Path, uri: &str) -> Response {
+    let request = Request::builder().uri(uri).body(Body::empty()).expect("request");
+    router(db_path.to_path_buf()).oneshot(request).await.expect("response")
+}
+
+async fn json_body(response: Response) -> Value {
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.expect("body");
+    serde_json::from_slice(&bytes).expect("JSON body")
+}
+
+fn ids(page: &Value) -> Vec<&str> {
+    page["events"]
+        .as_array()
+        .expect("events array")
+        .iter()
+        .map(|event| event["id"].as_str().expect("event id"))
+        .collect()
+}
+
+#[tokio::test]
+async fn events_are_paginated_in_start_order() {
+    let db_path = test_database("paginate");
+
+    let response = get(&db_path, "/events?per_page=2&page=2").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let page = json_body(response).await;
+    assert_eq!(page["total"], 3);
+    assert_eq!(page["page"], 2);
+    assert_eq!(page["per_page"], 2);
+    assert_eq!(ids(&page), ["/en/events/techno-weekender"]);
+}
+
+#[tokio::test]
+async fn events_can_be_filtered() {
+    let db_path = test_database("filter");
+
+    let by_venue = json_body(get(&db_path, "/events?venue=effenaar&from=2025-07-10").await).await;
+    assert_eq!(ids(&by_venue), ["/en/events/techno-weekender"]);
+
+    let free = json_body(get(&db_path, "/events?free=1").await).await;
+    assert_eq!(ids(&free), ["/en/events/open-air-cinema"]);
+
+    let search = json_body(get(&db_path, "/events?q=STARS").await).await;
+    assert_eq!(ids(&search), ["/en/events/open-air-cinema"]);
+}
+
+#[tokio::test]
+async fn events_can_be_sorted_by_price() {
+    let db_path = test_database("sort");
+
+    let page = json_body(get(&db_path, "/events?sort=price-desc").await).await;
+    assert_eq!(
+        ids(&page),
+        ["/en/events/techno-weekender", "/en/events/jazz-night", "/en/events/open-air-cinema"]
+    );
+}
+
+#[tokio::test]
+async fn single_event_with_fresh_details_is_served_from_the_database() {
+    let db_path = test_database("detail");
+
+    let response = get(&db_path, "/events/en/events/open-air-cinema").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let event = json_body(response).await;
+    assert_eq!(event["id"], "/en/events/open-air-cinema");
+    assert_eq!(event["full_description"], "Films under the stars.");
+}
+
+#[tokio::test]
+async fn unknown_event_is_a_json_404() {
+    let db_path = test_database("missing");
+
+    let response = get(&db_path, "/events/en/events/does-not-exist").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let error = json_body(response).await;
+    assert_eq!(error["code"], "not_found");
+    assert_eq!(error["message"], "Stored event '/en/events/does-not-exist' not found.");
+}
+
+#[tokio::test]
+async fn venues_list_their_event_counts() {
+    let db_path = test_database("venues");
+
+    let venues = json_body(get(&db_path, "/venues").await).await;
+    let counts: Vec<(&str, u64)> = venues
+        .as_array()
+        .expect("venues array")
+        .iter()
+        .map(|venue| (venue["name"].as_str().expect("name"), venue["event_count"].as_u64().expect("count")))
+        .collect();
+    assert!(counts.contains(&("Effenaar", 2)), "{:?}", counts);
+    assert!(counts.contains(&("Stadswandelpark", 1)), "{:?}", counts);
+}
+
+#[tokio::test]
+async fn venues_are_merged_across_spellings() {
+    let db_path = test_database("venue-aliases");
+    let mut conn = db::open_at(&db_path).expect("open test database");
+    let detailed = Event {
+        specific_location_name: Some("Effenaar Eindhoven".to_string()),
+        street: Some("Dommelstraat 2".to_string()),
+        postal_code: Some("5611 CK".to_string()),
+        ..event("jazz-night", 3, "effenaar", "€ 12,50")
+    };
+    db::save_event_details(&mut conn, &[detailed]).expect("save details");
+    let later = event("piano-evening", 24, "Effenaar Eindhoven", "€ 10");
+    db::save_event_details(&mut conn, &[later]).expect("save details");
+
+    let venues = json_body(get(&db_path, "/venues").await).await;
+    let effenaar: Vec<&Value> = venues
+        .as_array()
+        .expect("venues array")
+        .iter()
+        .filter(|venue| venue["normalized_name"].as_str().is_some_and(|name| name.starts_with("effenaar")))
+        .collect();
+    assert_eq!(effenaar.len(), 1, "{:?}", effenaar);
+    assert_eq!(effenaar[0]["name"], "Effenaar");
+    assert_eq!(effenaar[0]["aliases"], serde_json::json!(["Effenaar Eindhoven"]));
+    assert_eq!(effenaar[0]["street"], "Dommelstraat 2");
+    assert_eq!(effenaar[0]["postal_code"], "5611 CK");
+    assert_eq!(effenaar[0]["event_count"], 3);
+
+    let venue_id = effenaar[0]["id"].as_i64().expect("venue id");
+    let stored = db::find_event(&conn, "/en/events/piano-evening").expect("read event").expect("stored event");
+    assert_eq!(stored.venue_id, Some(venue_id));
+
+    let response = get(&db_path, "/venues/9999/events").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = get(&db_path, &format!("/venues/{}/events", venue_id)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn calendar_feed_honors_if_modified_since() {
+    let db_path = test_database("feed");
+
+    let response = get(&db_path, "/calendar.ics?free=1").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CONTENT_TYPE], "text/calendar; charset=utf-8");
+    let last_modified = response.headers()[LAST_MODIFIED].clone();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.expect("body");
+    let ics = String::from_utf8(bytes.to_vec()).expect("UTF-8 calendar");
+    assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+    assert!(ics.contains("SUMMARY:open air cinema"));
+
+    let request = Request::builder()
+        .uri("/calendar.ics?free=1")
+        .header(IF_MODIFIED_SINCE, last_modified)
+        .body(Body::empty())
+        .expect("request");
+    let response = router(db_path).oneshot(request).await.expect("response");
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+}
+
+#[tokio::test]
+async fn feed_cache_is_keyed_by_normalized_filter_and_bounded() {
+    let db_path = test_database("feed-cache");
+    let state = AppState::new(db_path);
+    let app = routes(state.clone());
+    let feed = |uri: String| {
+        let request = Request::builder().uri(uri).body(Body::empty()).expect("request");
+        app.clone().oneshot(request)
+    };
+
+    for uri in ["/calendar.ics?venue=Effenaar&free=yes", "/calendar.ics?venue=%20effenaar%20&free=1"] {
+        assert_eq!(feed(uri.to_string()).await.expect("response").status(), StatusCode::OK);
+    }
+    assert_eq!(state.feeds.lock().expect("feed cache").len(), 1);
+
+    for n in 0..feed::MAX_CACHED_FEEDS * 2 {
+        let response = feed(format!("/calendar.ics?q=jazz{}", n)).await.expect("response");
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+    let feeds = state.feeds.lock().expect("feed cache");
+    assert_eq!(feeds.len(), feed::MAX_CACHED_FEEDS);
+    assert!(feeds.keys().any(|key| key.q.as_deref() == Some(&format!("jazz{}", feed::MAX_CACHED_FEEDS * 2 - 1))));
+}
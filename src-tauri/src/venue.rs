@@ -0,0 +1,178 @@
+// File: src-tauri/src/venue.rs
+// Turns the location fields repeated on every event into one `Venue` per place. Venues are
+// matched by normalized name, so "Stage Music Café" and "Stage Music Cafe" are one venue.
+use chrono::{Local, NaiveDateTime};
+use rusqlite::Connection;
+
+use crate::db;
+use crate::error::AppError;
+use crate::models::Event;
+use crate::query;
+
+/// Location names the site uses for events that don't happen at one venue.
+const NOT_A_VENUE: &[&str] = &["various locations", "diverse locaties", "verschillende locaties", "online"];
+
+/// Lowercase, accents and punctuation dropped, whitespace collapsed:
+/// "Stage Music Café" and "stage music cafe" both become "stage music cafe".
+pub fn normalize_name(name: &str) -> String {
+    let folded: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+            'è' | 'é' | 'ê' | 'ë' => 'e',
+            'ì' | 'í' | 'î' | 'ï' => 'i',
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' => 'o',
+            'ù' | 'ú' | 'û' | 'ü' => 'u',
+            'ç' => 'c',
+            'ñ' => 'n',
+            c if c.is_alphanumeric() => c,
+            _ => ' ',
+        })
+        .collect();
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// What one event says about its venue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VenueDetails {
+    /// The detail page's location name, or the listing's when details weren't fetched.
+    pub name: String,
+    /// The other of the two names, when it's spelled differently.
+    pub aliases: Vec<String>,
+    pub street: Option<String>,
+    pub postal_code: Option<String>,
+    pub locality: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+impl VenueDetails {
+    /// `None` when the event names no venue, or only a placeholder like "Various locations".
+    pub fn from_event(event: &Event) -> Option<VenueDetails> {
+        let mut names = [event.specific_location_name.as_deref(), event.list_specific_location.as_deref()]
+            .into_iter()
+            .flatten()
+            .map(str::trim)
+            .filter(|name| !name.is_empty() && !NOT_A_VENUE.contains(&normalize_name(name).as_str()));
+        let name = names.next()?.to_string();
+        let aliases = names
+            .filter(|alias| normalize_name(alias) != normalize_name(&name))
+            .map(str::to_string)
+            .collect();
+
+        Some(VenueDetails {
+            name,
+            aliases,
+            street: event.street.clone(),
+            postal_code: event.postal_code.clone(),
+            locality: event.locality.clone(),
+            latitude: event.latitude,
+            longitude: event.longitude,
+        })
+    }
+
+    /// Every name the venue went by on the event.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.name.as_str()).chain(self.aliases.iter().map(String::as_str))
+    }
+}
+
+/// Whether the event hasn't ended before `now`. Events without any date are kept, since
+/// nothing says they're over.
+fn is_upcoming(event: &Event, now: NaiveDateTime) -> bool {
+    let today = now.date().and_hms_opt(0, 0, 0).unwrap_or(now);
+    match event.ends_at().or_else(|| query::event_date(event)) {
+        Some(last_moment) => last_moment >= today,
+        None => true,
+    }
+}
+
+/// The listed events at venue `venue_id` that haven't ended yet, soonest first.
+pub fn upcoming_events_at_venue(conn: &Connection, venue_id: i64) -> Result<Vec<Event>, AppError> {
+    if db::find_venue(conn, venue_id)?.is_none() {
+        return Err(AppError::NotFound {
+            what: format!("Venue {}", venue_id),
+        });
+    }
+    let now = Local::now().naive_local();
+    Ok(db::venue_events(conn, venue_id)?
+        .into_iter()
+        .filter(|event| is_upcoming(event, now))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_spelling_variants() {
+        assert_eq!(normalize_name("Stage Music Café"), "stage music cafe");
+        assert_eq!(normalize_name("  STAGE music cafe "), "stage music cafe");
+        assert_eq!(normalize_name("Muziekgebouw Eindhoven (Grote Zaal)"), "muziekgebouw eindhoven grote zaal");
+        assert_eq!(normalize_name("Strijp-S"), "strijp s");
+    }
+
+    #[test]
+    fn reads_venue_from_detailed_event() {
+        let event = Event {
+            list_specific_location: Some("Stage Music Cafe".to_string()),
+            specific_location_name: Some("Stage Music Café".to_string()),
+            street: Some("Stratumseind 23".to_string()),
+            postal_code: Some("5611 ET".to_string()),
+            locality: Some("Eindhoven".to_string()),
+            latitude: Some(51.4379),
+            longitude: Some(5.4826),
+            ..Event::default()
+        };
+        assert_eq!(
+            VenueDetails::from_event(&event),
+            Some(VenueDetails {
+                name: "Stage Music Café".to_string(),
+                aliases: vec![],
+                street: Some("Stratumseind 23".to_string()),
+                postal_code: Some("5611 ET".to_string()),
+                locality: Some("Eindhoven".to_string()),
+                latitude: Some(51.4379),
+                longitude: Some(5.4826),
+            })
+        );
+    }
+
+    #[test]
+    fn keeps_differently_named_listing_location_as_alias() {
+        let event = Event {
+            list_specific_location: Some("Muziekgebouw".to_string()),
+            specific_location_name: Some("Muziekgebouw Eindhoven".to_string()),
+            ..Event::default()
+        };
+        let details = VenueDetails::from_event(&event).unwrap();
+        assert_eq!(details.name, "Muziekgebouw Eindhoven");
+        assert_eq!(details.aliases, ["Muziekgebouw"]);
+    }
+
+    #[test]
+    fn placeholders_are_not_venues() {
+        let event = Event {
+            list_specific_location: Some("Various locations".to_string()),
+            specific_location_name: Some("Various locations".to_string()),
+            ..Event::default()
+        };
+        assert_eq!(VenueDetails::from_event(&event), None);
+        assert_eq!(VenueDetails::from_event(&Event::default()), None);
+    }
+
+    #[test]
+    fn upcoming_events_include_today_and_undated() {
+        let now = NaiveDateTime::parse_from_str("2025-07-09 20:00", "%Y-%m-%d %H:%M").unwrap();
+        let listed_on = |date: &str| Event {
+            list_date: Some(date.to_string()),
+            ..Event::default()
+        };
+        assert!(is_upcoming(&listed_on("09 Jul 2025"), now));
+        assert!(is_upcoming(&listed_on("10 Jul 2025"), now));
+        assert!(!is_upcoming(&listed_on("08 Jul 2025"), now));
+        assert!(is_upcoming(&Event::default(), now));
+    }
+}
@@ -1,5 +1,5 @@
 // File: src/App.tsx
-import React, { useState, useEffect, useCallback, useRef, useMemo } from "react";
+import { useState, useEffect, useCallback, useRef, useMemo } from "react";
 import { LatLngExpression } from "leaflet";
 import "./App.css";
 import EventDetailOverlay from "./components/EventDetailOverlay";
@@ -21,7 +21,8 @@ import FilterOverlay from "./components/FilterOverlay";
 import ScrapingOverlay from "./components/ScrapingOverlay";
 
 // Types
-import { EventData } from "./types";
+import { EventData, EventPage, EventQueryFilter, FetchedSummaries, MapBounds, SearchHit, SortKey, isAppError } from "./types";
+import { lowestPrice } from "./utils/priceUtils";
 
 const EindhovenCentraalStation: LatLngExpression = [51.4416, 5.4697];
 type Theme = "light" | "dark";
@@ -33,6 +34,7 @@ interface ScrapingProgress {
   events_on_current_page: number;
   total_events_scraped: number;
   message: string;
+  cancelled: boolean; // Set on the last message of a cancelled scrape
 }
 
 function App() {
@@ -62,7 +64,13 @@ function App() {
   );
   const [isFetchingAllDetails, setIsFetchingAllDetails] = useState(false);
   const [filterFreeEvents, setFilterFreeEvents] = useState<boolean>(false);
-  const [sortBy, setSortBy] = useState<string>("date-asc"); // Default sort by date ascending
+  const [sortBy, setSortBy] = useState<SortKey>("date-asc"); // Default sort by date ascending
+  const [queriedEvents, setQueriedEvents] = useState<EventData[]>([]);
+  const [queryError, setQueryError] = useState<string | null>(null);
+  const [searchQuery, setSearchQuery] = useState("");
+  const [searchHits, setSearchHits] = useState<SearchHit[] | null>(null); // null while not searching
+  const [mapBounds, setMapBounds] = useState<MapBounds | null>(null);
+  const [visibleMapEvents, setVisibleMapEvents] = useState<EventData[] | null>(null);
   const [pageLimit, setPageLimit] = useState<number | undefined>(undefined); // New state for page limit
   const [forceRefresh, setForceRefresh] = useState<boolean>(false); // New state to force refresh
   const [isScraping, setIsScraping] = useState<boolean>(false); // New state for scraping status
@@ -73,35 +81,22 @@ function App() {
   const [maxPriceFilter, setMaxPriceFilter] = useState<number>(0);
   const [showScrapingOverlay, setShowScrapingOverlay] = useState(false);
   const scrapingButtonRef = useRef<HTMLButtonElement>(null);
+  const scrapeJobIds = useRef<Set<string>>(new Set()); // Running scrapes `cancel_scrape` can stop
 
-  const minAvailablePrice = useMemo(() => {
-    if (events.length === 0) return 0;
-    const prices = events.map(event => {
-      const priceString = event.price || event.list_price;
-      if (priceString) {
-        if (priceString.toLowerCase().includes("free") || priceString.trim() === "€ 0,00") {
-          return 0;
-        }
-        const price = parseFloat(priceString.replace("€", "").replace(",", ".").trim());
-        return isNaN(price) ? Infinity : price;
-      }
-      return Infinity;
-    }).filter(price => price !== Infinity);
-    return prices.length > 0 ? Math.floor(Math.min(...prices)) : 0;
-  }, [events]);
-
-  const maxAvailablePrice = useMemo(() => {
-    if (events.length === 0) return 0;
-    const prices = events.map(event => {
-      const priceString = event.price || event.list_price;
-      if (priceString) {
-        const price = parseFloat(priceString.replace("€", "").replace(",", ".").trim());
-        return isNaN(price) ? 0 : price;
-      }
-      return 0;
-    }).filter(price => price !== 0);
-    return prices.length > 0 ? Math.ceil(Math.max(...prices)) : 0;
-  }, [events]);
+  const knownPrices = useMemo(
+    () => events.map(lowestPrice).filter((price): price is number => price !== undefined),
+    [events]
+  );
+
+  const minAvailablePrice = useMemo(
+    () => (knownPrices.length > 0 ? Math.floor(Math.min(...knownPrices)) : 0),
+    [knownPrices]
+  );
+
+  const maxAvailablePrice = useMemo(
+    () => (knownPrices.length > 0 ? Math.ceil(Math.max(...knownPrices)) : 0),
+    [knownPrices]
+  );
 
   useEffect(() => {
     setMinPriceFilter(minAvailablePrice);
@@ -134,21 +129,34 @@ function App() {
         setScrapingProgress(event.payload);
       });
 
+      const jobId = crypto.randomUUID();
+      scrapeJobIds.current.add(jobId);
       try {
-        const fetchedSummaries = await invoke<EventData[]>("fetch_events_rust", { pageLimit: pageLimit, forceRefresh: forceRefresh });
-        setEvents(
-          fetchedSummaries.map((event) => ({ ...event, isDetailed: false }))
-        );
+        const fetched = await invoke<FetchedSummaries>("fetch_events_rust", { pageLimit: pageLimit, forceRefresh: forceRefresh, jobId });
+        // A scrape cancelled before its first page has nothing to replace the current events with.
+        if (!fetched.cancelled || fetched.events.length > 0) {
+          setEvents(
+            fetched.events.map((event) => ({ ...event, isDetailed: false }))
+          );
+        }
+        if (fetched.failed_pages.length > 0) {
+          console.warn(`Could not scrape ${fetched.failed_pages.length} listing pages:`, fetched.failed_pages);
+        }
       } catch (e: any) {
-        setError(
-          `Failed to fetch event summaries: ${e.message || e.toString()}`
-        );
+        if (isAppError(e) && (e.code === "network" || e.code === "timeout")) {
+          setError(`Could not reach the event website. Check your connection and try again. (${e.message})`);
+        } else {
+          setError(
+            `Failed to fetch event summaries: ${e.message || e.toString()}`
+          );
+        }
         console.error("Fetch summaries error:", e);
       } finally {
         setLoading(false);
         setForceRefresh(false); // Reset forceRefresh after fetch
         setIsScraping(false); // End scraping animation
         setScrapingProgress(null); // Clear progress after completion/error
+        scrapeJobIds.current.delete(jobId);
         unlisten(); // Unlisten from the event
       }
     };
@@ -232,8 +240,8 @@ function App() {
         }
       }
 
-      if (!eventForIcs.start_datetime) {
-        alert("Precise start time not available for this event.");
+      if (!eventForIcs.start_datetime && !eventForIcs.start_date) {
+        alert("Start date not available for this event.");
         return;
       }
 
@@ -254,7 +262,11 @@ function App() {
           alert(`Event "${eventForIcs.title}" saved to ${filePath}`);
         }
       } catch (e: any) {
-        alert(`Error creating calendar file: ${e.message || e.toString()}`);
+        if (isAppError(e) && e.code === "timezone") {
+          alert(`The start time of "${eventForIcs.title}" doesn't exist in Eindhoven (it falls in the hour skipped when summer time starts).`);
+        } else {
+          alert(`Error creating calendar file: ${e.message || e.toString()}`);
+        }
         console.error("ICS Error:", e);
       }
     },
@@ -283,14 +295,24 @@ function App() {
         return;
     }
     const updatedEventsData = new Map<string, EventData>();
-    for (const eventSummary of eventsToFetchDetailsFor) {
-        try {
-            const detailedEvent = await invoke<EventData>("fetch_specific_event_details_rust", { eventSummary });
-            updatedEventsData.set(detailedEvent.id, { ...detailedEvent, isDetailed: true });
-        } catch (err) {
-            console.error(`Failed to fetch details for ${eventSummary.id}:`, err);
-            updatedEventsData.set(eventSummary.id, eventSummary);
+    const jobId = crypto.randomUUID();
+    scrapeJobIds.current.add(jobId);
+    try {
+        const batch = await invoke<{ events: EventData[]; failed_ids: string[]; cancelled_ids: string[] }>(
+          "fetch_event_details_batch_rust",
+          { eventSummaries: eventsToFetchDetailsFor, jobId }
+        );
+        const notDetailedIds = new Set([...batch.failed_ids, ...batch.cancelled_ids]);
+        for (const event of batch.events) {
+            updatedEventsData.set(event.id, { ...event, isDetailed: !notDetailedIds.has(event.id) });
+        }
+        if (batch.failed_ids.length > 0) {
+            console.error(`Failed to fetch details for ${batch.failed_ids.length} events:`, batch.failed_ids);
         }
+    } catch (err) {
+        console.error("Failed to fetch event details batch:", err);
+    } finally {
+        scrapeJobIds.current.delete(jobId);
     }
     setEvents(prevEvents => {
         const newEventsArray = prevEvents.map(event => 
@@ -305,69 +327,94 @@ function App() {
     setIsFetchingAllDetails(false);
   }, [events, overlayEvent]);
 
-  const mapEvents = events.filter((e) => e.latitude && e.longitude);
-
-  const filteredAndSortedEvents = React.useMemo(() => {
-    let currentEvents = [...events];
+  const handleCancelScrape = useCallback(() => {
+    for (const jobId of scrapeJobIds.current) {
+      // A job that finished in the meantime is no longer known; nothing to cancel then.
+      invoke("cancel_scrape", { jobId }).catch((err) => console.warn("Cancel scrape:", err));
+    }
+  }, []);
 
-    // 1. Filtering by free events (if enabled, overrides price range)
-    if (filterFreeEvents) {
-      currentEvents = currentEvents.filter(event => {
-        const priceString = event.price || event.list_price;
-        return priceString && (priceString.toLowerCase().includes("free") || priceString.trim() === "€ 0,00");
+  // The map only asks for the events inside its visible area.
+  useEffect(() => {
+    if (currentView !== "map" || !mapBounds) return;
+    let cancelled = false;
+    invoke<EventData[]>("get_events_in_bounds", { bounds: mapBounds })
+      .then(visible => {
+        if (!cancelled) setVisibleMapEvents(visible);
+      })
+      .catch(err => {
+        console.error("Failed to load events for the map area:", err);
+        if (!cancelled) setVisibleMapEvents(null);
       });
-    } else { // Apply price range filter only if not filtering for free events
-      currentEvents = currentEvents.filter(event => {
-        const priceString = event.price || event.list_price;
-        if (!priceString) return false; // Events without price are excluded from range filter
+    return () => {
+      cancelled = true;
+    };
+  }, [currentView, mapBounds, events]);
 
-        const price = parseFloat(priceString.replace("€", "").replace(",", ".").trim());
-        if (isNaN(price)) return false; // Invalid price string
+  const mapEvents = visibleMapEvents ?? events.filter((e) => e.latitude && e.longitude);
 
-        return price >= minPriceFilter && price <= maxPriceFilter;
+  // Filtering and sorting run in Rust against the stored events, so they match the CLI and REST API.
+  useEffect(() => {
+    // Free events override the price range
+    const filter: EventQueryFilter = filterFreeEvents
+      ? { free_only: true }
+      : { min_price: minPriceFilter, max_price: maxPriceFilter };
+    let cancelled = false;
+    invoke<EventPage>("query_events", { filter, sort: sortBy })
+      .then(page => {
+        if (cancelled) return;
+        setQueriedEvents(page.events);
+        setQueryError(null);
+      })
+      .catch(err => {
+        console.error("Failed to query stored events:", err);
+        if (cancelled) return;
+        // Showing the unfiltered events instead would pass them off as the filtered ones.
+        setQueriedEvents([]);
+        setQueryError(`Could not filter the events: ${err.message || err.toString()}`);
       });
-    }
+    return () => {
+      cancelled = true;
+    };
+  }, [events, filterFreeEvents, minPriceFilter, maxPriceFilter, sortBy]);
 
-    // 3. Sorting
-    currentEvents.sort((a, b) => {
-      switch (sortBy) {
-        case "date-asc":
-          {
-            const dateA = a.start_datetime || a.list_date;
-            const dateB = b.start_datetime || b.list_date;
-            if (!dateA && !dateB) return 0;
-            if (!dateA) return 1;
-            if (!dateB) return -1;
-            return new Date(dateA).getTime() - new Date(dateB).getTime();
-          }
-        case "date-desc":
-          {
-            const dateA = a.start_datetime || a.list_date;
-            const dateB = b.start_datetime || b.list_date;
-            if (!dateA && !dateB) return 0;
-            if (!dateA) return 1;
-            if (!dateB) return -1;
-            return new Date(dateB).getTime() - new Date(dateA).getTime();
-          }
-        case "price-asc":
-          {
-            const priceA = parseFloat((a.price || a.list_price || "0").replace("€", "").replace(",", ".").trim());
-            const priceB = parseFloat((b.price || b.list_price || "0").replace("€", "").replace(",", ".").trim());
-            return priceA - priceB;
-          }
-        case "price-desc":
-          {
-            const priceA = parseFloat((a.price || a.list_price || "0").replace("€", "").replace(",", ".").trim());
-            const priceB = parseFloat((b.price || b.list_price || "0").replace("€", "").replace(",", ".").trim());
-            return priceB - priceA;
-          }
-        default:
-          return 0;
-      }
-    });
+  // The query only knows the stored data; the in-memory events also carry the details
+  // fetched since and whether they were.
+  const filteredAndSortedEvents = useMemo(() => {
+    const eventsById = new Map(events.map(event => [event.id, event]));
+    return queriedEvents.map(event => eventsById.get(event.id) ?? { ...event, isDetailed: false });
+  }, [events, queriedEvents]);
 
-    return currentEvents;
-  }, [events, filterFreeEvents, minPriceFilter, maxPriceFilter, sortBy]);
+  // While there's a search query the list shows the ranked search results instead.
+  useEffect(() => {
+    const query = searchQuery.trim();
+    if (!query) {
+      setSearchHits(null);
+      return;
+    }
+    let cancelled = false;
+    const timeout = setTimeout(() => {
+      invoke<SearchHit[]>("search_events", { query })
+        .then(hits => {
+          if (!cancelled) setSearchHits(hits);
+        })
+        .catch(err => console.error("Search failed:", err));
+    }, 250);
+    return () => {
+      cancelled = true;
+      clearTimeout(timeout);
+    };
+  }, [searchQuery, events]);
+
+  const displayedEvents = searchHits ? searchHits.map(hit => hit.event) : filteredAndSortedEvents;
+  const searchSnippets = useMemo(() => {
+    if (!searchHits) return undefined;
+    const snippets: Record<string, string> = {};
+    for (const hit of searchHits) {
+      if (hit.snippet_html) snippets[hit.event.id] = hit.snippet_html;
+    }
+    return snippets;
+  }, [searchHits]);
 
   return (
     <div className="flex flex-col h-screen bg-gray-100 dark:bg-black text-gray-900 dark:text-gray-100 antialiased">
@@ -401,11 +448,20 @@ function App() {
                 <RefreshCwIcon className="w-4 h-4" />
             </button>
 
+            {/* Search */}
+            <input
+                type="search"
+                value={searchQuery}
+                onChange={(e) => setSearchQuery(e.target.value)}
+                placeholder="Search events…"
+                className="px-2 py-1 w-32 sm:w-48 rounded-md bg-gray-200 dark:bg-neutral-800 text-gray-700 dark:text-gray-300 text-xs sm:text-sm focus:outline-none focus:ring-2 focus:ring-blue-500 dark:focus:ring-blue-400"
+            />
+
             {/* Sort By */}
             <select
                 id="sort-by"
                 value={sortBy}
-                onChange={(e) => setSortBy(e.target.value)}
+                onChange={(e) => setSortBy(e.target.value as SortKey)}
                 className="px-2 py-1 rounded-md bg-gray-200 dark:bg-neutral-800 text-gray-700 dark:text-gray-300 text-xs sm:text-sm focus:outline-none focus:ring-2 focus:ring-blue-500 dark:focus:ring-blue-400"
             >
                 <option value="date-asc">Date (Asc)</option>
@@ -484,9 +540,15 @@ function App() {
 
         {(!loading || events.length > 0) && !error && (
           <>
+            {queryError && !searchHits && currentView !== "map" && (
+              <p className="p-4 text-center text-red-500 dark:text-red-400 text-base">
+                Error: {queryError}
+              </p>
+            )}
             {currentView === "list" && (
               <EventList
-                events={filteredAndSortedEvents}
+                events={displayedEvents}
+                snippets={searchSnippets}
                 onSelectEvent={handleSelectEvent}
                 loadingDetailsFor={loadingDetailsFor}
                 eventInOverlayId={overlayEvent?.id ?? null}
@@ -500,12 +562,13 @@ function App() {
                   mapZoom={mapZoom}
                   onMarkerClick={handleSelectEvent}
                   theme={theme}
+                  onBoundsChange={setMapBounds}
                 />
               </div>
             )}
             {currentView === "calendar" && (
               <EventCalendar
-                events={filteredAndSortedEvents}
+                events={displayedEvents}
                 onSelectEvent={handleSelectEvent}
                 loadingDetailsFor={loadingDetailsFor}
                 eventInOverlayId={overlayEvent?.id ?? null}
@@ -547,6 +610,8 @@ function App() {
         onPageLimitChange={setPageLimit}
         onForceRefresh={() => setForceRefresh(true)}
         isLoading={loading}
+        isScraping={isScraping || isFetchingAllDetails}
+        onCancelScrape={handleCancelScrape}
         theme={theme}
       />
     </div>
@@ -30,7 +30,7 @@ const EventActionsFooter: React.FC<EventActionsFooterProps> = ({ currentEvent, h
 
         <button 
           onClick={() => handleAddToCalendar(currentEvent)}
-          disabled={!currentEvent.start_datetime}
+          disabled={!currentEvent.start_datetime && !currentEvent.start_date}
           className={`w-full py-2.5 px-4 rounded-lg text-sm font-semibold transition-colors focus:outline-none focus-visible:ring-2 focus-visible:ring-offset-2 dark:focus-visible:ring-offset-neutral-900 disabled:opacity-60 disabled:cursor-not-allowed // Updated ring offset
                       ${!hasTicketUrl 
                         ? 'bg-indigo-600 hover:bg-indigo-700 text-white focus-visible:ring-indigo-500' 
@@ -8,9 +8,10 @@ interface EventListProps {
   onSelectEvent: (event: EventData) => void;
   loadingDetailsFor: string | null;
   eventInOverlayId?: string | null; 
+  snippets?: Record<string, string>; // Highlighted search snippets (HTML) by event id
 }
 
-const EventList: React.FC<EventListProps> = ({ events, onSelectEvent, loadingDetailsFor, eventInOverlayId }) => {
+const EventList: React.FC<EventListProps> = ({ events, onSelectEvent, loadingDetailsFor, eventInOverlayId, snippets }) => {
   const isLoadingSummaries = events.length === 0 && loadingDetailsFor === null; // Simplified check
   
   return (
@@ -31,6 +32,7 @@ const EventList: React.FC<EventListProps> = ({ events, onSelectEvent, loadingDet
             onSelectEvent={onSelectEvent}
             isSelectedInGrid={eventInOverlayId === event.id}
             isLoadingDetails={loadingDetailsFor === event.id}
+            snippetHtml={snippets?.[event.id]}
           />
         ))}
       </div>
@@ -15,6 +15,7 @@ interface EventListItemProps {
   eventColor?: { light: string; dark: string };
   hideDateInCalendar?: boolean; // New prop
   isCalendarView?: boolean; // New prop for calendar specific rendering
+  snippetHtml?: string; // Search snippet with matches in <b>, shown instead of the short description
 }
 
 const formatDate = (dateTimeStr?: string): string => {
@@ -83,6 +84,7 @@ const EventListItem: React.FC<EventListItemProps> = ({
   eventColor,
   hideDateInCalendar,
   isCalendarView,
+  snippetHtml,
 }) => {
   if (isContinuation) {
     const bgColorClass = eventColor ? `${eventColor.light} ${eventColor.dark}` : "bg-blue-500 dark:bg-blue-700";
@@ -260,7 +262,12 @@ const EventListItem: React.FC<EventListItemProps> = ({
               </div>
             </div>
 
-            {event.short_description &&
+            {snippetHtml ? (
+              <p
+                className="text-xs text-gray-500 dark:text-gray-400 mt-2 leading-relaxed line-clamp-2 [&_b]:font-semibold [&_b]:text-gray-800 dark:[&_b]:text-gray-200"
+                dangerouslySetInnerHTML={{ __html: snippetHtml }}
+              />
+            ) : event.short_description &&
               (!isSelectedInGrid || !event.isDetailed) && (
                 <p className="text-xs text-gray-500 dark:text-gray-400 mt-2 leading-relaxed line-clamp-2">
                   {event.short_description}
@@ -1,8 +1,8 @@
 import React from 'react';
 import ReactDOMServer from 'react-dom/server';
-import { MapContainer, TileLayer, Marker, useMap } from 'react-leaflet';
-import L, { LatLngExpression, DivIcon } from 'leaflet';
-import { EventData } from '../types';
+import { MapContainer, TileLayer, Marker, useMap, useMapEvents } from 'react-leaflet';
+import L, { LatLngBounds, LatLngExpression, DivIcon } from 'leaflet';
+import { EventData, MapBounds } from '../types';
 import CustomEventMapMarker from './CustomEventMapMarker';
 
 interface EventMapProps {
@@ -11,6 +11,7 @@ interface EventMapProps {
   mapZoom: number;
   onMarkerClick: (event: EventData) => void;
   theme: 'light' | 'dark';
+  onBoundsChange?: (bounds: MapBounds) => void; // Called with the visible area after every pan or zoom
 }
 
 function ChangeView({ center, zoom }: { center: LatLngExpression; zoom: number }) {
@@ -21,7 +22,25 @@ function ChangeView({ center, zoom }: { center: LatLngExpression; zoom: number }
   return null;
 }
 
-const EventMap: React.FC<EventMapProps> = ({ events, mapCenter, mapZoom, onMarkerClick, theme }) => {
+// Leaflet reports longitudes past ±180 once the world wraps; the backend wants real coordinates.
+const toMapBounds = (bounds: LatLngBounds): MapBounds => ({
+  south: Math.max(-90, bounds.getSouth()),
+  west: Math.max(-180, bounds.getWest()),
+  north: Math.min(90, bounds.getNorth()),
+  east: Math.min(180, bounds.getEast()),
+});
+
+function BoundsReporter({ onBoundsChange }: { onBoundsChange: (bounds: MapBounds) => void }) {
+  const map = useMapEvents({
+    moveend: () => onBoundsChange(toMapBounds(map.getBounds())),
+  });
+  React.useEffect(() => {
+    onBoundsChange(toMapBounds(map.getBounds()));
+  }, [map, onBoundsChange]);
+  return null;
+}
+
+const EventMap: React.FC<EventMapProps> = ({ events, mapCenter, mapZoom, onMarkerClick, theme, onBoundsChange }) => {
   
   let tileUrl: string;
   let tileAttribution: string;
@@ -70,6 +89,7 @@ const EventMap: React.FC<EventMapProps> = ({ events, mapCenter, mapZoom, onMarke
         scrollWheelZoom={true}
       >
         <ChangeView center={mapCenter} zoom={mapZoom} />
+        {onBoundsChange && <BoundsReporter onBoundsChange={onBoundsChange} />}
         <TileLayer
           url={tileUrl}
           attribution={tileAttribution}
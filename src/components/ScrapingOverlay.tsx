@@ -9,6 +9,8 @@ interface ScrapingOverlayProps {
   onPageLimitChange: (limit: number | undefined) => void;
   onForceRefresh: () => void;
   isLoading: boolean;
+  isScraping: boolean;
+  onCancelScrape: () => void;
   theme: 'light' | 'dark';
 }
 
@@ -20,6 +22,8 @@ const ScrapingOverlay: React.FC<ScrapingOverlayProps> = ({
   onPageLimitChange,
   onForceRefresh,
   isLoading,
+  isScraping,
+  onCancelScrape,
   theme,
 }) => {
   const overlayRef = useRef<HTMLDivElement>(null);
@@ -102,6 +106,15 @@ const ScrapingOverlay: React.FC<ScrapingOverlayProps> = ({
         {isLoading ? <RefreshCwIcon className="w-4 h-4 animate-spin inline-block mr-2" /> : null}
         Refresh Events
       </button>
+
+      {isScraping && (
+        <button
+          onClick={onCancelScrape}
+          className="w-full mt-2 px-3 py-2 rounded-md font-medium transition-all duration-200 text-sm focus:outline-none focus-visible:ring-2 focus-visible:ring-red-500 focus-visible:ring-offset-2 dark:focus-visible:ring-offset-neutral-800 bg-gray-200 hover:bg-gray-300 dark:bg-neutral-700 dark:hover:bg-neutral-600 text-gray-800 dark:text-gray-100"
+        >
+          Cancel Scraping
+        </button>
+      )}
     </div>
   );
 };
@@ -4,12 +4,18 @@ export interface EventData {
   title: string;
   url_suffix?: string;
   full_url?: string;
+  series_id?: string; // Shared by the dates of a recurring event, e.g. "/en/events/summer-art-club-creative-with-the-garden"
+  language?: string; // "en", or "nl" for events only on the Dutch listing
+  translations?: Record<string, EventTranslation>; // Keyed by language code, e.g. "nl"
   date_time_summary?: string; // From list view, e.g. "10 June"
   list_date?: string;         // More specific date from list meta if available (e.g. "10 April 2025")
   
   // Detailed fields, populated after fetching details
   start_datetime?: string; // ISO 8601 string or similar from NaiveDateTime
   end_datetime?: string;   // ISO 8601 string or similar from NaiveDateTime
+  start_date?: string;     // "YYYY-MM-DD", first day the event runs
+  end_date?: string;       // "YYYY-MM-DD", last day the event runs (inclusive)
+  all_day?: boolean;       // No start time or spans several days; only the dates apply
   datetime_str_raw_detail?: string; // Raw string from detail page, e.g. "Tuesday 28 May, 10:00 - 17:00"
   
   short_description?: string;
@@ -19,14 +25,166 @@ export interface EventData {
   
   list_specific_location?: string;
   specific_location_name?: string; 
-  address?: string; // Detailed address with postal code
+  address?: string; // Detailed address with postal code, derived from the parts below
+  street?: string;
+  postal_code?: string; // Dutch postcode, e.g. "5611 NB"
+  locality?: string;
   
   latitude?: number;
   longitude?: number;
+  venue_id?: number; // See `Venue`; missing for events without a venue
   
   list_price?: string;
   price?: string;
+  parsed_price?: Price; // `price` (or `list_price`) parsed by the backend
   ticket_url?: string;
+  performers?: string[];
+  event_status?: EventStatus;
+  field_sources?: Record<string, FieldSource>; // Where each detail field was read from
 
   isDetailed?: boolean; // New flag
-}
\ No newline at end of file
+}
+
+// The event as published in another language.
+export interface EventTranslation {
+  title: string;
+  short_description?: string;
+  full_description?: string;
+  full_url?: string;
+}
+
+// schema.org `eventStatus`, see src-tauri/src/models.rs.
+export type EventStatus = "scheduled" | "cancelled" | "postponed" | "rescheduled" | "moved_online";
+
+export type FieldSource = "json_ld" | "microdata" | "html";
+
+// Mirrors `Price` in src-tauri/src/price.rs. Amounts are in `currency` units (euros).
+export type PriceKind =
+  | { kind: "free" }
+  | { kind: "amount"; amount: number }
+  | { kind: "range"; min: number; max: number }
+  | { kind: "from"; amount: number }
+  | { kind: "donation"; suggested: number | null }
+  | { kind: "unknown" };
+
+export interface PriceTier {
+  audience: "student" | "child" | "senior";
+  amount: number; // 0 when the group gets in free
+}
+
+export type Price = PriceKind & {
+  currency: string | null; // ISO 4217, e.g. "EUR"
+  tiers?: PriceTier[];
+};
+
+// Arguments and result of the `query_events` command, see src-tauri/src/query.rs.
+export interface EventQueryFilter {
+  from?: string; // "YYYY-MM-DDTHH:MM:SS"
+  to?: string;
+  min_price?: number; // Bounds on the cheapest ticket; events without a known price are left out
+  max_price?: number;
+  free_only?: boolean;
+  venue?: string;
+  text?: string;
+  has_coordinates?: boolean;
+  has_tickets?: boolean;
+}
+
+export type SortKey = "date-asc" | "date-desc" | "price-asc" | "price-desc" | "title";
+
+export interface EventPage {
+  events: EventData[];
+  total: number; // Matching events across all pages
+  offset: number;
+}
+
+// Result of the `search_events` command, best match first. The HTML has matched words in <b> tags.
+export interface SearchHit {
+  event: EventData;
+  score: number;
+  title_html: string | null;   // Only when the title matched
+  snippet_html: string | null; // Passage of the description, only when it matched
+}
+
+// Visible map area in degrees, for `get_events_in_bounds`.
+export interface MapBounds {
+  south: number;
+  west: number;
+  north: number;
+  east: number;
+}
+
+// A listing page `fetch_events_rust` couldn't scrape, even after retrying.
+export interface FailedPage {
+  source: string;
+  language?: string; // Set for a page of a translated listing, e.g. "nl"
+  page: number;
+  url: string;
+  error: AppError;
+}
+
+// Result of `fetch_events_rust`: what was scraped, plus the pages that failed.
+export interface FetchedSummaries {
+  events: EventData[];
+  failed_pages: FailedPage[];
+  cancelled: boolean; // Stopped through `cancel_scrape`; `events` are those found until then
+}
+
+// Result of `get_events_near`, nearest first.
+export interface NearbyEvent {
+  event: EventData;
+  distance_km: number;
+}
+
+// A recurring event's dates from `query_event_series`; events that don't repeat have one occurrence.
+export interface EventSeries {
+  id: string;
+  title: string;
+  occurrences: EventData[]; // Earliest first
+}
+
+// A place events happen at, from `get_venues`. `get_venue_events` lists its upcoming events.
+export interface Venue {
+  id: number;
+  name: string;
+  normalized_name: string;
+  address?: string;
+  street?: string;
+  postal_code?: string; // e.g. "5611 ET"
+  locality?: string;
+  latitude?: number;
+  longitude?: number;
+  aliases: string[]; // Other spellings seen for this venue
+  event_count: number;
+}
+
+// Error returned by failing Tauri commands (and the REST API), see src-tauri/src/error.rs.
+export type AppErrorCode =
+  | "network"
+  | "timeout"
+  | "http_status"
+  | "robots_disallowed"
+  | "parse"
+  | "cache_io"
+  | "io"
+  | "missing_field"
+  | "timezone"
+  | "not_found"
+  | "busy"
+  | "invalid_request"
+  | "internal";
+
+export interface AppError {
+  code: AppErrorCode;
+  message: string;
+  url?: string;      // network, timeout, http_status, robots_disallowed
+  status?: number;   // http_status
+  event_id?: string; // missing_field
+  field?: string;    // missing_field: the EventData field, e.g. "start_date"
+  datetime?: string; // timezone
+  timezone?: string; // timezone
+}
+
+export function isAppError(e: unknown): e is AppError {
+  return typeof e === "object" && e !== null && "code" in e && "message" in e;
+}
@@ -0,0 +1,24 @@
+// src/utils/priceUtils.ts
+import { EventData } from "../types";
+
+// Cheapest regular ticket, for sorting and the price slider. Undefined when the price is unknown.
+export const lowestPrice = (event: EventData): number | undefined => {
+  const price = event.parsed_price;
+  if (!price) return undefined;
+  switch (price.kind) {
+    case "free":
+      return 0;
+    case "amount":
+    case "from":
+      return price.amount;
+    case "range":
+      return price.min;
+    case "donation":
+      return price.suggested ?? 0;
+    default:
+      return undefined;
+  }
+};
+
+export const isFreeEvent = (event: EventData): boolean =>
+  event.parsed_price?.kind === "free";